@@ -1,8 +1,32 @@
 use scrypto::prelude::*;
 
+#[derive(NonFungibleData)]
+pub struct AccountOwnerBadge {}
+
+/// Returns the public key non-fungible, if the rule only requires a virtual key.
+fn virtual_key(withdraw_rule: &AccessRule) -> Option<NonFungibleAddress> {
+    match withdraw_rule {
+        AccessRule::Protected(AccessRuleNode::ProofRule(ProofRule::Require(
+            SoftResourceOrNonFungible::StaticNonFungible(non_fungible_address),
+        ))) if non_fungible_address.resource_address() == ECDSA_TOKEN => {
+            Some(non_fungible_address.clone())
+        }
+        _ => None,
+    }
+}
+
 blueprint! {
     struct Account {
         vaults: LazyMap<ResourceAddress, Vault>,
+        /// Which resources are accepted as deposits, unless overridden by `deposit_rules`.
+        ///
+        /// Account state created by an account package without deposit policies has neither
+        /// field and can't be decoded by this one. Ledgers keep the account package they were
+        /// bootstrapped with, so existing ledgers need a reset (e.g. `resim reset`) to use them.
+        deposit_mode: AccountDepositMode,
+        deposit_rules: HashMap<ResourceAddress, ResourceDepositRule>,
+        /// The owner of a securifiable account, either its virtual key or its owner badge.
+        owner: Vec<NonFungibleAddress>,
     }
 
     impl Account {
@@ -12,13 +36,30 @@ blueprint! {
                 vaults.insert(b.resource_address(), Vault::with_bucket(b));
             }
 
+            // Accounts owned by a virtual key check the owner in their state, so they can be securified
+            let owner: Vec<NonFungibleAddress> = virtual_key(&withdraw_rule).into_iter().collect();
+            let withdraw_rule = if owner.is_empty() {
+                withdraw_rule
+            } else {
+                rule!(require_any_of("owner"))
+            };
+
             let access_rules = AccessRules::new()
                 .method("balance", rule!(allow_all))
                 .method("deposit", rule!(allow_all))
                 .method("deposit_batch", rule!(allow_all))
+                .method("accepts_deposit", rule!(allow_all))
                 .default(withdraw_rule);
 
-            Self { vaults }.instantiate().add_access_check(access_rules).globalize()
+            Self {
+                vaults,
+                deposit_mode: AccountDepositMode::AcceptAll,
+                deposit_rules: HashMap::new(),
+                owner,
+            }
+            .instantiate()
+            .add_access_check(access_rules)
+            .globalize()
         }
 
         pub fn new(withdraw_rule: AccessRule) -> ComponentAddress {
@@ -36,9 +77,39 @@ blueprint! {
                 .unwrap_or_default()
         }
 
+        /// Checks if this account accepts deposits of the given resource.
+        pub fn accepts_deposit(&self, resource_address: ResourceAddress) -> bool {
+            self.deposit_mode
+                .accepts(self.deposit_rules.get(&resource_address).cloned())
+        }
+
+        /// Sets which resources this account accepts as deposits.
+        pub fn set_deposit_mode(&mut self, deposit_mode: AccountDepositMode) {
+            self.deposit_mode = deposit_mode;
+        }
+
+        /// Allows or denies deposits of a resource, overriding the deposit mode.
+        pub fn set_resource_deposit_rule(
+            &mut self,
+            resource_address: ResourceAddress,
+            rule: ResourceDepositRule,
+        ) {
+            self.deposit_rules.insert(resource_address, rule);
+        }
+
+        /// Removes the deposit rule of a resource.
+        pub fn remove_resource_deposit_rule(&mut self, resource_address: ResourceAddress) {
+            self.deposit_rules.remove(&resource_address);
+        }
+
         /// Deposits resource into this account.
+        ///
+        /// Panics if the resource is not accepted by the deposit mode of this account.
         pub fn deposit(&mut self, bucket: Bucket) {
             let resource_address = bucket.resource_address();
+            if !self.accepts_deposit(resource_address) {
+                panic!("Deposits of resource {} are not accepted", resource_address);
+            }
             match self.vaults.get(&resource_address) {
                 Some(mut v) => {
                     v.put(bucket);
@@ -83,6 +154,25 @@ blueprint! {
             }
         }
 
+        /// Withdraws resources from this account, by amount.
+        pub fn withdraw_batch(&mut self, resources: Vec<(Decimal, ResourceAddress)>) -> Vec<Bucket> {
+            resources
+                .into_iter()
+                .map(|(amount, resource_address)| self.withdraw_by_amount(amount, resource_address))
+                .collect()
+        }
+
+        /// Locks some amount of XRD in this account to pay the transaction fee.
+        pub fn lock_fee(&mut self, amount: Decimal) {
+            let vault = self.vaults.get(&RADIX_TOKEN);
+            match vault {
+                Some(mut vault) => vault.lock_fee(amount),
+                None => {
+                    panic!("No XRD in account");
+                }
+            }
+        }
+
         /// Withdraws resource from this account, by non-fungible ids.
         pub fn withdraw_by_ids(
             &mut self,
@@ -98,6 +188,26 @@ blueprint! {
             }
         }
 
+        /// Replaces the virtual key owning this account with a newly minted owner badge.
+        ///
+        /// The returned badge is required for any further withdrawal from this account.
+        pub fn securify(&mut self) -> Bucket {
+            match self.owner.first() {
+                Some(owner) if owner.resource_address() == ECDSA_TOKEN => {}
+                _ => panic!("Account is not owned by a virtual key"),
+            }
+
+            let badge = ResourceBuilder::new_non_fungible()
+                .metadata("name", "Account Owner Badge")
+                .id_type(NonFungibleIdType::U32)
+                .initial_supply([(NonFungibleId::from_u32(1), AccountOwnerBadge {})]);
+            self.owner = vec![NonFungibleAddress::new(
+                badge.resource_address(),
+                NonFungibleId::from_u32(1),
+            )];
+            badge
+        }
+
         /// Create proof of resource.
         pub fn create_proof(&self, resource_address: ResourceAddress) -> Proof {
             let vault = self.vaults.get(&resource_address);
@@ -19,7 +19,14 @@ blueprint! {
             access_rules: HashMap<ResourceMethod, (AccessRule, Mutability)>,
             initial_supply: Option<MintParams>,
         ) -> (ResourceAddress, Option<Bucket>) {
-            resource_system().new_resource(resource_type, metadata, access_rules, initial_supply)
+            resource_system().new_resource(
+                resource_type,
+                metadata,
+                access_rules,
+                initial_supply,
+                None,
+                None,
+            )
         }
 
         /// Mints fungible resource. TODO: Remove
@@ -0,0 +1,57 @@
+use scrypto::core::{Level, SNodeRef};
+use scrypto::engine::types::*;
+use scrypto::values::*;
+
+use crate::engine::*;
+use crate::errors::RuntimeError;
+use crate::model::*;
+
+/// Observes the execution of transactions, e.g. for profiling or coverage analysis.
+///
+/// Every callback does nothing by default, so an observer only implements the ones it needs.
+/// When no observer is attached to the executor, none of the callbacks are made.
+pub trait ExecutionObserver {
+    /// Called before a function/method is invoked, at the call depth of the callee.
+    fn on_invoke_enter(
+        &mut self,
+        _depth: usize,
+        _snode_ref: &SNodeRef,
+        _function: &str,
+        _args: &[ScryptoValue],
+    ) {
+    }
+
+    /// Called after a function/method invocation has returned or failed.
+    fn on_invoke_exit(
+        &mut self,
+        _depth: usize,
+        _snode_ref: &SNodeRef,
+        _function: &str,
+        _result: Result<&ScryptoValue, &RuntimeError>,
+    ) {
+    }
+
+    /// Called before a call from a blueprint into the engine is handled.
+    fn on_host_call(&mut self, _depth: usize, _operation: u32) {}
+
+    /// Called when a substate is read through the track.
+    fn on_substate_read(&mut self, _substate_id: &SubstateId) {}
+
+    /// Called when a substate is written through the track.
+    fn on_substate_write(&mut self, _substate_id: &SubstateId) {}
+
+    /// Called when a bucket is created.
+    fn on_bucket_created(&mut self, _bucket_id: BucketId, _bucket: &Bucket) {}
+
+    /// Called before a bucket is burnt.
+    fn on_bucket_dropped(&mut self, _bucket_id: BucketId, _bucket: &Bucket) {}
+
+    /// Called when a proof is created.
+    fn on_proof_created(&mut self, _proof_id: ProofId, _proof: &Proof) {}
+
+    /// Called before a proof is dropped.
+    fn on_proof_dropped(&mut self, _proof_id: ProofId, _proof: &Proof) {}
+
+    /// Called when a log message is emitted.
+    fn on_log(&mut self, _level: Level, _message: &str) {}
+}
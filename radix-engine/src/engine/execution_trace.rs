@@ -0,0 +1,181 @@
+use scrypto::core::{SNodeRef, ScryptoActorInfo};
+use scrypto::engine::types::*;
+use scrypto::resource::NonFungibleAddress;
+use scrypto::rust::collections::*;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+use scrypto::values::*;
+
+use crate::model::*;
+
+/// Identifies a substate accessed through the track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubstateId {
+    Package(PackageAddress),
+    Component(ComponentAddress),
+    ResourceManager(ResourceAddress),
+    Vault(ComponentAddress, VaultId),
+    NonFungible(NonFungibleAddress),
+    LazyMapEntry(ComponentAddress, LazyMapId, Vec<u8>),
+}
+
+/// A read or write of a substate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubstateAccess {
+    Read(SubstateId),
+    Write(SubstateId),
+}
+
+/// A bucket or proof moved between a caller and a callee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracedResource {
+    /// The bucket or proof id, in the process it's moved from
+    pub id: u32,
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+}
+
+/// A function/method call, with the calls it made in turn.
+#[derive(Debug, Clone)]
+pub struct TracedCall {
+    /// The call depth of the callee
+    pub depth: usize,
+    pub snode_ref: SNodeRef,
+    pub function: String,
+    /// The callee's actor, if it's a blueprint or component and could be loaded
+    pub actor: Option<ScryptoActorInfo>,
+    pub args: Vec<ScryptoValue>,
+    /// The return value, empty if the call failed
+    pub output: Option<ScryptoValue>,
+    /// Buckets moved from the caller to the callee
+    pub buckets_in: Vec<TracedResource>,
+    /// Proofs moved from the caller to the callee
+    pub proofs_in: Vec<TracedResource>,
+    /// Buckets moved from the callee back to the caller
+    pub buckets_out: Vec<TracedResource>,
+    /// Proofs moved from the callee back to the caller
+    pub proofs_out: Vec<TracedResource>,
+    /// Substates accessed by the call itself, in order
+    pub substates: Vec<SubstateAccess>,
+    pub calls: Vec<TracedCall>,
+}
+
+/// The execution trace of a transaction, as a tree of calls.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTrace {
+    /// The calls made by the transaction
+    pub calls: Vec<TracedCall>,
+    /// Substates accessed by the transaction outside of any call
+    pub substates: Vec<SubstateAccess>,
+}
+
+/// Records an execution trace as calls are entered and exited.
+#[derive(Debug, Default)]
+pub struct ExecutionTraceRecorder {
+    trace: ExecutionTrace,
+    open_calls: Vec<TracedCall>,
+}
+
+impl ExecutionTraceRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a call, which becomes the current call until it's exited.
+    pub fn enter(
+        &mut self,
+        depth: usize,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
+    ) {
+        self.open_calls.push(TracedCall {
+            depth,
+            snode_ref,
+            function,
+            actor: None,
+            args,
+            output: None,
+            buckets_in: Vec::new(),
+            proofs_in: Vec::new(),
+            buckets_out: Vec::new(),
+            proofs_out: Vec::new(),
+            substates: Vec::new(),
+            calls: Vec::new(),
+        });
+    }
+
+    /// Closes the current call and adds it to its caller.
+    pub fn exit(&mut self, actor: Option<ScryptoActorInfo>, output: Option<ScryptoValue>) {
+        let mut call = self.open_calls.pop().expect("No call to exit");
+        call.actor = actor;
+        call.output = output;
+        match self.open_calls.last_mut() {
+            Some(caller) => caller.calls.push(call),
+            None => self.trace.calls.push(call),
+        }
+    }
+
+    /// Records the buckets and proofs moved into the current call.
+    pub fn record_moved_in(
+        &mut self,
+        buckets: &HashMap<BucketId, Bucket>,
+        proofs: &HashMap<ProofId, Proof>,
+    ) {
+        if let Some(call) = self.open_calls.last_mut() {
+            call.buckets_in = Self::traced_buckets(buckets);
+            call.proofs_in = Self::traced_proofs(proofs);
+        }
+    }
+
+    /// Records the buckets and proofs moved out of the current call.
+    pub fn record_moved_out(
+        &mut self,
+        buckets: &HashMap<BucketId, Bucket>,
+        proofs: &HashMap<ProofId, Proof>,
+    ) {
+        if let Some(call) = self.open_calls.last_mut() {
+            call.buckets_out = Self::traced_buckets(buckets);
+            call.proofs_out = Self::traced_proofs(proofs);
+        }
+    }
+
+    /// Records a substate access by the current call.
+    pub fn record_substate(&mut self, access: SubstateAccess) {
+        match self.open_calls.last_mut() {
+            Some(call) => call.substates.push(access),
+            None => self.trace.substates.push(access),
+        }
+    }
+
+    /// Returns the recorded trace.
+    pub fn finish(self) -> ExecutionTrace {
+        self.trace
+    }
+
+    fn traced_buckets(buckets: &HashMap<BucketId, Bucket>) -> Vec<TracedResource> {
+        let mut traced: Vec<TracedResource> = buckets
+            .iter()
+            .map(|(bucket_id, bucket)| TracedResource {
+                id: *bucket_id,
+                resource_address: bucket.resource_address(),
+                amount: bucket.total_amount(),
+            })
+            .collect();
+        traced.sort_by_key(|resource| resource.id);
+        traced
+    }
+
+    fn traced_proofs(proofs: &HashMap<ProofId, Proof>) -> Vec<TracedResource> {
+        let mut traced: Vec<TracedResource> = proofs
+            .iter()
+            .map(|(proof_id, proof)| TracedResource {
+                id: *proof_id,
+                resource_address: proof.resource_address(),
+                amount: proof.total_amount(),
+            })
+            .collect();
+        traced.sort_by_key(|resource| resource.id);
+        traced
+    }
+}
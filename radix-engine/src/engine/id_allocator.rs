@@ -1,9 +1,23 @@
-use scrypto::crypto::hash;
+use sbor::decode_with_type;
+use scrypto::crypto::{hash, EcdsaPublicKey};
 use scrypto::engine::types::*;
 use scrypto::rust::ops::Range;
+use scrypto::rust::vec::Vec;
 
 pub const ECDSA_TOKEN_BUCKET_ID: BucketId = 0;
 
+/// The first byte of virtual account addresses, which allocated component addresses never have.
+///
+/// Calling a component which doesn't exist at such an address creates the virtual account.
+pub const VIRTUAL_ACCOUNT_ADDRESS_PREFIX: u8 = 0x0a;
+
+/// Returns the 25 byte hash of a public key, as used in virtual account addresses.
+fn public_key_hash(public_key: &[u8]) -> [u8; 25] {
+    let mut result = [0u8; 25];
+    result.copy_from_slice(&hash(public_key).0[7..32]);
+    result
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IdSpace {
     System,
@@ -60,7 +74,52 @@ impl IdAllocator {
     ) -> Result<ComponentAddress, IdAllocatorError> {
         let mut data = transaction_hash.to_vec();
         data.extend(self.next()?.to_le_bytes());
-        Ok(ComponentAddress(hash(data).lower_26_bytes()))
+        let mut address = hash(data).lower_26_bytes();
+        // Leave the first byte of virtual account addresses to them
+        if address[0] == VIRTUAL_ACCOUNT_ADDRESS_PREFIX {
+            address[0] = 0;
+        }
+        Ok(ComponentAddress(address))
+    }
+
+    /// Returns the address of the virtual account owned by a public key.
+    ///
+    /// The address is `VIRTUAL_ACCOUNT_ADDRESS_PREFIX` followed by the key hash, so it's known
+    /// before the account exists and the owner can be recovered from the address alone.
+    pub fn new_virtual_account_address(public_key: &EcdsaPublicKey) -> ComponentAddress {
+        let mut address = [0u8; 26];
+        address[0] = VIRTUAL_ACCOUNT_ADDRESS_PREFIX;
+        address[1..].copy_from_slice(&public_key_hash(&public_key.to_vec()));
+        ComponentAddress(address)
+    }
+
+    /// Returns the non-fungible address a virtual account is owned by, if the address is one.
+    pub fn virtual_account_owner(
+        component_address: &ComponentAddress,
+    ) -> Option<NonFungibleAddress> {
+        if component_address.0[0] == VIRTUAL_ACCOUNT_ADDRESS_PREFIX {
+            Some(NonFungibleAddress::new(
+                ECDSA_TOKEN,
+                NonFungibleId::from_bytes(component_address.0[1..].to_vec()),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the id of the `ECDSA_TOKEN` non-fungible which owns the virtual account of a
+    /// public key.
+    pub fn public_key_hash_id(public_key: &EcdsaPublicKey) -> NonFungibleId {
+        NonFungibleId::from_bytes(public_key_hash(&public_key.to_vec()).to_vec())
+    }
+
+    /// Returns the id of the virtual account owner proven by a signer's `ECDSA_TOKEN`
+    /// non-fungible, which is identified by the public key.
+    pub fn signer_hash_id(signer: &NonFungibleId) -> Option<NonFungibleId> {
+        let public_key = decode_with_type::<Vec<u8>>(&signer.to_vec()).ok()?;
+        Some(NonFungibleId::from_bytes(
+            public_key_hash(&public_key).to_vec(),
+        ))
     }
 
     /// Creates a new resource address.
@@ -103,3 +162,18 @@ impl IdAllocator {
         Ok((transaction_hash, self.next()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_addresses_are_not_virtual_accounts() {
+        let mut id_allocator = IdAllocator::new(IdSpace::Application);
+        for _ in 0..2048 {
+            let component_address = id_allocator.new_component_address(hash("tx")).unwrap();
+            assert_ne!(component_address.0[0], VIRTUAL_ACCOUNT_ADDRESS_PREFIX);
+            assert_eq!(IdAllocator::virtual_account_owner(&component_address), None);
+        }
+    }
+}
@@ -1,4 +1,6 @@
 mod component_objects;
+mod execution_observer;
+mod execution_trace;
 mod id_allocator;
 mod id_validator;
 mod process;
@@ -6,8 +8,10 @@ mod track;
 mod wasm_env;
 
 pub use component_objects::*;
+pub use execution_observer::ExecutionObserver;
+pub use execution_trace::*;
 pub use id_allocator::*;
 pub use id_validator::*;
-pub use process::{Process, SNodeState, SystemApi};
+pub use process::{CallFrame, Process, SNodeState, SystemApi};
 pub use track::{CommitReceipt, Track};
 pub use wasm_env::{EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME};
@@ -3,10 +3,15 @@ use colored::*;
 use sbor::*;
 use sbor::path::SborPath;
 use scrypto::buffer::*;
+use scrypto::constants::*;
 use scrypto::core::{SNodeRef, ScryptoActor};
+use scrypto::crypto::EcdsaPublicKey;
 use scrypto::engine::api::*;
 use scrypto::engine::types::*;
+use scrypto::resource::{require, NonFungibleAddress, SchemaPath};
+use scrypto::{access_rule_node, rule};
 use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::boxed::Box;
 use scrypto::rust::collections::*;
 use scrypto::rust::fmt;
 use scrypto::rust::format;
@@ -85,8 +90,28 @@ pub trait SystemApi {
         resource_manager: ResourceManager,
     );
 
+    fn borrow_global_mut_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+    ) -> Result<Vault, RuntimeError>;
+
+    fn return_borrowed_global_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        vault: Vault,
+    );
+
     fn create_bucket(&mut self, container: ResourceContainer) -> Result<BucketId, RuntimeError>;
 
+    fn lock_fee(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        fee: ResourceContainer,
+    ) -> Result<(), VaultError>;
+
     fn take_bucket(&mut self, bucket_id: BucketId) -> Result<Bucket, RuntimeError>;
 
     fn create_proof(&mut self, proof: Proof) -> Result<ProofId, RuntimeError>;
@@ -96,6 +121,38 @@ pub trait SystemApi {
     fn create_resource(&mut self, resource_manager: ResourceManager) -> ResourceAddress;
 
     fn create_package(&mut self, package: Package) -> PackageAddress;
+
+    fn create_virtual_account(
+        &mut self,
+        public_key: EcdsaPublicKey,
+    ) -> Result<ComponentAddress, RuntimeError>;
+
+    fn invoke_subintent(
+        &mut self,
+        subintent: ValidatedSubintent,
+        bucket_ids: Vec<BucketId>,
+    ) -> Result<ScryptoValue, RuntimeError>;
+
+    fn read_component_state(
+        &mut self,
+        component_address: ComponentAddress,
+        path: &SchemaPath,
+    ) -> Result<Option<ScryptoValue>, RuntimeError>;
+
+    fn read_vault_amount(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: &VaultId,
+    ) -> Result<Decimal, RuntimeError>;
+
+    fn read_lazy_map_entry(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        key: &[u8],
+    ) -> Option<Vec<u8>>;
+
+    fn current_epoch(&self) -> u64;
 }
 
 pub enum SNodeState {
@@ -142,6 +199,8 @@ struct WasmProcess<'a> {
     trace: bool,
     vm: Interpreter,
     interpreter_state: InterpreterState<'a>,
+    /// The message of a panic reported by the blueprint, before it traps
+    panic_message: Option<String>,
 }
 
 ///TODO: Remove
@@ -182,6 +241,19 @@ pub enum MoveMethod {
     AsArgument,
 }
 
+/// A call an error propagated through, as recorded in `RuntimeError::CallFailed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallFrame {
+    /// The call depth of the callee
+    pub depth: usize,
+    /// The called snode
+    pub snode_ref: SNodeRef,
+    /// The called function or method
+    pub function: String,
+    /// The callee's actor, if it's a blueprint or component and could be loaded
+    pub actor: Option<ScryptoActorInfo>,
+}
+
 /// A process keeps track of resource movements and code execution.
 pub struct Process<'r, 'l, L: SubstateStore> {
     /// The call depth
@@ -201,8 +273,14 @@ pub struct Process<'r, 'l, L: SubstateStore> {
     worktop: Option<Worktop>,
     auth_zone: Option<AuthZone>,
 
-    /// The caller's auth zone
-    caller_auth_zone: Option<&'r AuthZone>,
+    /// The proofs lent to this process by its caller, as an auth zone of their own
+    lent_auth_zone: Option<&'r AuthZone>,
+
+    /// The auth zones of the caller, i.e. its own auth zone and the proofs lent to it
+    caller_auth_zones: Vec<&'r AuthZone>,
+
+    /// The caller's actor, empty if called by the transaction
+    caller_actor: Option<ScryptoActorInfo>,
 
     /// State for the given wasm process, empty only on the root process
     /// (root process cannot create components nor is a component itself)
@@ -230,7 +308,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             worktop,
             auth_zone,
             snode_refs: ComponentObjectRefs::new(),
-            caller_auth_zone: None,
+            lent_auth_zone: None,
+            caller_auth_zones: Vec::new(),
+            caller_actor: None,
             wasm_process_state: None,
         }
     }
@@ -261,6 +341,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         let now = std::time::Instant::now();
         re_info!(self, "Run started: function = {:?}", function);
 
+        // Blueprint functions and component methods are barriers for proofs
+        let is_barrier = matches!(snode, SNodeState::Scrypto(_, _));
+
         // Execution
         let output = match snode {
             SNodeState::Transaction(transaction_process) => {
@@ -324,6 +407,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         memory,
                     },
                     interpreter_state,
+                    panic_message: None,
                 });
 
                 // Execution
@@ -333,10 +417,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 re_debug!(self, "Invoke result: {:?}", result);
                 let rtn = result
                     .map_err(|e| {
-                        match e.into_host_error() {
+                        match e.try_into_host_error() {
                             // Pass-through runtime errors
-                            Some(host_error) => *host_error.downcast::<RuntimeError>().unwrap(),
-                            None => RuntimeError::InvokeError,
+                            Ok(host_error) => *host_error.downcast::<RuntimeError>().unwrap(),
+                            Err(Error::Trap(trap)) => self.blueprint_trap_error(&trap, actor),
+                            Err(_) => RuntimeError::InvokeError,
                         }
                     })?
                     .ok_or(RuntimeError::NoReturnData)?;
@@ -362,9 +447,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             SNodeState::ProofRef(_, proof) => proof
                 .main(function.as_str(), args, self)
                 .map_err(RuntimeError::ProofError),
-            SNodeState::VaultRef(vault_id, _, vault) =>
+            SNodeState::VaultRef(vault_id, component_address, vault) =>
                 vault
-                    .main(*vault_id, function.as_str(), args, self)
+                    .main(*vault_id, *component_address, function.as_str(), args, self)
                     .map_err(RuntimeError::VaultError),
             _ => Err(RuntimeError::IllegalSystemCall),
         }?;
@@ -373,10 +458,13 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
         // figure out what buckets and resources to return
         let moving_buckets = self.send_buckets(&output.bucket_ids)?;
-        let moving_proofs = self.send_proofs(&output.proof_ids, MoveMethod::AsReturn)?;
+        let moving_proofs =
+            self.send_proofs(&output.proof_ids, MoveMethod::AsReturn, is_barrier)?;
 
         // drop proofs and check resource leak
-        for (_, proof) in self.proofs.drain() {
+        for (proof_id, proof) in self.proofs.drain() {
+            self.track
+                .observe(|observer| observer.on_proof_dropped(proof_id, &proof));
             proof.drop();
         }
 
@@ -397,6 +485,50 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok((output, moving_buckets, moving_proofs))
     }
 
+    /// Instantiates an account at a virtual account address, withdrawable by the given owner.
+    fn new_virtual_account(
+        &mut self,
+        component_address: ComponentAddress,
+        owner: NonFungibleAddress,
+    ) -> Result<(), RuntimeError> {
+        let withdraw_rule = rule!(require(owner));
+        self.track.set_reserved_component_address(Some(component_address));
+        let result = self.invoke_snode(
+            SNodeRef::Scrypto(ScryptoActor::Blueprint(
+                ACCOUNT_PACKAGE,
+                "Account".to_owned(),
+            )),
+            "new".to_owned(),
+            vec![ScryptoValue::from_value(&withdraw_rule)],
+        );
+        self.track.set_reserved_component_address(None);
+        result.map(|_| ())
+    }
+
+    /// Converts a trap of a blueprint into an error, reporting the panic message the blueprint
+    /// forwarded if there is one.
+    fn blueprint_trap_error(&mut self, trap: &Trap, actor: &ScryptoActorInfo) -> RuntimeError {
+        let panic_message = self
+            .wasm_process_state
+            .as_mut()
+            .and_then(|wasm_process| wasm_process.panic_message.take());
+        match panic_message {
+            Some(message) => RuntimeError::BlueprintPanic {
+                actor: Box::new(actor.clone()),
+                message,
+            },
+            None => RuntimeError::WasmTrap {
+                kind: trap.kind().into(),
+                actor: Box::new(actor.clone()),
+            },
+        }
+    }
+
+    /// Returns the auth zones of this process: its own auth zone and the proofs lent to it.
+    fn auth_zones(&self) -> Vec<&AuthZone> {
+        self.auth_zone.iter().chain(self.lent_auth_zone).collect()
+    }
+
     /// Calls a function/method.
     pub fn invoke_snode(
         &mut self,
@@ -404,6 +536,83 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         function: String,
         args: Vec<ScryptoValue>,
     ) -> Result<ScryptoValue, RuntimeError> {
+        self.invoke_snode_with_proofs(snode_ref, function, args, Vec::new())
+    }
+
+    /// Calls a function/method, lending the given proofs to it for the duration of the call.
+    ///
+    /// Authorization is evaluated against the auth zones of the calling process, i.e. its own
+    /// auth zone and the proofs lent to it, and the proofs being lent. Resource operations also
+    /// consider the auth zones of the caller of the calling process. The lent proofs can only
+    /// be used for authorization by the callee and are given back once the call returns, so
+    /// restricted proofs can be lent as well.
+    ///
+    /// If the call fails, the error is wrapped in `RuntimeError::CallFailed`, with a frame
+    /// describing the call added to its call stack.
+    pub fn invoke_snode_with_proofs(
+        &mut self,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
+        lent_proof_ids: Vec<ProofId>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        let depth = self.depth + 1;
+        self.track
+            .observe(|observer| observer.on_invoke_enter(depth, &snode_ref, &function, &args));
+        if let Some(recorder) = self.track.execution_trace_recorder() {
+            recorder.enter(depth, snode_ref.clone(), function.clone(), args.clone());
+        }
+
+        let mut actor = None;
+        let result = self.invoke_snode_in_frame(
+            snode_ref.clone(),
+            function.clone(),
+            args,
+            lent_proof_ids,
+            &mut actor,
+        );
+
+        self.track.observe(|observer| {
+            observer.on_invoke_exit(depth, &snode_ref, &function, result.as_ref())
+        });
+        if let Some(recorder) = self.track.execution_trace_recorder() {
+            recorder.exit(actor.clone(), result.as_ref().ok().cloned());
+        }
+        result.map_err(|error| {
+            error.with_call_frame(CallFrame {
+                depth,
+                snode_ref,
+                function,
+                actor,
+            })
+        })
+    }
+
+    fn invoke_snode_in_frame(
+        &mut self,
+        snode_ref: SNodeRef,
+        function: String,
+        args: Vec<ScryptoValue>,
+        lent_proof_ids: Vec<ProofId>,
+        actor: &mut Option<ScryptoActorInfo>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        // Only blueprint functions and component methods can be lent proofs
+        let lent_auth_zone = if lent_proof_ids.is_empty() {
+            None
+        } else if matches!(snode_ref, SNodeRef::Scrypto(_)) {
+            let mut lent_proofs = Vec::new();
+            for proof_id in &lent_proof_ids {
+                let proof = self
+                    .proofs
+                    .remove(proof_id)
+                    .ok_or(RuntimeError::ProofNotFound(*proof_id))?;
+                lent_proofs.push(proof);
+            }
+            Some(AuthZone::new_with_proofs(lent_proofs))
+        } else {
+            return Err(RuntimeError::IllegalSystemCall);
+        };
+
         // Authorization and state load
         let (mut snode, method_auths) = match &snode_ref {
             SNodeRef::PackageStatic => Ok((SNodeState::PackageStatic, vec![])),
@@ -438,9 +647,20 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         ))
                     }
                     ScryptoActor::Component(component_address) => {
-                        let component = self
+                        let component = match self
                             .track
-                            .borrow_global_mut_component(component_address.clone())?;
+                            .borrow_global_mut_component(component_address.clone())
+                        {
+                            Err(RuntimeError::ComponentNotFound(_)) => {
+                                // Virtual accounts are created by the first call to them
+                                let owner = IdAllocator::virtual_account_owner(component_address)
+                                    .ok_or(RuntimeError::ComponentNotFound(*component_address))?;
+                                self.new_virtual_account(*component_address, owner)?;
+                                self.track
+                                    .borrow_global_mut_component(component_address.clone())?
+                            }
+                            result => result?,
+                        };
                         let package_address = component.package_address();
                         let blueprint_name = component.blueprint_name().to_string();
                         let export_name = format!("{}_main", blueprint_name);
@@ -455,7 +675,14 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                             .unwrap()
                             .clone();
 
-                        let (_, method_auths) = component.method_authorization(&schema, &function);
+                        let track = &mut self.track;
+                        let (_, method_auths) = component.method_authorization(
+                            &schema,
+                            &function,
+                            &mut |lazy_map_id, key| {
+                                track.get_lazy_map_entry(*component_address, lazy_map_id, key)
+                            },
+                        );
                         Ok((
                             SNodeState::Scrypto(
                                 ScryptoActorInfo::component(
@@ -543,19 +770,26 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 ))
             }
         }?;
+        if let SNodeState::Scrypto(actor_info, _) = &snode {
+            *actor = Some(actor_info.clone());
+        }
 
         // Authorization check
         if !method_auths.is_empty() {
-            let mut auth_zones = Vec::new();
-            if let Some(self_auth_zone) = &self.auth_zone {
-                auth_zones.push(self_auth_zone);
+            let mut auth_zones = self.auth_zones();
+            auth_zones.extend(lent_auth_zone.iter());
+            let mut callers = Vec::new();
+            if let Some(wasm_process) = &self.wasm_process_state {
+                callers.push(&wasm_process.vm.actor);
             }
 
             match &snode {
                 // Resource auth check includes caller
                 SNodeState::ResourceRef(_, _) | SNodeState::VaultRef(_, _, _) | SNodeState::BucketRef(_, _) | SNodeState::Bucket(_) => {
-                    if let Some(auth_zone) = self.caller_auth_zone {
-                        auth_zones.push(auth_zone);
+                    auth_zones.extend(self.caller_auth_zones.iter().copied());
+                    // Native snodes call on behalf of the actor which called them
+                    if callers.is_empty() {
+                        callers.extend(self.caller_actor.iter());
                     }
                 }
                 // Extern call auth check
@@ -563,34 +797,55 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             };
 
             for method_auth in method_auths {
-                method_auth
-                    .check(&auth_zones)
-                    .map_err(|error| RuntimeError::AuthorizationError {
+                if let Err(error) = method_auth.check(&auth_zones, &callers) {
+                    // Only failed checks are traced, as the trace is reported with the error
+                    let (_, trace) = method_auth.check_with_trace(&auth_zones, &callers);
+                    return Err(RuntimeError::AuthorizationError {
                         function: function.clone(),
-                        authorization: method_auth,
-                        error
-                    })?;
+                        authorization: Box::new(method_auth),
+                        error,
+                        trace: Box::new(trace),
+                    });
+                }
             }
         }
 
         // Execution
         let result = match snode {
             SNodeState::Proof(proof) => {
+                if let (SNodeRef::Proof(proof_id), "drop") = (&snode_ref, function.as_str()) {
+                    self.track
+                        .observe(|observer| observer.on_proof_dropped(*proof_id, &proof));
+                }
                 proof.main_consume(function.as_str())
                     .map_err(RuntimeError::ProofError)
             },
             SNodeState::Bucket(bucket) => match function.as_str() {
-                "burn" => bucket.drop(self).map_err(RuntimeError::BucketError),
+                "burn" => {
+                    if let SNodeRef::Bucket(bucket_id) = &snode_ref {
+                        self.track
+                            .observe(|observer| observer.on_bucket_dropped(*bucket_id, &bucket));
+                    }
+                    bucket.drop(self).map_err(RuntimeError::BucketError)
+                }
                 _ => Err(RuntimeError::IllegalSystemCall),
             },
             _ => {
                 // Figure out what buckets and proofs to move from this process
                 let mut moving_buckets = HashMap::new();
                 let mut moving_proofs = HashMap::new();
+                let is_barrier = matches!(snode, SNodeState::Scrypto(_, _));
                 for arg in &args {
                     self.process_call_data(arg)?;
                     moving_buckets.extend(self.send_buckets(&arg.bucket_ids)?);
-                    moving_proofs.extend(self.send_proofs(&arg.proof_ids, MoveMethod::AsArgument)?);
+                    moving_proofs.extend(self.send_proofs(
+                        &arg.proof_ids,
+                        MoveMethod::AsArgument,
+                        is_barrier,
+                    )?);
+                }
+                if let Some(recorder) = self.track.execution_trace_recorder() {
+                    recorder.record_moved_in(&moving_buckets, &moving_proofs);
                 }
 
                 // start a new process
@@ -609,18 +864,31 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                     moving_buckets,
                     moving_proofs,
                 );
-                if let Some(auth_zone) = &self.auth_zone {
-                    process.caller_auth_zone = Option::Some(auth_zone);
-                }
+                process.lent_auth_zone = lent_auth_zone.as_ref();
+                process.caller_auth_zones =
+                    self.auth_zone.iter().chain(self.lent_auth_zone).collect();
+                process.caller_actor = self
+                    .wasm_process_state
+                    .as_ref()
+                    .map(|wasm_process| wasm_process.vm.actor.clone());
 
                 // invoke the main function
                 let (result, received_buckets, received_proofs) =
                     process.run(&mut snode, function, args)?;
+                if let Some(recorder) = self.track.execution_trace_recorder() {
+                    recorder.record_moved_out(&received_buckets, &received_proofs);
+                }
 
                 // move buckets and proofs to this process.
                 self.buckets.extend(received_buckets);
                 self.proofs.extend(received_proofs);
 
+                // give back the lent proofs
+                if let Some(lent_auth_zone) = lent_auth_zone {
+                    self.proofs
+                        .extend(lent_proof_ids.into_iter().zip(lent_auth_zone.proofs));
+                }
+
                 // Return borrowed snodes
                 match snode {
                     SNodeState::AuthZone(auth_zone) => {
@@ -666,6 +934,51 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(result)
     }
 
+    /// Runs a subintent with its own worktop and auth zone, moving the given buckets onto its worktop.
+    pub fn invoke_subintent(
+        &mut self,
+        subintent: ValidatedSubintent,
+        bucket_ids: Vec<BucketId>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        // Only transaction processes can yield to a subintent
+        if self.worktop.is_none() {
+            return Err(RuntimeError::IllegalSystemCall);
+        }
+
+        let mut worktop = Worktop::new();
+        for bucket_id in bucket_ids {
+            let bucket = self
+                .buckets
+                .remove(&bucket_id)
+                .ok_or(RuntimeError::BucketNotFound(bucket_id))?;
+            if bucket.is_locked() {
+                return Err(RuntimeError::CantMoveLockedBucket);
+            }
+            worktop
+                .put(bucket)
+                .map_err(|e| RuntimeError::WorktopError(WorktopError::ResourceContainerError(e)))?;
+        }
+
+        let auth_zone = AuthZone::new_with_signers(&subintent.signers);
+        let mut snode = SNodeState::Transaction(TransactionProcess::new_subintent(subintent));
+        let mut process = Process::new(
+            self.depth + 1,
+            self.trace,
+            self.track,
+            Some(auth_zone),
+            Some(worktop),
+            HashMap::new(),
+            HashMap::new(),
+        );
+        let (result, received_buckets, received_proofs) =
+            process.run(&mut snode, "execute".to_string(), vec![])?;
+
+        self.buckets.extend(received_buckets);
+        self.proofs.extend(received_proofs);
+
+        Ok(result)
+    }
+
     /// Calls the ABI generator of a blueprint.
     // TODO: Remove
     pub fn call_abi(
@@ -822,10 +1135,16 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     }
 
     /// Sends proofs to another component/blueprint, either as argument or return
+    ///
+    /// Moving into or out of a blueprint function or component method crosses a barrier:
+    /// restricted proofs can't cross it, and proofs passed across it as argument become
+    /// restricted. Moving proofs to or from native objects, like the auth zone, keeps their
+    /// restriction.
     fn send_proofs(
         &mut self,
         proof_ids: &HashMap<ProofId, SborPath>,
         method: MoveMethod,
+        across_barrier: bool,
     ) -> Result<HashMap<ProofId, Proof>, RuntimeError> {
         let mut proofs = HashMap::new();
         for (proof_id, _) in proof_ids {
@@ -834,11 +1153,13 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 .remove(proof_id)
                 .ok_or(RuntimeError::ProofNotFound(*proof_id))?;
             re_debug!(self, "Moving proof: {}, {:?}", proof_id, proof);
-            if proof.is_restricted() {
-                return Err(RuntimeError::CantMoveRestrictedProof(*proof_id));
-            }
-            if matches!(method, MoveMethod::AsArgument) {
-                proof.change_to_restricted();
+            if across_barrier {
+                if proof.is_restricted() {
+                    return Err(RuntimeError::CantMoveRestrictedProof(*proof_id));
+                }
+                if matches!(method, MoveMethod::AsArgument) {
+                    proof.change_to_restricted();
+                }
             }
             proofs.insert(*proof_id, proof);
         }
@@ -938,7 +1259,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         input: CreateComponentInput,
     ) -> Result<CreateComponentOutput, RuntimeError> {
         let data = Self::process_entry_data(&input.state)?;
-        let new_objects = self.owned_snodes.take(data)?;
+        let mut new_objects = self.owned_snodes.take(data)?;
 
         let wasm_process = self
             .wasm_process_state
@@ -951,6 +1272,36 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             input.access_rules_list,
             input.state,
         );
+
+        let package = self
+            .track
+            .get_package(&package_address)
+            .ok_or(RuntimeError::PackageNotFound(package_address))?;
+        let blueprint_not_found = || {
+            RuntimeError::BlueprintNotFound(package_address, component.blueprint_name().to_owned())
+        };
+        let schema = package
+            .load_blueprint_schema(component.blueprint_name())
+            .map_err(|_| blueprint_not_found())?;
+        let methods = package
+            .load_blueprint_methods(component.blueprint_name())
+            .map_err(|_| blueprint_not_found())?;
+        component
+            .validate_access_rules(schema, &methods)
+            .map_err(RuntimeError::InvalidAccessRules)?;
+
+        if input.fail_on_unresolved_access_rules {
+            let unresolved =
+                component.unresolved_access_rule_paths(schema, &mut |lazy_map_id, key| {
+                    new_objects
+                        .get_lazy_map_entry(lazy_map_id, key)
+                        .and_then(|(_, entry)| entry)
+                });
+            if let Some((method, path)) = unresolved.into_iter().next() {
+                return Err(RuntimeError::UnresolvedAccessRulePath { method, path });
+            }
+        }
+
         let component_address = self.track.create_component(component);
         self.track
             .insert_objects_into_component(new_objects, component_address);
@@ -1190,12 +1541,45 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(InvokeSNodeOutput { rtn: result.raw })
     }
 
+    fn handle_invoke_snode_with_proofs(
+        &mut self,
+        input: InvokeSNodeWithProofsInput,
+    ) -> Result<InvokeSNodeOutput, RuntimeError> {
+        let mut validated_args = Vec::new();
+        for arg in input.args {
+            validated_args.push(
+                ScryptoValue::from_slice(&arg).map_err(RuntimeError::ParseScryptoValueError)?,
+            );
+        }
+
+        let result = self.invoke_snode_with_proofs(
+            input.snode_ref,
+            input.function,
+            validated_args,
+            input.proof_ids,
+        )?;
+        Ok(InvokeSNodeOutput { rtn: result.raw })
+    }
+
     fn handle_emit_log(&mut self, input: EmitLogInput) -> Result<EmitLogOutput, RuntimeError> {
         self.track.add_log(input.level, input.message);
 
         Ok(EmitLogOutput {})
     }
 
+    fn handle_report_panic(
+        &mut self,
+        input: ReportPanicInput,
+    ) -> Result<ReportPanicOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::InterpreterNotStarted)?;
+        wasm_process.panic_message = Some(input.message);
+
+        Ok(ReportPanicOutput {})
+    }
+
     fn handle_get_call_data(
         &mut self,
         _input: GetCallDataInput,
@@ -1262,7 +1646,10 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         let mut simulated_auth_zone = AuthZone::new_with_proofs(proofs);
 
         let method_authorization = convert(&Type::Unit, &Value::Unit, &input.access_rule);
-        let is_authorized = method_authorization.check(&[&simulated_auth_zone]).is_ok();
+        let callers: Vec<&ScryptoActorInfo> = self.caller_actor.iter().collect();
+        let is_authorized = method_authorization
+            .check(&[&simulated_auth_zone], &callers)
+            .is_ok();
         simulated_auth_zone.main("clear", Vec::new(), self).map_err(RuntimeError::AuthZoneError)?;
 
         return Ok(CheckAccessRuleOutput{
@@ -1318,8 +1705,32 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
             .return_borrowed_global_resource_manager(resource_address, resource_manager)
     }
 
+    fn borrow_global_mut_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+    ) -> Result<Vault, RuntimeError> {
+        // Vaults which are missing or already borrowed can't be found
+        if self.track.get_vault(component_address, &vault_id).is_none() {
+            return Err(RuntimeError::VaultNotFound(vault_id));
+        }
+        Ok(self.track.borrow_vault_mut(&component_address, &vault_id))
+    }
+
+    fn return_borrowed_global_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        vault: Vault,
+    ) {
+        self.track
+            .return_borrowed_vault(&component_address, &vault_id, vault)
+    }
+
     fn create_proof(&mut self, proof: Proof) -> Result<ProofId, RuntimeError> {
         let proof_id = self.new_proof_id()?;
+        self.track
+            .observe(|observer| observer.on_proof_created(proof_id, &proof));
         self.proofs.insert(proof_id, proof);
         Ok(proof_id)
     }
@@ -1334,10 +1745,22 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
 
     fn create_bucket(&mut self, container: ResourceContainer) -> Result<BucketId, RuntimeError> {
         let bucket_id = self.new_bucket_id()?;
-        self.buckets.insert(bucket_id, Bucket::new(container));
+        let bucket = Bucket::new(container);
+        self.track
+            .observe(|observer| observer.on_bucket_created(bucket_id, &bucket));
+        self.buckets.insert(bucket_id, bucket);
         Ok(bucket_id)
     }
 
+    fn lock_fee(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        fee: ResourceContainer,
+    ) -> Result<(), VaultError> {
+        self.track.lock_fee(component_address, vault_id, fee)
+    }
+
     fn take_bucket(&mut self, bucket_id: BucketId) -> Result<Bucket, RuntimeError> {
         self.buckets
             .remove(&bucket_id)
@@ -1351,6 +1774,92 @@ impl<'r, 'l, L: SubstateStore> SystemApi for Process<'r, 'l, L> {
     fn create_package(&mut self, package: Package) -> PackageAddress {
         self.track.create_package(package)
     }
+
+    fn create_virtual_account(
+        &mut self,
+        public_key: EcdsaPublicKey,
+    ) -> Result<ComponentAddress, RuntimeError> {
+        let component_address = IdAllocator::new_virtual_account_address(&public_key);
+        if self.track.get_component(component_address).is_none() {
+            let owner = NonFungibleAddress::new(
+                ECDSA_TOKEN,
+                IdAllocator::public_key_hash_id(&public_key),
+            );
+            self.new_virtual_account(component_address, owner)?;
+        }
+
+        Ok(component_address)
+    }
+
+    fn invoke_subintent(
+        &mut self,
+        subintent: ValidatedSubintent,
+        bucket_ids: Vec<BucketId>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        self.invoke_subintent(subintent, bucket_ids)
+    }
+
+    fn read_component_state(
+        &mut self,
+        component_address: ComponentAddress,
+        path: &SchemaPath,
+    ) -> Result<Option<ScryptoValue>, RuntimeError> {
+        let component = self
+            .track
+            .get_component(component_address)
+            .ok_or(RuntimeError::ComponentNotFound(component_address))?;
+        let package_address = component.package_address();
+        let blueprint_name = component.blueprint_name().to_owned();
+        let state = ScryptoValue::from_slice(component.state())
+            .map_err(RuntimeError::ParseScryptoValueError)?;
+
+        let package = self
+            .track
+            .get_package(&package_address)
+            .ok_or(RuntimeError::PackageNotFound(package_address))?;
+        let schema = package
+            .load_blueprint_schema(&blueprint_name)
+            .map_err(RuntimeError::PackageError)?;
+        let value = match path
+            .to_sbor_path(schema)
+            .and_then(|sbor_path| sbor_path.get_from_value(&state.dom).cloned())
+        {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let mut bytes = Vec::new();
+        let mut enc = Encoder::with_type(&mut bytes);
+        encode_any(None, &value, &mut enc);
+        ScryptoValue::from_slice(&bytes)
+            .map(Some)
+            .map_err(RuntimeError::ParseScryptoValueError)
+    }
+
+    fn read_vault_amount(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: &VaultId,
+    ) -> Result<Decimal, RuntimeError> {
+        self.track
+            .get_vault(component_address, vault_id)
+            .map(Vault::total_amount)
+            .ok_or(RuntimeError::VaultNotFound(*vault_id))
+    }
+
+    fn read_lazy_map_entry(
+        &mut self,
+        component_address: ComponentAddress,
+        lazy_map_id: &LazyMapId,
+        key: &[u8],
+    ) -> Option<Vec<u8>> {
+        self.track
+            .get_lazy_map_entry(component_address, lazy_map_id, key)
+    }
+
+    fn current_epoch(&self) -> u64 {
+        self.track.current_epoch()
+    }
 }
 
 impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
@@ -1362,6 +1871,9 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
         match index {
             ENGINE_FUNCTION_INDEX => {
                 let operation: u32 = args.nth_checked(0)?;
+                let depth = self.depth;
+                self.track
+                    .observe(|observer| observer.on_host_call(depth, operation));
                 match operation {
                     CREATE_COMPONENT => self.handle(args, Self::handle_create_component),
                     GET_COMPONENT_INFO => self.handle(args, Self::handle_get_component_info),
@@ -1375,6 +1887,9 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
                     CREATE_EMPTY_VAULT => self.handle(args, Self::handle_create_vault),
 
                     INVOKE_SNODE => self.handle(args, Self::handle_invoke_snode),
+                    INVOKE_SNODE_WITH_PROOFS => {
+                        self.handle(args, Self::handle_invoke_snode_with_proofs)
+                    }
 
                     EMIT_LOG => self.handle(args, Self::handle_emit_log),
                     GET_CALL_DATA => self.handle(args, Self::handle_get_call_data),
@@ -1384,6 +1899,7 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
                     GET_ACTOR => self.handle(args, Self::handle_get_actor),
 
                     CHECK_ACCESS_RULE => self.handle(args, Self::handle_check_access_rule),
+                    REPORT_PANIC => self.handle(args, Self::handle_report_panic),
 
                     _ => Err(RuntimeError::InvalidRequestCode(operation).into()),
                 }
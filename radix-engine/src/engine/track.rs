@@ -1,5 +1,5 @@
 use indexmap::IndexMap;
-use scrypto::constants::*;
+use scrypto::buffer::scrypto_encode;
 use scrypto::engine::types::*;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
@@ -49,7 +49,10 @@ pub struct Track<'s, S: SubstateStore> {
     transaction_hash: Hash,
     transaction_signers: Vec<EcdsaPublicKey>,
     id_allocator: IdAllocator,
+    reserved_component_address: Option<ComponentAddress>,
     logs: Vec<(Level, String)>,
+    execution_trace: Option<ExecutionTraceRecorder>,
+    observer: Option<&'s mut dyn ExecutionObserver>,
 
     packages: IndexMap<PackageAddress, SubstateUpdate<Package>>,
 
@@ -61,10 +64,16 @@ pub struct Track<'s, S: SubstateStore> {
 
     vaults: HashMap<(ComponentAddress, VaultId), SubstateUpdate<Vault>>,
     borrowed_vaults: HashMap<(ComponentAddress, VaultId), Option<(Hash, u32)>>,
+    /// Vaults loaded for reading only, which are never committed
+    read_only_vaults: HashMap<(ComponentAddress, VaultId), Vault>,
 
     non_fungibles: HashMap<NonFungibleAddress, SubstateUpdate<Option<NonFungible>>>,
 
     lazy_map_entries: HashMap<(ComponentAddress, LazyMapId, Vec<u8>), SubstateUpdate<Vec<u8>>>,
+
+    /// Fees locked from vaults, which are paid even if the transaction fails
+    locked_fees: Vec<(ComponentAddress, VaultId, Decimal)>,
+    fee_reserve: Option<ResourceContainer>,
 }
 
 impl<'s, S: SubstateStore> Track<'s, S> {
@@ -78,7 +87,10 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             transaction_hash,
             transaction_signers,
             id_allocator: IdAllocator::new(IdSpace::Application),
+            reserved_component_address: None,
             logs: Vec::new(),
+            execution_trace: None,
+            observer: None,
             packages: IndexMap::new(),
             components: IndexMap::new(),
             borrowed_components: HashMap::new(),
@@ -87,40 +99,22 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             lazy_map_entries: HashMap::new(),
             vaults: HashMap::new(),
             borrowed_vaults: HashMap::new(),
+            read_only_vaults: HashMap::new(),
             non_fungibles: HashMap::new(),
+            locked_fees: Vec::new(),
+            fee_reserve: None,
         }
     }
 
     /// Start a process.
     pub fn start_process<'r>(&'r mut self, verbose: bool) -> Process<'r, 's, S> {
-        let signers: BTreeSet<NonFungibleId> = self
-            .transaction_signers
-            .clone()
-            .into_iter()
-            .map(|public_key| NonFungibleId::from_bytes(public_key.to_vec()))
-            .collect();
-
-        // With the latest change, proof amount can't be zero, thus a virtual proof is created
-        // only if there are signers.
-        //
-        // Transactions that refer to the signature virtual proof will pass static check
-        // but will fail at runtime, if there are no signers.
-        //
-        // TODO: possible to update static check to reject them early?
-        let mut initial_auth_zone_proofs = Vec::new();
-        if !signers.is_empty() {
-            // Proofs can't be zero amount
-            let mut ecdsa_bucket =
-                Bucket::new(ResourceContainer::new_non_fungible(ECDSA_TOKEN, signers));
-            let ecdsa_proof = ecdsa_bucket.create_proof(ECDSA_TOKEN_BUCKET_ID).unwrap();
-            initial_auth_zone_proofs.push(ecdsa_proof);
-        }
+        let auth_zone = AuthZone::new_with_signers(&self.transaction_signers);
 
         Process::new(
             0,
             verbose,
             self,
-            Some(AuthZone::new_with_proofs(initial_auth_zone_proofs)),
+            Some(auth_zone),
             Some(Worktop::new()),
             HashMap::new(),
             HashMap::new(),
@@ -177,11 +171,61 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
     /// Adds a log message.
     pub fn add_log(&mut self, level: Level, message: String) {
+        if let Some(observer) = &mut self.observer {
+            observer.on_log(level, &message);
+        }
         self.logs.push((level, message));
     }
 
+    /// Starts recording an execution trace.
+    pub fn start_execution_trace(&mut self) {
+        self.execution_trace = Some(ExecutionTraceRecorder::new());
+    }
+
+    /// Returns the execution trace recorder, if an execution trace is being recorded.
+    pub fn execution_trace_recorder(&mut self) -> Option<&mut ExecutionTraceRecorder> {
+        self.execution_trace.as_mut()
+    }
+
+    /// Stops recording the execution trace and returns it, if any.
+    pub fn take_execution_trace(&mut self) -> Option<ExecutionTrace> {
+        self.execution_trace
+            .take()
+            .map(ExecutionTraceRecorder::finish)
+    }
+
+    /// Attaches an observer, to be notified of the execution.
+    pub fn set_observer(&mut self, observer: &'s mut dyn ExecutionObserver) {
+        self.observer = Some(observer);
+    }
+
+    /// Notifies the observer, if any.
+    pub fn observe<F: FnOnce(&mut dyn ExecutionObserver)>(&mut self, notify: F) {
+        if let Some(observer) = &mut self.observer {
+            notify(&mut **observer);
+        }
+    }
+
+    fn record_substate<F: FnOnce() -> SubstateAccess>(&mut self, access: F) {
+        if self.execution_trace.is_none() && self.observer.is_none() {
+            return;
+        }
+
+        let access = access();
+        if let Some(observer) = &mut self.observer {
+            match &access {
+                SubstateAccess::Read(substate_id) => observer.on_substate_read(substate_id),
+                SubstateAccess::Write(substate_id) => observer.on_substate_write(substate_id),
+            }
+        }
+        if let Some(recorder) = &mut self.execution_trace {
+            recorder.record_substate(access);
+        }
+    }
+
     /// Returns an immutable reference to a package, if exists.
     pub fn get_package(&mut self, package_address: &PackageAddress) -> Option<&Package> {
+        self.record_substate(|| SubstateAccess::Read(SubstateId::Package(*package_address)));
         if self.packages.contains_key(package_address) {
             return self.packages.get(package_address).map(|p| &p.value);
         }
@@ -204,6 +248,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     /// Inserts a new package.
     pub fn create_package(&mut self, package: Package) -> PackageAddress {
         let package_address = self.new_package_address();
+        self.record_substate(|| SubstateAccess::Write(SubstateId::Package(package_address)));
         self.packages.insert(
             package_address,
             SubstateUpdate {
@@ -218,6 +263,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         component_address: ComponentAddress,
     ) -> Result<Component, RuntimeError> {
+        self.record_substate(|| SubstateAccess::Read(SubstateId::Component(component_address)));
         let maybe_component = self.components.remove(&component_address);
         if let Some(SubstateUpdate { value, prev_id }) = maybe_component {
             self.borrowed_components.insert(component_address, prev_id);
@@ -240,6 +286,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         component_address: ComponentAddress,
         component: Component,
     ) {
+        self.record_substate(|| SubstateAccess::Write(SubstateId::Component(component_address)));
         if let Some(prev_id) = self.borrowed_components.remove(&component_address) {
             self.components.insert(
                 component_address,
@@ -255,6 +302,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
     /// Returns an immutable reference to a component, if exists.
     pub fn get_component(&mut self, component_address: ComponentAddress) -> Option<&Component> {
+        self.record_substate(|| SubstateAccess::Read(SubstateId::Component(component_address)));
         if self.components.contains_key(&component_address) {
             return self.components.get(&component_address).map(|c| &c.value);
         }
@@ -275,9 +323,15 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
     }
 
+    /// Sets the address to be assigned to the next component created, or clears it.
+    pub fn set_reserved_component_address(&mut self, component_address: Option<ComponentAddress>) {
+        self.reserved_component_address = component_address;
+    }
+
     /// Inserts a new component.
     pub fn create_component(&mut self, component: Component) -> ComponentAddress {
         let component_address = self.new_component_address();
+        self.record_substate(|| SubstateAccess::Write(SubstateId::Component(component_address)));
         self.components.insert(
             component_address,
             SubstateUpdate {
@@ -293,6 +347,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         non_fungible_address: &NonFungibleAddress,
     ) -> Option<&NonFungible> {
+        self.record_substate(|| {
+            SubstateAccess::Read(SubstateId::NonFungible(non_fungible_address.clone()))
+        });
         if self.non_fungibles.contains_key(non_fungible_address) {
             return self
                 .non_fungibles
@@ -327,6 +384,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         non_fungible_address: NonFungibleAddress,
         non_fungible: Option<NonFungible>,
     ) {
+        self.record_substate(|| {
+            SubstateAccess::Write(SubstateId::NonFungible(non_fungible_address.clone()))
+        });
         let cur: Option<(Option<NonFungible>, (Hash, u32))> =
             self.substate_store.get_decoded_child_substate(
                 &non_fungible_address.resource_address(),
@@ -349,6 +409,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         lazy_map_id: &LazyMapId,
         key: &[u8],
     ) -> Option<Vec<u8>> {
+        self.record_substate(|| {
+            SubstateAccess::Read(SubstateId::LazyMapEntry(
+                component_address,
+                *lazy_map_id,
+                key.to_vec(),
+            ))
+        });
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.to_vec());
 
         if self.lazy_map_entries.contains_key(&canonical_id) {
@@ -385,6 +452,13 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         key: Vec<u8>,
         value: Vec<u8>,
     ) {
+        self.record_substate(|| {
+            SubstateAccess::Write(SubstateId::LazyMapEntry(
+                component_address,
+                lazy_map_id,
+                key.clone(),
+            ))
+        });
         let canonical_id = (component_address.clone(), lazy_map_id.clone(), key.clone());
 
         if !self.lazy_map_entries.contains_key(&canonical_id) {
@@ -424,6 +498,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         resource_address: &ResourceAddress,
     ) -> Option<&ResourceManager> {
+        self.record_substate(|| {
+            SubstateAccess::Read(SubstateId::ResourceManager(*resource_address))
+        });
         if self.resource_managers.contains_key(resource_address) {
             return self
                 .resource_managers
@@ -453,6 +530,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         resource_address: ResourceAddress,
     ) -> Result<ResourceManager, RuntimeError> {
+        self.record_substate(|| {
+            SubstateAccess::Read(SubstateId::ResourceManager(resource_address))
+        });
         let maybe_resource = self.resource_managers.remove(&resource_address);
         if self
             .borrowed_resource_managers
@@ -479,6 +559,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         resource_address: ResourceAddress,
         resource_manager: ResourceManager,
     ) {
+        self.record_substate(|| {
+            SubstateAccess::Write(SubstateId::ResourceManager(resource_address))
+        });
         if let Some(prev_id) = self.borrowed_resource_managers.remove(&resource_address) {
             self.resource_managers.insert(
                 resource_address,
@@ -498,6 +581,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         resource_manager: ResourceManager,
     ) -> ResourceAddress {
         let resource_address = self.new_resource_address();
+        self.record_substate(|| {
+            SubstateAccess::Write(SubstateId::ResourceManager(resource_address))
+        });
         self.resource_managers.insert(
             resource_address,
             SubstateUpdate {
@@ -508,7 +594,39 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         resource_address
     }
 
+    /// Returns an immutable reference to a vault, if exists and not borrowed.
+    ///
+    /// Vaults loaded here are cached for reading only and are not committed.
+    pub fn get_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        vid: &VaultId,
+    ) -> Option<&Vault> {
+        self.record_substate(|| SubstateAccess::Read(SubstateId::Vault(component_address, *vid)));
+        let canonical_id = (component_address, *vid);
+        if self.vaults.contains_key(&canonical_id) {
+            return self.vaults.get(&canonical_id).map(|v| &v.value);
+        }
+        if self.borrowed_vaults.contains_key(&canonical_id) {
+            return None;
+        }
+        if self.read_only_vaults.contains_key(&canonical_id) {
+            return self.read_only_vaults.get(&canonical_id);
+        }
+
+        if let Some((vault, _)) = self
+            .substate_store
+            .get_decoded_child_substate(&component_address, vid)
+        {
+            self.read_only_vaults.insert(canonical_id, vault);
+            self.read_only_vaults.get(&canonical_id)
+        } else {
+            None
+        }
+    }
+
     pub fn borrow_vault_mut(&mut self, component_address: &ComponentAddress, vid: &VaultId) -> Vault {
+        self.record_substate(|| SubstateAccess::Read(SubstateId::Vault(*component_address, *vid)));
         let canonical_id = (component_address.clone(), vid.clone());
         if self.borrowed_vaults.contains_key(&canonical_id) {
             panic!("Invalid vault reentrancy");
@@ -534,6 +652,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         vid: &VaultId,
         vault: Vault,
     ) {
+        self.record_substate(|| SubstateAccess::Write(SubstateId::Vault(*component_address, *vid)));
         let canonical_id = (component_address.clone(), vid.clone());
         if let Some(prev_id) = self.borrowed_vaults.remove(&canonical_id) {
             self.vaults.insert(
@@ -555,6 +674,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         vault_id: VaultId,
         vault: Vault,
     ) {
+        self.record_substate(|| {
+            SubstateAccess::Write(SubstateId::Vault(component_address, vault_id))
+        });
         let canonical_id = (component_address, vault_id);
         self.vaults.insert(
             canonical_id,
@@ -565,6 +687,57 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         );
     }
 
+    /// Locks a fee taken from a vault, which is paid even if the transaction fails.
+    ///
+    /// If the transaction fails, the fee is taken from the committed state of the vault instead,
+    /// so the vault must exist and cover all fees locked from it before the transaction.
+    pub fn lock_fee(
+        &mut self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        fee: ResourceContainer,
+    ) -> Result<(), VaultError> {
+        let amount = self
+            .locked_fees
+            .iter()
+            .filter(|(c, v, _)| *c == component_address && *v == vault_id)
+            .fold(fee.total_amount(), |sum, (_, _, amount)| sum + *amount);
+        let (mut committed_vault, _): (Vault, _) = self
+            .substate_store
+            .get_decoded_child_substate(&component_address, &vault_id)
+            .ok_or(VaultError::LockFeeFromUncommittedVault)?;
+        committed_vault.take(amount, self.current_epoch())?;
+
+        self.locked_fees
+            .push((component_address, vault_id, fee.total_amount()));
+        self.reserve_fee(fee);
+        Ok(())
+    }
+
+    /// Returns the total amount of fees locked.
+    pub fn locked_fee(&self) -> Decimal {
+        self.fee_reserve
+            .as_ref()
+            .map(|fee| fee.total_amount())
+            .unwrap_or_default()
+    }
+
+    fn reserve_fee(&mut self, fee: ResourceContainer) {
+        match &mut self.fee_reserve {
+            Some(fee_reserve) => fee_reserve.put(fee).expect("Fees are paid in XRD"),
+            None => self.fee_reserve = Some(fee),
+        }
+    }
+
+    /// Pays the locked fees into the XRD vault of the system component.
+    fn pay_fees(&mut self) {
+        if let Some(fee) = self.fee_reserve.take() {
+            let mut vault = self.borrow_vault_mut(&SYSTEM_COMPONENT, &XRD_VAULT_ID);
+            vault.put(Bucket::new(fee)).expect("Fees are paid in XRD");
+            self.return_borrowed_vault(&SYSTEM_COMPONENT, &XRD_VAULT_ID, vault);
+        }
+    }
+
     /// Creates a new package ID.
     fn new_package_address(&mut self) -> PackageAddress {
         // Security Alert: ensure ID allocating will practically never fail
@@ -577,6 +750,9 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
     /// Creates a new component address.
     fn new_component_address(&mut self) -> ComponentAddress {
+        if let Some(component_address) = self.reserved_component_address.take() {
+            return component_address;
+        }
         let component_address = self
             .id_allocator
             .new_component_address(self.transaction_hash())
@@ -636,6 +812,8 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             panic!("Borrowed vaults should be empty by end of transaction.");
         }
 
+        self.pay_fees();
+
         let mut receipt = CommitReceipt::new();
         let mut id_gen = SubstateIdGenerator::new(self.transaction_hash());
 
@@ -743,4 +921,39 @@ impl<'s, S: SubstateStore> Track<'s, S> {
 
         receipt
     }
+
+    /// Commits the payment of the locked fees only, discarding all other changes.
+    ///
+    /// Fees are taken from the committed state of the vaults. Nothing is committed if a vault
+    /// can't pay its fees.
+    pub fn commit_fee_payment(&mut self) -> Result<CommitReceipt, VaultError> {
+        self.packages.clear();
+        self.components.clear();
+        self.borrowed_components.clear();
+        self.resource_managers.clear();
+        self.borrowed_resource_managers.clear();
+        self.vaults.clear();
+        self.borrowed_vaults.clear();
+        self.read_only_vaults.clear();
+        self.non_fungibles.clear();
+        self.lazy_map_entries.clear();
+        self.fee_reserve = None;
+
+        let current_epoch = self.current_epoch();
+        for (component_address, vault_id, amount) in self.locked_fees.clone() {
+            if self
+                .substate_store
+                .get_child_substate(&component_address, &scrypto_encode(&vault_id))
+                .is_none()
+            {
+                return Err(VaultError::LockFeeFromUncommittedVault);
+            }
+            let mut vault = self.borrow_vault_mut(&component_address, &vault_id);
+            let fee = vault.take(amount, current_epoch);
+            self.return_borrowed_vault(&component_address, &vault_id, vault);
+            self.reserve_fee(fee?);
+        }
+
+        Ok(self.commit())
+    }
 }
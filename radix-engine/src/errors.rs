@@ -1,7 +1,11 @@
 use sbor::*;
+use scrypto::core::ScryptoActorInfo;
 use scrypto::engine::types::*;
+use scrypto::resource::SchemaPath;
+use scrypto::rust::boxed::Box;
 use scrypto::rust::fmt;
 use scrypto::rust::string::String;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 use wasmi::*;
@@ -28,7 +32,7 @@ pub enum WasmiError {
     /// Value-level error.
     Value(String),
     /// Trap.
-    Trap,
+    Trap(WasmTrapKind),
     /// Custom embedder error.
     Host,
 }
@@ -43,12 +47,51 @@ impl From<wasmi::Error> for WasmiError {
             Error::Memory(e) => WasmiError::Memory(e),
             Error::Global(e) => WasmiError::Global(e),
             Error::Value(e) => WasmiError::Value(e),
-            Error::Trap(_) => WasmiError::Trap,
+            Error::Trap(trap) => WasmiError::Trap(trap.kind().into()),
             Error::Host(_) => WasmiError::Host,
         }
     }
 }
 
+/// The kind of a WASM trap, which maps to wasmi::TrapKind but is cloneable
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WasmTrapKind {
+    /// The `unreachable` opcode was executed, e.g. by an aborting panic.
+    Unreachable,
+    /// Memory was accessed out of bounds.
+    MemoryAccessOutOfBounds,
+    /// A table element was accessed out of bounds.
+    TableAccessOutOfBounds,
+    /// An uninitialized table element was accessed.
+    ElemUninitialized,
+    /// Division by zero.
+    DivisionByZero,
+    /// A conversion to an integer failed.
+    InvalidConversionToInt,
+    /// The stack overflowed.
+    StackOverflow,
+    /// A function was invoked with a mismatching signature.
+    UnexpectedSignature,
+    /// Error specified by the host.
+    Host,
+}
+
+impl From<&TrapKind> for WasmTrapKind {
+    fn from(kind: &TrapKind) -> Self {
+        match kind {
+            TrapKind::Unreachable => WasmTrapKind::Unreachable,
+            TrapKind::MemoryAccessOutOfBounds => WasmTrapKind::MemoryAccessOutOfBounds,
+            TrapKind::TableAccessOutOfBounds => WasmTrapKind::TableAccessOutOfBounds,
+            TrapKind::ElemUninitialized => WasmTrapKind::ElemUninitialized,
+            TrapKind::DivisionByZero => WasmTrapKind::DivisionByZero,
+            TrapKind::InvalidConversionToInt => WasmTrapKind::InvalidConversionToInt,
+            TrapKind::StackOverflow => WasmTrapKind::StackOverflow,
+            TrapKind::UnexpectedSignature => WasmTrapKind::UnexpectedSignature,
+            TrapKind::Host(_) => WasmTrapKind::Host,
+        }
+    }
+}
+
 /// Represents an error when validating a WASM file.
 #[derive(Debug, PartialEq, Clone)]
 pub enum WasmValidationError {
@@ -69,11 +112,26 @@ pub enum WasmValidationError {
 /// Represents an error when validating a transaction.
 #[derive(Debug, PartialEq, Eq)]
 pub enum TransactionValidationError {
+    DecodeError(DecodeError),
     ParseScryptoValueError(ParseScryptoValueError),
     IdValidatorError(IdValidatorError),
     VaultNotAllowed(VaultId),
     LazyMapNotAllowed(LazyMapId),
+    BucketNotAllowed(BucketId),
+    ProofNotAllowed(ProofId),
     InvalidSignature,
+    SubintentNotFound(u32),
+    SubintentYieldedMoreThanOnce(u32),
+    SubintentNotYielded(u32),
+    NestedSubintentNotAllowed,
+    ReturnToParentNotAllowed,
+    InstructionAfterReturnToParent,
+    NetworkMismatch { expected: u8, actual: u8 },
+    PayloadTooLarge { size: usize, max: usize },
+    TooManyInstructions { count: usize, max: usize },
+    TooManySignatures { count: usize, max: usize },
+    PackageTooLarge { size: usize, max: usize },
+    SborDepthExceeded(usize),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -84,11 +142,56 @@ pub enum ResourceFailure {
     Unknown
 }
 
+/// Represents a failed manifest assertion, with the observed state.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AssertionFailure {
+    VaultBalance {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        condition: AmountCondition,
+        actual: Decimal,
+    },
+    AccountBalance {
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+        actual: Decimal,
+    },
+    ResourceSupply {
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+        actual: Decimal,
+    },
+    ComponentState {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        expected: Value,
+        actual: Value,
+    },
+    LazyMapEntry {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        key: Vec<u8>,
+        expected: Option<Value>,
+        actual: Option<Value>,
+    },
+    /// The path does not exist in the component state.
+    SchemaPathNotFound {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+    },
+    /// The path does not point to a vault or lazy map.
+    InvalidSchemaPathTarget {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+    },
+}
+
 /// Represents an error when executing a transaction.
 #[derive(Debug, PartialEq, Clone)]
 pub enum RuntimeError {
     /// Assertion check failed.
-    AssertionFailed,
+    AssertionFailed(Box<AssertionFailure>),
 
     /// The data is not a valid SBOR value.
     ParseScryptoValueError(ParseScryptoValueError),
@@ -105,6 +208,18 @@ pub enum RuntimeError {
     /// Error when invoking an export.
     InvokeError,
 
+    /// A blueprint panicked, with the message it reported.
+    BlueprintPanic {
+        actor: Box<ScryptoActorInfo>,
+        message: String,
+    },
+
+    /// A blueprint trapped without reporting a panic.
+    WasmTrap {
+        kind: WasmTrapKind,
+        actor: Box<ScryptoActorInfo>,
+    },
+
     /// Error when accessing the program memory.
     MemoryAccessError,
 
@@ -142,6 +257,12 @@ pub enum RuntimeError {
     /// Component does not exist.
     ComponentNotFound(ComponentAddress),
 
+    /// Subintent does not exist or has already been yielded to.
+    SubintentNotFound(u32),
+
+    /// Subintent was yielded to after its expiry epoch.
+    SubintentExpired(u32),
+
     /// Component is already loaded
     ComponentAlreadyLoaded(ComponentAddress),
 
@@ -226,8 +347,18 @@ pub enum RuntimeError {
     /// System Authorization Failure
     AuthorizationError {
         function: String,
-        authorization: MethodAuthorization,
+        authorization: Box<MethodAuthorization>,
         error: MethodAuthorizationError,
+        trace: Box<AuthorizationTrace>,
+    },
+
+    /// The access rules of a new component don't match its blueprint.
+    InvalidAccessRules(AccessRulesError),
+
+    /// A dynamic access rule path can't be resolved against the component state.
+    UnresolvedAccessRulePath {
+        method: Option<String>,
+        path: SchemaPath,
     },
 
     /// Index out of bounds.
@@ -242,11 +373,57 @@ pub enum RuntimeError {
     /// Can't move restricted proof.
     CantMoveRestrictedProof(ProofId),
 
+    /// An error propagated out of the given calls, innermost first.
+    CallFailed {
+        error: Box<RuntimeError>,
+        call_stack: Vec<CallFrame>,
+    },
+}
+
+impl RuntimeError {
+    /// Records that this error propagated out of the given call.
+    pub fn with_call_frame(self, frame: CallFrame) -> Self {
+        match self {
+            RuntimeError::CallFailed {
+                error,
+                mut call_stack,
+            } => {
+                call_stack.push(frame);
+                RuntimeError::CallFailed { error, call_stack }
+            }
+            error => RuntimeError::CallFailed {
+                error: Box::new(error),
+                call_stack: vec![frame],
+            },
+        }
+    }
+
+    /// Splits this error into the error that occurred and the calls it propagated out of,
+    /// innermost first.
+    pub fn into_call_stack(self) -> (RuntimeError, Vec<CallFrame>) {
+        match self {
+            RuntimeError::CallFailed { error, call_stack } => (*error, call_stack),
+            error => (error, Vec::new()),
+        }
+    }
 }
 
 impl fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            RuntimeError::CallFailed { error, call_stack } => {
+                write!(f, "{}", error)?;
+                for frame in call_stack {
+                    write!(
+                        f,
+                        "\n  #{} {:?}::{}",
+                        frame.depth, frame.snode_ref, frame.function
+                    )?;
+                }
+                Ok(())
+            }
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -6,3 +6,4 @@ pub use traits::QueryableSubstateStore;
 pub use traits::Substate;
 pub use traits::SubstateIdGenerator;
 pub use traits::SubstateStore;
+pub(crate) use traits::XRD_VAULT_ID;
@@ -18,7 +18,7 @@ const XRD_NAME: &str = "Radix";
 const XRD_DESCRIPTION: &str = "The Radix Public Network's native token, used to pay the network's required transaction fees and to secure the network through staking to its validator nodes.";
 const XRD_URL: &str = "https://tokens.radixdlt.com";
 const XRD_MAX_SUPPLY: i128 = 24_000_000_000i128;
-const XRD_VAULT_ID: VaultId = (Hash([0u8; 32]), 0);
+pub(crate) const XRD_VAULT_ID: VaultId = (Hash([0u8; 32]), 0);
 const XRD_VAULT: scrypto::resource::Vault = scrypto::resource::Vault(XRD_VAULT_ID);
 
 const SYSTEM_COMPONENT_NAME: &str = "System";
@@ -34,6 +34,11 @@ pub trait QueryableSubstateStore {
         component_address: ComponentAddress,
         lazy_map_id: &LazyMapId,
     ) -> HashMap<Vec<u8>, Vec<u8>>;
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: ResourceAddress,
+    ) -> HashMap<NonFungibleId, NonFungible>;
 }
 
 #[derive(Clone, Debug, Encode, Decode, TypeId)]
@@ -176,6 +181,8 @@ pub trait SubstateStore {
                 ResourceType::Fungible { divisibility: 18 },
                 metadata,
                 resource_auth,
+                None,
+                None,
             )
             .unwrap();
             self.put_encoded_substate(&RADIX_TOKEN, &xrd, id_gen.next());
@@ -186,9 +193,13 @@ pub trait SubstateStore {
             let mut ecdsa_resource_auth = HashMap::new();
             ecdsa_resource_auth.insert(Withdraw, (rule!(allow_all), LOCKED));
             let ecdsa_token = ResourceManager::new(
-                ResourceType::NonFungible,
+                ResourceType::NonFungible {
+                    id_type: NonFungibleIdType::Bytes,
+                },
                 HashMap::new(),
                 ecdsa_resource_auth,
+                None,
+                None,
             )
             .unwrap();
             self.put_encoded_substate(&ECDSA_TOKEN, &ecdsa_token, id_gen.next());
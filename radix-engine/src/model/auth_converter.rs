@@ -8,77 +8,75 @@ use sbor::*;
 use scrypto::engine::types::*;
 use scrypto::prelude::{AccessRuleNode, AccessRule, SoftResource};
 use scrypto::resource::{
-    NonFungibleAddress, ProofRule, SoftCount, SoftDecimal, SoftResourceOrNonFungible,
+    NonFungibleAddress, ProofRule, SchemaPath, SoftCount, SoftDecimal, SoftResourceOrNonFungible,
     SoftResourceOrNonFungibleList,
 };
 use scrypto::rust::vec::Vec;
 use scrypto::types::ScryptoType;
 
-fn soft_to_hard_decimal(schema: &Type, soft_decimal: &SoftDecimal, dom: &Value) -> HardDecimal {
+/// Resolves the dynamic paths of access rules against a component state.
+struct PathResolver<'a> {
+    schema: &'a Type,
+    dom: &'a Value,
+    load_lazy_map_entry: &'a mut dyn FnMut(&LazyMapId, &[u8]) -> Option<Vec<u8>>,
+    unresolved: Vec<SchemaPath>,
+}
+
+impl<'a> PathResolver<'a> {
+    fn resolve(&mut self, schema_path: &SchemaPath) -> Option<Value> {
+        schema_path.get_from_value(self.schema, self.dom, &mut self.load_lazy_map_entry)
+    }
+
+    fn not_found<T>(&mut self, schema_path: &SchemaPath, not_found: T) -> T {
+        self.unresolved.push(schema_path.clone());
+        not_found
+    }
+}
+
+fn soft_to_hard_decimal(soft_decimal: &SoftDecimal, resolver: &mut PathResolver) -> HardDecimal {
     match soft_decimal {
         SoftDecimal::Static(amount) => HardDecimal::Amount(amount.clone()),
-        SoftDecimal::Dynamic(schema_path) => {
-            let sbor_path = schema_path.to_sbor_path(schema);
-            if let None = sbor_path {
-                return HardDecimal::SoftDecimalNotFound;
-            }
-            match sbor_path.unwrap().get_from_value(dom) {
-                Some(Value::Custom { type_id, bytes }) => {
-                    match ScryptoType::from_id(*type_id).unwrap() {
-                        ScryptoType::Decimal => {
-                            HardDecimal::Amount(Decimal::try_from(bytes.as_slice()).unwrap())
-                        }
-                        _ => HardDecimal::SoftDecimalNotFound,
-                    }
-                }
-                _ => HardDecimal::SoftDecimalNotFound,
+        SoftDecimal::Dynamic(schema_path) => match resolver.resolve(schema_path) {
+            Some(Value::Custom { type_id, bytes })
+                if ScryptoType::from_id(type_id) == Some(ScryptoType::Decimal) =>
+            {
+                HardDecimal::Amount(Decimal::try_from(bytes.as_slice()).unwrap())
             }
-        }
+            _ => resolver.not_found(schema_path, HardDecimal::SoftDecimalNotFound),
+        },
     }
 }
 
-fn soft_to_hard_count(schema: &Type, soft_count: &SoftCount, dom: &Value) -> HardCount {
+fn soft_to_hard_count(soft_count: &SoftCount, resolver: &mut PathResolver) -> HardCount {
     match soft_count {
         SoftCount::Static(count) => HardCount::Count(count.clone()),
-        SoftCount::Dynamic(schema_path) => {
-            let sbor_path = schema_path.to_sbor_path(schema);
-            if let None = sbor_path {
-                return HardCount::SoftCountNotFound;
-            }
-            match sbor_path.unwrap().get_from_value(dom) {
-                Some(Value::U8 { value }) => HardCount::Count(value.clone()),
-                _ => HardCount::SoftCountNotFound,
-            }
-        }
+        SoftCount::Dynamic(schema_path) => match resolver.resolve(schema_path) {
+            Some(Value::U8 { value }) => HardCount::Count(value),
+            _ => resolver.not_found(schema_path, HardCount::SoftCountNotFound),
+        },
     }
 }
 
 fn soft_to_hard_resource_list(
-    schema: &Type,
     list: &SoftResourceOrNonFungibleList,
-    dom: &Value,
+    resolver: &mut PathResolver,
 ) -> HardProofRuleResourceList {
     match list {
         SoftResourceOrNonFungibleList::Static(resources) => {
             let mut hard_resources = Vec::new();
             for soft_resource in resources {
-                let resource = soft_to_hard_resource_or_non_fungible(schema, soft_resource, dom);
+                let resource = soft_to_hard_resource_or_non_fungible(soft_resource, resolver);
                 hard_resources.push(resource);
             }
             HardProofRuleResourceList::List(hard_resources)
         }
         SoftResourceOrNonFungibleList::Dynamic(schema_path) => {
-            let sbor_path = schema_path.to_sbor_path(schema);
-            if let None = sbor_path {
-                return HardProofRuleResourceList::SoftResourceListNotFound;
-            }
-
-            match sbor_path.unwrap().get_from_value(dom) {
+            match resolver.resolve(schema_path) {
                 Some(Value::Vec {
                     element_type_id,
                     elements,
-                }) => match ScryptoType::from_id(*element_type_id).unwrap() {
-                    ScryptoType::ResourceAddress => HardProofRuleResourceList::List(
+                }) => match ScryptoType::from_id(element_type_id) {
+                    Some(ScryptoType::ResourceAddress) => HardProofRuleResourceList::List(
                         elements
                             .iter()
                             .map(|v| {
@@ -91,7 +89,7 @@ fn soft_to_hard_resource_list(
                             })
                             .collect(),
                     ),
-                    ScryptoType::NonFungibleAddress => HardProofRuleResourceList::List(
+                    Some(ScryptoType::NonFungibleAddress) => HardProofRuleResourceList::List(
                         elements
                             .iter()
                             .map(|v| {
@@ -104,37 +102,33 @@ fn soft_to_hard_resource_list(
                             })
                             .collect(),
                     ),
-                    _ => HardProofRuleResourceList::SoftResourceListNotFound,
+                    _ => resolver.not_found(
+                        schema_path,
+                        HardProofRuleResourceList::SoftResourceListNotFound,
+                    ),
                 },
-                _ => HardProofRuleResourceList::SoftResourceListNotFound,
+                _ => resolver.not_found(
+                    schema_path,
+                    HardProofRuleResourceList::SoftResourceListNotFound,
+                ),
             }
         }
     }
 }
 
 fn soft_to_hard_resource(
-    schema: &Type,
     soft_resource: &SoftResource,
-    dom: &Value,
+    resolver: &mut PathResolver,
 ) -> HardResourceOrNonFungible {
     match soft_resource {
-        SoftResource::Dynamic(schema_path) => {
-            let sbor_path = schema_path.to_sbor_path(schema);
-            if let None = sbor_path {
-                return HardResourceOrNonFungible::SoftResourceNotFound;
+        SoftResource::Dynamic(schema_path) => match resolver.resolve(schema_path) {
+            Some(Value::Custom { type_id, bytes })
+                if ScryptoType::from_id(type_id) == Some(ScryptoType::ResourceAddress) =>
+            {
+                ResourceAddress::try_from(bytes.as_slice()).unwrap().into()
             }
-            match sbor_path.unwrap().get_from_value(dom) {
-                Some(Value::Custom { type_id, bytes }) => {
-                    match ScryptoType::from_id(*type_id).unwrap() {
-                        ScryptoType::ResourceAddress => {
-                            ResourceAddress::try_from(bytes.as_slice()).unwrap().into()
-                        }
-                        _ => HardResourceOrNonFungible::SoftResourceNotFound,
-                    }
-                }
-                _ => HardResourceOrNonFungible::SoftResourceNotFound,
-            }
-        }
+            _ => resolver.not_found(schema_path, HardResourceOrNonFungible::SoftResourceNotFound),
+        },
         SoftResource::Static(resource_def_id) => {
             HardResourceOrNonFungible::Resource(resource_def_id.clone())
         }
@@ -142,33 +136,26 @@ fn soft_to_hard_resource(
 }
 
 fn soft_to_hard_resource_or_non_fungible(
-    schema: &Type,
     proof_rule_resource: &SoftResourceOrNonFungible,
-    dom: &Value,
+    resolver: &mut PathResolver,
 ) -> HardResourceOrNonFungible {
     match proof_rule_resource {
-        SoftResourceOrNonFungible::Dynamic(schema_path) => {
-            let sbor_path = schema_path.to_sbor_path(schema);
-            if let None = sbor_path {
-                return HardResourceOrNonFungible::SoftResourceNotFound;
-            }
-            match sbor_path.unwrap().get_from_value(dom) {
-                Some(Value::Custom { type_id, bytes }) => {
-                    match ScryptoType::from_id(*type_id).unwrap() {
-                        ScryptoType::ResourceAddress => {
-                            ResourceAddress::try_from(bytes.as_slice()).unwrap().into()
-                        }
-                        ScryptoType::NonFungibleAddress => {
-                            NonFungibleAddress::try_from(bytes.as_slice())
-                                .unwrap()
-                                .into()
-                        }
-                        _ => HardResourceOrNonFungible::SoftResourceNotFound,
-                    }
+        SoftResourceOrNonFungible::Dynamic(schema_path) => match resolver.resolve(schema_path) {
+            Some(Value::Custom { type_id, bytes }) => match ScryptoType::from_id(type_id) {
+                Some(ScryptoType::ResourceAddress) => {
+                    ResourceAddress::try_from(bytes.as_slice()).unwrap().into()
                 }
-                _ => HardResourceOrNonFungible::SoftResourceNotFound,
-            }
-        }
+                Some(ScryptoType::NonFungibleAddress) => {
+                    NonFungibleAddress::try_from(bytes.as_slice())
+                        .unwrap()
+                        .into()
+                }
+                _ => {
+                    resolver.not_found(schema_path, HardResourceOrNonFungible::SoftResourceNotFound)
+                }
+            },
+            _ => resolver.not_found(schema_path, HardResourceOrNonFungible::SoftResourceNotFound),
+        },
         SoftResourceOrNonFungible::StaticNonFungible(non_fungible_address) => {
             HardResourceOrNonFungible::NonFungible(non_fungible_address.clone())
         }
@@ -178,50 +165,51 @@ fn soft_to_hard_resource_or_non_fungible(
     }
 }
 
-fn soft_to_hard_proof_rule(schema: &Type, proof_rule: &ProofRule, dom: &Value) -> HardProofRule {
+fn soft_to_hard_proof_rule(proof_rule: &ProofRule, resolver: &mut PathResolver) -> HardProofRule {
     match proof_rule {
         ProofRule::Require(soft_resource_or_non_fungible) => {
             let resource =
-                soft_to_hard_resource_or_non_fungible(schema, soft_resource_or_non_fungible, dom);
+                soft_to_hard_resource_or_non_fungible(soft_resource_or_non_fungible, resolver);
             HardProofRule::This(resource)
         }
         ProofRule::AmountOf(soft_decimal, soft_resource) => {
-            let resource = soft_to_hard_resource(schema, soft_resource, dom);
-            let hard_decimal = soft_to_hard_decimal(schema, soft_decimal, dom);
+            let resource = soft_to_hard_resource(soft_resource, resolver);
+            let hard_decimal = soft_to_hard_decimal(soft_decimal, resolver);
             HardProofRule::SomeOfResource(hard_decimal, resource)
         }
         ProofRule::AllOf(resources) => {
-            let hard_resources = soft_to_hard_resource_list(schema, resources, dom);
+            let hard_resources = soft_to_hard_resource_list(resources, resolver);
             HardProofRule::AllOf(hard_resources)
         }
         ProofRule::AnyOf(resources) => {
-            let hard_resources = soft_to_hard_resource_list(schema, resources, dom);
+            let hard_resources = soft_to_hard_resource_list(resources, resolver);
             HardProofRule::AnyOf(hard_resources)
         }
         ProofRule::CountOf(soft_count, resources) => {
-            let hard_count = soft_to_hard_count(schema, soft_count, dom);
-            let hard_resources = soft_to_hard_resource_list(schema, resources, dom);
+            let hard_count = soft_to_hard_count(soft_count, resolver);
+            let hard_resources = soft_to_hard_resource_list(resources, resolver);
             HardProofRule::CountOf(hard_count, hard_resources)
         }
+        ProofRule::RequireCaller(caller) => HardProofRule::RequireCaller(caller.clone()),
     }
 }
 
-fn soft_to_hard_auth_rule(schema: &Type, auth_rule: &AccessRuleNode, dom: &Value) -> HardAuthRule {
+fn soft_to_hard_auth_rule(auth_rule: &AccessRuleNode, resolver: &mut PathResolver) -> HardAuthRule {
     match auth_rule {
         AccessRuleNode::ProofRule(proof_rule) => {
-            HardAuthRule::ProofRule(soft_to_hard_proof_rule(schema, proof_rule, dom))
+            HardAuthRule::ProofRule(soft_to_hard_proof_rule(proof_rule, resolver))
         }
         AccessRuleNode::AnyOf(rules) => {
             let hard_rules = rules
                 .iter()
-                .map(|r| soft_to_hard_auth_rule(schema, r, dom))
+                .map(|r| soft_to_hard_auth_rule(r, resolver))
                 .collect();
             HardAuthRule::AnyOf(hard_rules)
         }
         AccessRuleNode::AllOf(rules) => {
             let hard_rules = rules
                 .iter()
-                .map(|r| soft_to_hard_auth_rule(schema, r, dom))
+                .map(|r| soft_to_hard_auth_rule(r, resolver))
                 .collect();
             HardAuthRule::AllOf(hard_rules)
         }
@@ -229,11 +217,128 @@ fn soft_to_hard_auth_rule(schema: &Type, auth_rule: &AccessRuleNode, dom: &Value
 }
 
 pub fn convert(schema: &Type, dom: &Value, method_auth: &AccessRule) -> MethodAuthorization {
-    match method_auth {
+    let (authorization, _) = convert_with_lazy_maps(schema, dom, method_auth, &mut |_, _| None);
+    authorization
+}
+
+/// Converts an access rule like `convert`, also resolving paths into lazy maps whose entries
+/// are loaded through `load_lazy_map_entry`. Returns the paths which could not be resolved.
+pub fn convert_with_lazy_maps(
+    schema: &Type,
+    dom: &Value,
+    method_auth: &AccessRule,
+    load_lazy_map_entry: &mut dyn FnMut(&LazyMapId, &[u8]) -> Option<Vec<u8>>,
+) -> (MethodAuthorization, Vec<SchemaPath>) {
+    let mut resolver = PathResolver {
+        schema,
+        dom,
+        load_lazy_map_entry,
+        unresolved: Vec::new(),
+    };
+    let authorization = match method_auth {
         AccessRule::Protected(auth_rule) => {
-            MethodAuthorization::Protected(soft_to_hard_auth_rule(schema, auth_rule, dom))
+            MethodAuthorization::Protected(soft_to_hard_auth_rule(auth_rule, &mut resolver))
         }
         AccessRule::AllowAll => MethodAuthorization::AllowAll,
         AccessRule::DenyAll => MethodAuthorization::DenyAll,
+    };
+    (authorization, resolver.unresolved)
+}
+
+/// Type checks the dynamic paths of access rules against a blueprint schema, collecting the
+/// paths which don't exist or don't point to a value of the type the rule expects.
+struct PathChecker<'a> {
+    schema: &'a Type,
+    invalid: Vec<SchemaPath>,
+}
+
+impl<'a> PathChecker<'a> {
+    fn check<F: Fn(&Type) -> bool>(&mut self, schema_path: &SchemaPath, is_expected: F) {
+        match schema_path.get_type(self.schema) {
+            Some(path_type) if is_expected(path_type) => {}
+            _ => self.invalid.push(schema_path.clone()),
+        }
+    }
+
+    fn check_resource_or_non_fungible(&mut self, resource: &SoftResourceOrNonFungible) {
+        if let SoftResourceOrNonFungible::Dynamic(schema_path) = resource {
+            self.check(schema_path, is_resource_or_non_fungible);
+        }
+    }
+
+    fn check_resource_list(&mut self, list: &SoftResourceOrNonFungibleList) {
+        match list {
+            SoftResourceOrNonFungibleList::Static(resources) => {
+                for resource in resources {
+                    self.check_resource_or_non_fungible(resource);
+                }
+            }
+            SoftResourceOrNonFungibleList::Dynamic(schema_path) => {
+                self.check(schema_path, |path_type| {
+                    matches!(path_type, Type::Vec { element } if is_resource_or_non_fungible(element))
+                })
+            }
+        }
+    }
+
+    fn check_proof_rule(&mut self, proof_rule: &ProofRule) {
+        match proof_rule {
+            ProofRule::Require(resource) => self.check_resource_or_non_fungible(resource),
+            ProofRule::AmountOf(soft_decimal, soft_resource) => {
+                if let SoftDecimal::Dynamic(schema_path) = soft_decimal {
+                    self.check(schema_path, |path_type| {
+                        is_scrypto_type(path_type, ScryptoType::Decimal)
+                    });
+                }
+                if let SoftResource::Dynamic(schema_path) = soft_resource {
+                    self.check(schema_path, |path_type| {
+                        is_scrypto_type(path_type, ScryptoType::ResourceAddress)
+                    });
+                }
+            }
+            ProofRule::AllOf(resources) | ProofRule::AnyOf(resources) => {
+                self.check_resource_list(resources)
+            }
+            ProofRule::CountOf(soft_count, resources) => {
+                if let SoftCount::Dynamic(schema_path) = soft_count {
+                    self.check(schema_path, |path_type| matches!(path_type, Type::U8));
+                }
+                self.check_resource_list(resources);
+            }
+            ProofRule::RequireCaller(_) => {}
+        }
+    }
+
+    fn check_auth_rule(&mut self, auth_rule: &AccessRuleNode) {
+        match auth_rule {
+            AccessRuleNode::ProofRule(proof_rule) => self.check_proof_rule(proof_rule),
+            AccessRuleNode::AnyOf(rules) | AccessRuleNode::AllOf(rules) => {
+                for rule in rules {
+                    self.check_auth_rule(rule);
+                }
+            }
+        }
+    }
+}
+
+fn is_scrypto_type(path_type: &Type, scrypto_type: ScryptoType) -> bool {
+    matches!(path_type, Type::Custom { name, .. } if *name == scrypto_type.name())
+}
+
+fn is_resource_or_non_fungible(path_type: &Type) -> bool {
+    is_scrypto_type(path_type, ScryptoType::ResourceAddress)
+        || is_scrypto_type(path_type, ScryptoType::NonFungibleAddress)
+}
+
+/// Returns the dynamic paths of an access rule which don't exist in the blueprint schema, or
+/// which point to a value of a type the rule can't use.
+pub fn invalid_access_rule_paths(schema: &Type, access_rule: &AccessRule) -> Vec<SchemaPath> {
+    let mut checker = PathChecker {
+        schema,
+        invalid: Vec::new(),
+    };
+    if let AccessRule::Protected(auth_rule) = access_rule {
+        checker.check_auth_rule(auth_rule);
     }
+    checker.invalid
 }
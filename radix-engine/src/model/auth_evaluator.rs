@@ -0,0 +1,161 @@
+use scrypto::engine::types::*;
+use scrypto::resource::ResourceMethod;
+use scrypto::rust::collections::BTreeSet;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+
+use crate::ledger::SubstateStore;
+use crate::model::*;
+
+/// A proof that a caller could present, described by what it would lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HypotheticalProof {
+    Amount(Decimal, ResourceAddress),
+    Ids(BTreeSet<NonFungibleId>, ResourceAddress),
+}
+
+impl HypotheticalProof {
+    pub fn resource_address(&self) -> ResourceAddress {
+        match self {
+            HypotheticalProof::Amount(_, resource_address)
+            | HypotheticalProof::Ids(_, resource_address) => *resource_address,
+        }
+    }
+}
+
+/// Represents an error when evaluating an authorization offline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthEvaluationError {
+    ComponentNotFound(ComponentAddress),
+    PackageNotFound(PackageAddress),
+    BlueprintNotFound(PackageAddress, String),
+    ResourceManagerNotFound(ResourceAddress),
+    ResourceTypeNotMatching(ResourceAddress),
+    ResourceContainerError(ResourceContainerError),
+    ProofError(ProofError),
+}
+
+/// The outcome of evaluating the authorization of a method against a set of proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthEvaluation {
+    /// Each authorization guarding the method, with its result and evaluation trace.
+    pub layers: Vec<(
+        MethodAuthorization,
+        Result<(), MethodAuthorizationError>,
+        AuthorizationTrace,
+    )>,
+}
+
+impl AuthEvaluation {
+    fn evaluate(authorizations: Vec<MethodAuthorization>, auth_zone: &AuthZone) -> Self {
+        let layers = authorizations
+            .into_iter()
+            .map(|authorization| {
+                let (result, trace) = authorization.check_with_trace(&[auth_zone], &[]);
+                (authorization, result, trace)
+            })
+            .collect();
+        Self { layers }
+    }
+
+    /// Returns whether the proofs satisfy every authorization guarding the method.
+    pub fn is_authorized(&self) -> bool {
+        self.layers.iter().all(|(_, result, _)| result.is_ok())
+    }
+}
+
+/// Evaluates whether the given proofs would be authorized to call a component method,
+/// resolving the access rules against the current component state.
+pub fn evaluate_component_method_auth<S: SubstateStore>(
+    substate_store: &S,
+    component_address: ComponentAddress,
+    method_name: &str,
+    proofs: &[HypotheticalProof],
+) -> Result<AuthEvaluation, AuthEvaluationError> {
+    let component: Component = substate_store
+        .get_decoded_substate(&component_address)
+        .map(|(component, _)| component)
+        .ok_or(AuthEvaluationError::ComponentNotFound(component_address))?;
+    let package_address = component.package_address();
+    let package: Package = substate_store
+        .get_decoded_substate(&package_address)
+        .map(|(package, _)| package)
+        .ok_or(AuthEvaluationError::PackageNotFound(package_address))?;
+    let schema = package
+        .load_blueprint_schema(component.blueprint_name())
+        .map_err(|_| {
+            AuthEvaluationError::BlueprintNotFound(
+                package_address,
+                component.blueprint_name().to_string(),
+            )
+        })?;
+
+    let auth_zone = create_auth_zone(substate_store, proofs)?;
+    let (_, authorizations) =
+        component.method_authorization(schema, method_name, &mut |lazy_map_id, key| {
+            substate_store
+                .get_decoded_grand_child_substate(&component_address, lazy_map_id, key)
+                .map(|(entry, _)| entry)
+        });
+    Ok(AuthEvaluation::evaluate(authorizations, &auth_zone))
+}
+
+/// Evaluates whether the given proofs would be authorized to perform a resource method.
+pub fn evaluate_resource_method_auth<S: SubstateStore>(
+    substate_store: &S,
+    resource_address: ResourceAddress,
+    method: ResourceMethod,
+    proofs: &[HypotheticalProof],
+) -> Result<AuthEvaluation, AuthEvaluationError> {
+    let resource_manager = get_resource_manager(substate_store, resource_address)?;
+
+    let auth_zone = create_auth_zone(substate_store, proofs)?;
+    let authorization = resource_manager.get_method_auth(&method).clone();
+    Ok(AuthEvaluation::evaluate(vec![authorization], &auth_zone))
+}
+
+fn get_resource_manager<S: SubstateStore>(
+    substate_store: &S,
+    resource_address: ResourceAddress,
+) -> Result<ResourceManager, AuthEvaluationError> {
+    substate_store
+        .get_decoded_substate(&resource_address)
+        .map(|(resource_manager, _)| resource_manager)
+        .ok_or(AuthEvaluationError::ResourceManagerNotFound(
+            resource_address,
+        ))
+}
+
+/// Creates an auth zone holding a proof for each of the hypothetical proofs, each backed by
+/// its own container.
+fn create_auth_zone<S: SubstateStore>(
+    substate_store: &S,
+    proofs: &[HypotheticalProof],
+) -> Result<AuthZone, AuthEvaluationError> {
+    let mut auth_zone = AuthZone::new();
+    for (i, proof) in proofs.iter().enumerate() {
+        let resource_address = proof.resource_address();
+        let resource_manager = get_resource_manager(substate_store, resource_address)?;
+        let container = match (proof, resource_manager.resource_type()) {
+            (HypotheticalProof::Amount(amount, _), ResourceType::Fungible { divisibility }) => {
+                ResourceContainer::new_fungible(resource_address, divisibility, *amount)
+            }
+            (HypotheticalProof::Ids(ids, _), ResourceType::NonFungible { id_type }) => {
+                ResourceContainer::new_non_fungible(resource_address, id_type, ids.clone())
+                    .map_err(AuthEvaluationError::ResourceContainerError)?
+            }
+            _ => {
+                return Err(AuthEvaluationError::ResourceTypeNotMatching(
+                    resource_address,
+                ))
+            }
+        };
+        let proof = Bucket::new(container)
+            .create_proof(i as BucketId)
+            .map_err(AuthEvaluationError::ProofError)?;
+        auth_zone.push(proof);
+    }
+    Ok(auth_zone)
+}
@@ -1,4 +1,6 @@
 use sbor::DecodeError;
+use scrypto::constants::ECDSA_TOKEN;
+use scrypto::crypto::EcdsaPublicKey;
 use scrypto::engine::types::*;
 use scrypto::prelude::scrypto_decode;
 use scrypto::rust::collections::BTreeSet;
@@ -6,9 +8,9 @@ use scrypto::rust::vec::Vec;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::values::ScryptoValue;
-use crate::engine::SystemApi;
+use crate::engine::{SystemApi, ECDSA_TOKEN_BUCKET_ID};
 
-use crate::model::{Proof, ProofError, ResourceManager};
+use crate::model::{Bucket, Proof, ProofError, ResourceContainer, ResourceManager};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AuthZoneError {
@@ -40,6 +42,38 @@ impl AuthZone {
         }
     }
 
+    /// Creates an auth zone holding a virtual proof of the given signers.
+    pub fn new_with_signers(signers: &[EcdsaPublicKey]) -> Self {
+        let signers: BTreeSet<NonFungibleId> = signers
+            .iter()
+            .map(|public_key| NonFungibleId::from_bytes(public_key.to_vec()))
+            .collect();
+
+        // With the latest change, proof amount can't be zero, thus a virtual proof is created
+        // only if there are signers.
+        //
+        // Transactions that refer to the signature virtual proof will pass static check
+        // but will fail at runtime, if there are no signers.
+        //
+        // TODO: possible to update static check to reject them early?
+        let mut proofs = Vec::new();
+        if !signers.is_empty() {
+            // Proofs can't be zero amount
+            let mut ecdsa_bucket = Bucket::new(
+                ResourceContainer::new_non_fungible(
+                    ECDSA_TOKEN,
+                    NonFungibleIdType::Bytes,
+                    signers,
+                )
+                .unwrap(),
+            );
+            let ecdsa_proof = ecdsa_bucket.create_proof(ECDSA_TOKEN_BUCKET_ID).unwrap();
+            proofs.push(ecdsa_proof);
+        }
+
+        Self::new_with_proofs(proofs)
+    }
+
     pub fn pop(&mut self) -> Result<Proof, AuthZoneError> {
         if self.proofs.is_empty() {
             return Err(AuthZoneError::EmptyAuthZone);
@@ -97,11 +131,7 @@ impl AuthZone {
             "push" => {
                 let proof_id: scrypto::resource::Proof =
                     scrypto_decode(&args[0].raw).map_err(|e| AuthZoneError::InvalidRequestData(e))?;
-                let mut proof = system_api.take_proof(proof_id.0).map_err(|_| AuthZoneError::CouldNotGetProof)?;
-                // FIXME: this is a hack for now until we can get snode_state into process
-                // FIXME: and be able to determine which snode the proof is going into
-                proof.change_to_unrestricted();
-
+                let proof = system_api.take_proof(proof_id.0).map_err(|_| AuthZoneError::CouldNotGetProof)?;
                 self.push(proof);
                 Ok(ScryptoValue::from_value(&()))
             }
@@ -61,7 +61,7 @@ impl Bucket {
             ResourceType::Fungible { .. } => {
                 self.create_proof_by_amount(self.total_amount(), container_id)
             }
-            ResourceType::NonFungible => {
+            ResourceType::NonFungible { .. } => {
                 self.create_proof_by_ids(&self.total_ids().unwrap(), container_id)
             }
         }
@@ -125,7 +125,7 @@ impl Bucket {
         self.borrow_container().resource_type()
     }
 
-    fn total_amount(&self) -> Decimal {
+    pub fn total_amount(&self) -> Decimal {
         self.borrow_container().total_amount()
     }
 
@@ -229,7 +229,10 @@ impl Bucket {
             .borrow_global_mut_resource_manager(resource_address)
             .unwrap();
         resource_manager.burn(self.total_amount());
-        if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+        if matches!(
+            resource_manager.resource_type(),
+            ResourceType::NonFungible { .. }
+        ) {
             for id in self.total_ids().unwrap() {
                 let non_fungible_address = NonFungibleAddress::new(resource_address, id);
                 system_api.set_non_fungible(non_fungible_address, Option::None);
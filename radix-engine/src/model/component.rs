@@ -1,11 +1,26 @@
 use sbor::*;
+use scrypto::abi::Method;
 use scrypto::engine::types::*;
-use scrypto::resource::AccessRules;
+use scrypto::resource::{AccessRules, SchemaPath};
 use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
-use crate::model::{convert, MethodAuthorization};
+use crate::model::{convert_with_lazy_maps, invalid_access_rule_paths, MethodAuthorization};
+
+/// Represents an error when validating the access rules of a component against its blueprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessRulesError {
+    /// An access rule is given for a method the blueprint doesn't have.
+    MethodNotFound(String),
+    /// A dynamic path doesn't exist in the blueprint state, or has a type the rule can't use.
+    /// The method is `None` for a default rule.
+    InvalidPath {
+        method: Option<String>,
+        path: SchemaPath,
+    },
+}
 
 /// A component is an instance of blueprint.
 #[derive(Debug, TypeId, Encode, Decode)]
@@ -35,19 +50,73 @@ impl Component {
         &self,
         schema: &Type,
         method_name: &str,
+        load_lazy_map_entry: &mut dyn FnMut(&LazyMapId, &[u8]) -> Option<Vec<u8>>,
     ) -> (ScryptoValue, Vec<MethodAuthorization>) {
         let data = ScryptoValue::from_slice(&self.state).unwrap();
 
         let mut authorizations = Vec::new();
         for auth in &self.auths {
             let method_auth = auth.get(method_name);
-            let authorization = convert(schema, &data.dom, method_auth);
+            let (authorization, _) =
+                convert_with_lazy_maps(schema, &data.dom, method_auth, load_lazy_map_entry);
             authorizations.push(authorization);
         }
 
         (data, authorizations)
     }
 
+    /// Validates the access rules against the methods and schema of the blueprint.
+    pub fn validate_access_rules(
+        &self,
+        schema: &Type,
+        methods: &[Method],
+    ) -> Result<(), AccessRulesError> {
+        for auth in &self.auths {
+            for (method_name, rule) in auth.iter() {
+                if !methods.iter().any(|method| method.name == *method_name) {
+                    return Err(AccessRulesError::MethodNotFound(method_name.to_string()));
+                }
+                if let Some(path) = invalid_access_rule_paths(schema, rule).into_iter().next() {
+                    return Err(AccessRulesError::InvalidPath {
+                        method: Some(method_name.to_string()),
+                        path,
+                    });
+                }
+            }
+            if let Some(path) = invalid_access_rule_paths(schema, auth.get_default())
+                .into_iter()
+                .next()
+            {
+                return Err(AccessRulesError::InvalidPath { method: None, path });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the dynamic paths of the access rules which can't be resolved against the current
+    /// state, with the method they guard or `None` for a default rule.
+    pub fn unresolved_access_rule_paths(
+        &self,
+        schema: &Type,
+        load_lazy_map_entry: &mut dyn FnMut(&LazyMapId, &[u8]) -> Option<Vec<u8>>,
+    ) -> Vec<(Option<String>, SchemaPath)> {
+        let data = ScryptoValue::from_slice(&self.state).unwrap();
+
+        let mut unresolved = Vec::new();
+        for auth in &self.auths {
+            let rules = auth
+                .iter()
+                .map(|(method_name, rule)| (Some(method_name.to_string()), rule))
+                .chain([(None, auth.get_default())]);
+            for (method_name, rule) in rules {
+                let (_, paths) =
+                    convert_with_lazy_maps(schema, &data.dom, rule, load_lazy_map_entry);
+                unresolved.extend(paths.into_iter().map(|path| (method_name.clone(), path)));
+            }
+        }
+        unresolved
+    }
+
     pub fn authorization(&self) -> &[AccessRules] {
         &self.auths
     }
@@ -1,9 +1,18 @@
+use colored::*;
 use sbor::*;
+use scrypto::constants::ECDSA_TOKEN;
+use scrypto::core::ScryptoActorInfo;
 use scrypto::engine::types::*;
+use scrypto::resource::CallerRequirement;
+use scrypto::rust::collections::BTreeSet;
+use scrypto::rust::fmt;
+use scrypto::rust::format;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 
+use crate::engine::IdAllocator;
 use crate::model::method_authorization::MethodAuthorizationError::NotAuthorized;
-use crate::model::{AuthZone, Proof};
+use crate::model::{AuthZone, LockedAmountOrIds, Proof};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode)]
 pub enum MethodAuthorizationError {
@@ -31,46 +40,87 @@ pub enum HardResourceOrNonFungible {
 }
 
 impl HardResourceOrNonFungible {
-    pub fn proof_matches(&self, proof: &Proof) -> bool {
-        match self {
+    /// Computes what the proofs of the resource across all auth zones lock in total, so that
+    /// several smaller proofs can together satisfy a requirement.
+    fn total_locked(&self, auth_zones: &[&AuthZone]) -> Option<LockedAmountOrIds> {
+        let resource_address = match self {
             HardResourceOrNonFungible::NonFungible(non_fungible_address) => {
-                let proof_resource_address = proof.resource_address();
-                proof_resource_address == non_fungible_address.resource_address()
-                    && match proof.total_ids() {
-                        Ok(ids) => ids.contains(&non_fungible_address.non_fungible_id()),
-                        Err(_) => false,
-                    }
-            }
-            HardResourceOrNonFungible::Resource(resource_address) => {
-                let proof_resource_address = proof.resource_address();
-                proof_resource_address == *resource_address
+                non_fungible_address.resource_address()
             }
-            HardResourceOrNonFungible::SoftResourceNotFound => false,
+            HardResourceOrNonFungible::Resource(resource_address) => *resource_address,
+            HardResourceOrNonFungible::SoftResourceNotFound => return None,
+        };
+        let proofs: Vec<&Proof> = auth_zones
+            .iter()
+            .flat_map(|auth_zone| auth_zone.proofs.iter())
+            .filter(|p| p.resource_address() == resource_address)
+            .collect();
+        let resource_type = proofs.first()?.resource_type();
+        let (total_locked, _) = Proof::compute_total_locked_of(&proofs, resource_type);
+        Some(total_locked)
+    }
+
+    /// Returns the combined amount of the resource proven by the auth zones.
+    pub fn available_amount(&self, auth_zones: &[&AuthZone]) -> Decimal {
+        match self.total_locked(auth_zones) {
+            Some(LockedAmountOrIds::Amount(amount)) => amount,
+            Some(LockedAmountOrIds::Ids(ids)) => ids.len().into(),
+            None => Decimal::zero(),
         }
     }
 
     pub fn check_has_amount(&self, amount: Decimal, auth_zones: &[&AuthZone]) -> bool {
-        for auth_zone in auth_zones {
-            // FIXME: Need to check the composite max amount rather than just each proof individually
-            if auth_zone.proofs
-                .iter()
-                .any(|p| self.proof_matches(p) && p.total_amount() >= amount)
-            {
-                return true;
+        match (self, self.total_locked(auth_zones)) {
+            (HardResourceOrNonFungible::Resource(_), Some(LockedAmountOrIds::Amount(total))) => {
+                total >= amount
+            }
+            (HardResourceOrNonFungible::Resource(_), Some(LockedAmountOrIds::Ids(ids))) => {
+                Decimal::from(ids.len()) >= amount
             }
+            (
+                HardResourceOrNonFungible::NonFungible(non_fungible_address),
+                Some(LockedAmountOrIds::Ids(ids)),
+            ) => Self::ids_prove(&ids, non_fungible_address) && Decimal::from(ids.len()) >= amount,
+            _ => false,
         }
-
-        false
     }
 
     pub fn check(&self, auth_zones: &[&AuthZone]) -> bool {
-        for auth_zone in auth_zones {
-            if auth_zone.proofs.iter().any(|p| self.proof_matches(p)) {
-                return true;
-            }
+        match (self, self.total_locked(auth_zones)) {
+            (HardResourceOrNonFungible::Resource(_), Some(_)) => true,
+            (
+                HardResourceOrNonFungible::NonFungible(non_fungible_address),
+                Some(LockedAmountOrIds::Ids(ids)),
+            ) => Self::ids_prove(&ids, non_fungible_address),
+            _ => false,
         }
+    }
 
-        false
+    /// Returns whether the given ids prove the non-fungible.
+    ///
+    /// A signer's `ECDSA_TOKEN` also proves the key hash id which owns the signer's virtual
+    /// account, so that it's never minted into the signature proof.
+    fn ids_prove(ids: &BTreeSet<NonFungibleId>, non_fungible_address: &NonFungibleAddress) -> bool {
+        let non_fungible_id = non_fungible_address.non_fungible_id();
+        ids.contains(&non_fungible_id)
+            || (non_fungible_address.resource_address() == ECDSA_TOKEN
+                && ids
+                    .iter()
+                    .any(|id| IdAllocator::signer_hash_id(id).as_ref() == Some(&non_fungible_id)))
+    }
+}
+
+impl fmt::Display for HardResourceOrNonFungible {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HardResourceOrNonFungible::NonFungible(non_fungible_address) => {
+                write!(f, "{}", non_fungible_address)
+            }
+            HardResourceOrNonFungible::Resource(resource_address) => {
+                write!(f, "{}", resource_address)
+            }
+            HardResourceOrNonFungible::SoftResourceNotFound => write!(f, "<resource not found>"),
+        }
     }
 }
 
@@ -99,59 +149,120 @@ pub enum HardProofRule {
     AllOf(HardProofRuleResourceList),
     AnyOf(HardProofRuleResourceList),
     CountOf(HardCount, HardProofRuleResourceList),
+    RequireCaller(CallerRequirement),
 }
 
 impl HardProofRule {
-    pub fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
-        match self {
-            HardProofRule::This(resource) => {
-                if resource.check(auth_zones) {
-                    Ok(())
-                } else {
-                    Err(NotAuthorized)
-                }
+    fn evaluate_resources<F>(
+        node: AuthRuleTraceNode,
+        resources: &HardProofRuleResourceList,
+        auth_zones: &[&AuthZone],
+        traced: bool,
+        passed: F,
+    ) -> (bool, Option<AuthRuleTrace>)
+    where
+        F: FnOnce(usize, usize) -> bool,
+    {
+        match resources {
+            HardProofRuleResourceList::List(resources) => {
+                let passed_count = resources
+                    .iter()
+                    .filter(|resource| resource.check(auth_zones))
+                    .count();
+                let passed = passed(passed_count, resources.len());
+                let trace = traced.then(|| AuthRuleTrace {
+                    node,
+                    passed,
+                    children: resources
+                        .iter()
+                        .map(|resource| {
+                            AuthRuleTrace::leaf(
+                                AuthRuleTraceNode::Require(resource.clone()),
+                                resource.check(auth_zones),
+                            )
+                        })
+                        .collect(),
+                });
+                (passed, trace)
             }
-            HardProofRule::SomeOfResource(HardDecimal::Amount(amount), resource) => {
-                if resource.check_has_amount(*amount, auth_zones) {
-                    Ok(())
-                } else {
-                    Err(NotAuthorized)
-                }
+            HardProofRuleResourceList::SoftResourceListNotFound => {
+                let trace = traced.then(|| AuthRuleTrace {
+                    node,
+                    passed: false,
+                    children: vec![AuthRuleTrace::leaf(
+                        AuthRuleTraceNode::ResourceListNotFound,
+                        false,
+                    )],
+                });
+                (false, trace)
             }
-            HardProofRule::AllOf(HardProofRuleResourceList::List(resources)) => {
-                for resource in resources {
-                    if !resource.check(auth_zones) {
-                        return Err(NotAuthorized);
-                    }
-                }
+        }
+    }
 
-                Ok(())
+    /// Evaluates this rule against the given auth zones and callers, building the trace of the
+    /// evaluation only if `traced` is set.
+    fn evaluate(
+        &self,
+        auth_zones: &[&AuthZone],
+        callers: &[&ScryptoActorInfo],
+        traced: bool,
+    ) -> (bool, Option<AuthRuleTrace>) {
+        match self {
+            HardProofRule::This(resource) => {
+                let passed = resource.check(auth_zones);
+                let trace = traced.then(|| {
+                    AuthRuleTrace::leaf(AuthRuleTraceNode::Require(resource.clone()), passed)
+                });
+                (passed, trace)
             }
-            HardProofRule::AnyOf(HardProofRuleResourceList::List(resources)) => {
-                for resource in resources {
-                    if resource.check(auth_zones) {
-                        return Ok(());
-                    }
-                }
-
-                Err(NotAuthorized)
+            HardProofRule::SomeOfResource(amount, resource) => {
+                let passed = match amount {
+                    HardDecimal::Amount(amount) => resource.check_has_amount(*amount, auth_zones),
+                    HardDecimal::SoftDecimalNotFound => false,
+                };
+                let trace = traced.then(|| {
+                    AuthRuleTrace::leaf(
+                        AuthRuleTraceNode::AmountOf {
+                            resource: resource.clone(),
+                            required: amount.clone(),
+                            available: resource.available_amount(auth_zones),
+                        },
+                        passed,
+                    )
+                });
+                (passed, trace)
             }
-            HardProofRule::CountOf(
-                HardCount::Count(count),
-                HardProofRuleResourceList::List(resources),
-            ) => {
-                let mut left = count.clone();
-                for resource in resources {
-                    if resource.check(auth_zones) {
-                        left -= 1;
-                        if left == 0 {
-                            return Ok(());
-                        }
-                    }
-                }
-                Err(NotAuthorized)
+            HardProofRule::AllOf(resources) => Self::evaluate_resources(
+                AuthRuleTraceNode::AllOf,
+                resources,
+                auth_zones,
+                traced,
+                |passed, total| passed == total,
+            ),
+            HardProofRule::AnyOf(resources) => Self::evaluate_resources(
+                AuthRuleTraceNode::AnyOf,
+                resources,
+                auth_zones,
+                traced,
+                |passed, _| passed > 0,
+            ),
+            HardProofRule::CountOf(count, resources) => Self::evaluate_resources(
+                AuthRuleTraceNode::CountOf(count.clone()),
+                resources,
+                auth_zones,
+                traced,
+                |passed, _| match count {
+                    HardCount::Count(count) => passed >= *count as usize,
+                    HardCount::SoftCountNotFound => false,
+                },
+            ),
+            HardProofRule::RequireCaller(caller) => {
+                let passed = callers.iter().any(|actor| caller.matches(actor));
+                let trace = traced.then(|| {
+                    AuthRuleTrace::leaf(AuthRuleTraceNode::RequireCaller(caller.clone()), passed)
+                });
+                (passed, trace)
             }
-            _ => Err(NotAuthorized),
         }
     }
 }
@@ -164,22 +275,44 @@ pub enum HardAuthRule {
 }
 
 impl HardAuthRule {
-    fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
-        match self {
-            HardAuthRule::ProofRule(rule) => rule.check(auth_zones),
-            HardAuthRule::AnyOf(rules) => {
-                if !rules.iter().any(|r| r.check(auth_zones).is_ok()) {
-                    return Err(NotAuthorized);
-                }
-                Ok(())
-            }
-            HardAuthRule::AllOf(rules) => {
-                if rules.iter().any(|r| r.check(auth_zones).is_err()) {
-                    return Err(NotAuthorized);
-                }
-                Ok(())
-            }
+    /// Evaluates this rule against the given auth zones and callers, building the trace of the
+    /// evaluation only if `traced` is set.
+    fn evaluate(
+        &self,
+        auth_zones: &[&AuthZone],
+        callers: &[&ScryptoActorInfo],
+        traced: bool,
+    ) -> (bool, Option<AuthRuleTrace>) {
+        let (node, rules) = match self {
+            HardAuthRule::ProofRule(rule) => return rule.evaluate(auth_zones, callers, traced),
+            HardAuthRule::AnyOf(rules) => (AuthRuleTraceNode::AnyOf, rules),
+            HardAuthRule::AllOf(rules) => (AuthRuleTraceNode::AllOf, rules),
+        };
+        if !traced {
+            let passed = match self {
+                HardAuthRule::AnyOf(_) => rules
+                    .iter()
+                    .any(|r| r.evaluate(auth_zones, callers, false).0),
+                _ => rules
+                    .iter()
+                    .all(|r| r.evaluate(auth_zones, callers, false).0),
+            };
+            return (passed, None);
         }
+        let children: Vec<AuthRuleTrace> = rules
+            .iter()
+            .filter_map(|r| r.evaluate(auth_zones, callers, true).1)
+            .collect();
+        let passed = match self {
+            HardAuthRule::AnyOf(_) => children.iter().any(|child| child.passed),
+            _ => children.iter().all(|child| child.passed),
+        };
+        let trace = AuthRuleTrace {
+            node,
+            passed,
+            children,
+        };
+        (passed, Some(trace))
     }
 }
 
@@ -193,12 +326,158 @@ pub enum MethodAuthorization {
 }
 
 impl MethodAuthorization {
-    pub fn check(&self, auth_zones: &[&AuthZone]) -> Result<(), MethodAuthorizationError> {
+    /// Checks the proofs in the given auth zones, and the actors the call comes from,
+    /// against this authorization.
+    pub fn check(
+        &self,
+        auth_zones: &[&AuthZone],
+        callers: &[&ScryptoActorInfo],
+    ) -> Result<(), MethodAuthorizationError> {
         match self {
-            MethodAuthorization::Protected(rule) => rule.check(auth_zones),
+            MethodAuthorization::Protected(rule) => {
+                let (passed, _) = rule.evaluate(auth_zones, callers, false);
+                if passed {
+                    Ok(())
+                } else {
+                    Err(NotAuthorized)
+                }
+            }
             MethodAuthorization::AllowAll => Ok(()),
-            MethodAuthorization::DenyAll => Err(MethodAuthorizationError::NotAuthorized),
+            MethodAuthorization::DenyAll => Err(NotAuthorized),
             MethodAuthorization::Unsupported => Err(MethodAuthorizationError::UnsupportedMethod),
         }
     }
+
+    /// Same as `check`, but also returns how each rule was evaluated and what was presented.
+    ///
+    /// Building the trace clones the rule tree and the presented proofs, so prefer `check`
+    /// unless the trace is needed.
+    pub fn check_with_trace(
+        &self,
+        auth_zones: &[&AuthZone],
+        callers: &[&ScryptoActorInfo],
+    ) -> (Result<(), MethodAuthorizationError>, AuthorizationTrace) {
+        let (result, rule) = match self {
+            MethodAuthorization::Protected(rule) => {
+                let (passed, trace) = rule.evaluate(auth_zones, callers, true);
+                let result = if passed { Ok(()) } else { Err(NotAuthorized) };
+                (result, trace)
+            }
+            MethodAuthorization::AllowAll => (Ok(()), None),
+            MethodAuthorization::DenyAll => (Err(NotAuthorized), None),
+            MethodAuthorization::Unsupported => {
+                (Err(MethodAuthorizationError::UnsupportedMethod), None)
+            }
+        };
+        let trace = AuthorizationTrace {
+            rule,
+            proofs: auth_zones
+                .iter()
+                .flat_map(|auth_zone| auth_zone.proofs.iter())
+                .map(|proof| (proof.resource_address(), proof.total_locked()))
+                .collect(),
+            callers: callers.iter().map(|actor| (*actor).clone()).collect(),
+        };
+        (result, trace)
+    }
+}
+
+/// A node of an auth rule tree, as shown in an evaluation trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthRuleTraceNode {
+    AnyOf,
+    AllOf,
+    CountOf(HardCount),
+    Require(HardResourceOrNonFungible),
+    AmountOf {
+        resource: HardResourceOrNonFungible,
+        required: HardDecimal,
+        available: Decimal,
+    },
+    RequireCaller(CallerRequirement),
+    ResourceListNotFound,
+}
+
+impl fmt::Display for AuthRuleTraceNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuthRuleTraceNode::AnyOf => write!(f, "AnyOf"),
+            AuthRuleTraceNode::AllOf => write!(f, "AllOf"),
+            AuthRuleTraceNode::CountOf(HardCount::Count(count)) => write!(f, "CountOf({})", count),
+            AuthRuleTraceNode::CountOf(HardCount::SoftCountNotFound) => {
+                write!(f, "CountOf(<count not found>)")
+            }
+            AuthRuleTraceNode::Require(resource) => write!(f, "Require({})", resource),
+            AuthRuleTraceNode::AmountOf {
+                resource,
+                required,
+                available,
+            } => {
+                write!(f, "AmountOf({}): required ", resource)?;
+                match required {
+                    HardDecimal::Amount(amount) => write!(f, "{}", amount)?,
+                    HardDecimal::SoftDecimalNotFound => write!(f, "<amount not found>")?,
+                }
+                write!(f, ", available {}", available)
+            }
+            AuthRuleTraceNode::RequireCaller(caller) => write!(f, "RequireCaller({:?})", caller),
+            AuthRuleTraceNode::ResourceListNotFound => write!(f, "<resource list not found>"),
+        }
+    }
+}
+
+/// The evaluation of a node of an auth rule tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthRuleTrace {
+    pub node: AuthRuleTraceNode,
+    pub passed: bool,
+    pub children: Vec<AuthRuleTrace>,
+}
+
+impl AuthRuleTrace {
+    fn leaf(node: AuthRuleTraceNode, passed: bool) -> Self {
+        Self {
+            node,
+            passed,
+            children: Vec::new(),
+        }
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter, indent: &str, last: bool) -> fmt::Result {
+        write!(
+            f,
+            "{}{} {} {}",
+            indent,
+            if last { "└─" } else { "├─" },
+            if self.passed {
+                "✓".green()
+            } else {
+                "✗".red()
+            },
+            self.node
+        )?;
+        let indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+        for (i, child) in self.children.iter().enumerate() {
+            writeln!(f)?;
+            child.fmt_tree(f, &indent, i == self.children.len() - 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AuthRuleTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_tree(f, "", true)
+    }
+}
+
+/// How a method authorization was evaluated, and what the caller presented to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationTrace {
+    /// The evaluated rule tree, absent if the method is not protected by a rule.
+    pub rule: Option<AuthRuleTrace>,
+    /// The resource and amount or IDs locked by each proof in the auth zones.
+    pub proofs: Vec<(ResourceAddress, LockedAmountOrIds)>,
+    /// The actors the call came from.
+    pub callers: Vec<ScryptoActorInfo>,
 }
@@ -1,4 +1,5 @@
 mod auth_converter;
+mod auth_evaluator;
 mod auth_zone;
 mod bucket;
 mod component;
@@ -12,15 +13,21 @@ mod resource_manager;
 mod transaction;
 mod transaction_process;
 mod validated_transaction;
+mod validation_config;
 mod vault;
 mod worktop;
 
 pub use auth_zone::{AuthZone, AuthZoneError};
-pub use auth_converter::convert;
+pub use auth_converter::{convert, convert_with_lazy_maps, invalid_access_rule_paths};
+pub use auth_evaluator::{
+    evaluate_component_method_auth, evaluate_resource_method_auth, AuthEvaluation,
+    AuthEvaluationError, HypotheticalProof,
+};
 pub use bucket::{Bucket, BucketError};
-pub use component::Component;
+pub use component::{AccessRulesError, Component};
 pub use method_authorization::{
-    HardProofRule, HardResourceOrNonFungible, MethodAuthorization, MethodAuthorizationError,
+    AuthRuleTrace, AuthRuleTraceNode, AuthorizationTrace, HardCount, HardDecimal, HardProofRule,
+    HardResourceOrNonFungible, MethodAuthorization, MethodAuthorizationError,
 };
 pub use non_fungible::NonFungible;
 pub use package::{Package, PackageError};
@@ -30,8 +37,10 @@ pub use resource::*;
 pub use resource_manager::{ResourceManager, ResourceManagerError};
 pub use transaction_process::{TransactionProcess};
 pub use transaction::{
-    Instruction, SignedTransaction, Transaction,
+    AmountCondition, Instruction, PartiallySignedTransaction, SignedSubintent, SignedTransaction,
+    Subintent, Transaction,
 };
-pub use validated_transaction::{ValidatedTransaction, ValidatedInstruction};
-pub use vault::{Vault, VaultError};
+pub use validated_transaction::{ValidatedInstruction, ValidatedSubintent, ValidatedTransaction};
+pub use validation_config::{ValidationConfig, LOCAL_SIMULATOR_NETWORK_ID};
+pub use vault::{TimeLock, Vault, VaultError};
 pub use worktop::{Worktop, WorktopError};
@@ -2,6 +2,7 @@ use sbor::*;
 use scrypto::abi::{Function, Method};
 use scrypto::buffer::scrypto_decode;
 use scrypto::rust::collections::HashMap;
+use scrypto::rust::format;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
@@ -74,29 +75,7 @@ impl Package {
         let mut blueprints = HashMap::new();
 
         for method_name in blueprint_abi_methods {
-            let rtn = module
-                .invoke_export(&method_name, &[], &mut NopExternals)
-                .map_err(|e| WasmValidationError::NoPackageInitExport(e.into()))?
-                .ok_or(WasmValidationError::InvalidPackageInit)?;
-
-            let blueprint_type: Type = match rtn {
-                RuntimeValue::I32(ptr) => {
-                    let len: u32 = memory
-                        .get_value(ptr as u32)
-                        .map_err(|_| WasmValidationError::InvalidPackageInit)?;
-
-                    // SECURITY: meter before allocating memory
-                    let mut data = vec![0u8; len as usize];
-                    memory
-                        .get_into((ptr + 4) as u32, &mut data)
-                        .map_err(|_| WasmValidationError::InvalidPackageInit)?;
-
-                    let result: (Type, Vec<Function>, Vec<Method>) = scrypto_decode(&data)
-                        .map_err(|_| WasmValidationError::InvalidPackageInit)?;
-                    Ok(result.0)
-                }
-                _ => Err(WasmValidationError::InvalidPackageInit),
-            }?;
+            let (blueprint_type, _, _) = Self::export_abi(&module, &memory, &method_name)?;
 
             if let Type::Struct { name, fields: _ } = &blueprint_type {
                 blueprints.insert(name.clone(), blueprint_type);
@@ -122,12 +101,59 @@ impl Package {
             .ok_or(PackageError::BlueprintNotFound)
     }
 
+    /// Loads the methods of a blueprint, by calling its ABI export.
+    ///
+    /// Methods aren't part of the persisted package, so that packages stored before they were
+    /// needed remain readable.
+    pub fn load_blueprint_methods(
+        &self,
+        blueprint_name: &str,
+    ) -> Result<Vec<Method>, PackageError> {
+        if !self.contains_blueprint(blueprint_name) {
+            return Err(PackageError::BlueprintNotFound);
+        }
+        let (module, memory) = self.load_module()?;
+        let (_, _, methods) =
+            Self::export_abi(&module, &memory, &format!("{}_abi", blueprint_name))
+                .map_err(PackageError::WasmValidationError)?;
+        Ok(methods)
+    }
+
     pub fn load_module(&self) -> Result<(ModuleRef, MemoryRef), PackageError> {
         let module = Self::parse_module(&self.code).unwrap();
         let inst = Self::instantiate_module(&module).unwrap();
         Ok(inst)
     }
 
+    /// Calls the ABI export of a blueprint, which returns its schema, functions and methods.
+    fn export_abi(
+        module: &ModuleRef,
+        memory: &MemoryRef,
+        export_name: &str,
+    ) -> Result<(Type, Vec<Function>, Vec<Method>), WasmValidationError> {
+        let rtn = module
+            .invoke_export(export_name, &[], &mut NopExternals)
+            .map_err(|e| WasmValidationError::NoPackageInitExport(e.into()))?
+            .ok_or(WasmValidationError::InvalidPackageInit)?;
+
+        match rtn {
+            RuntimeValue::I32(ptr) => {
+                let len: u32 = memory
+                    .get_value(ptr as u32)
+                    .map_err(|_| WasmValidationError::InvalidPackageInit)?;
+
+                // SECURITY: meter before allocating memory
+                let mut data = vec![0u8; len as usize];
+                memory
+                    .get_into((ptr + 4) as u32, &mut data)
+                    .map_err(|_| WasmValidationError::InvalidPackageInit)?;
+
+                scrypto_decode(&data).map_err(|_| WasmValidationError::InvalidPackageInit)
+            }
+            _ => Err(WasmValidationError::InvalidPackageInit),
+        }
+    }
+
     fn parse_module(code: &[u8]) -> Result<Module, WasmValidationError> {
         Module::from_buffer(code).map_err(|_| WasmValidationError::InvalidModule)
     }
@@ -35,6 +35,8 @@ pub enum ProofError {
     EmptyProofNotAllowed,
     /// The base proofs are not enough to cover the requested amount or non-fungible ids.
     InsufficientBaseProofs,
+    /// Can't compose restricted proofs into new proofs.
+    RestrictedProofNotComposable,
     /// Can't apply a non-fungible operation on fungible proofs.
     NonFungibleOperationNotAllowed,
     /// Can't apply a fungible operation on non-fungible proofs.
@@ -78,11 +80,25 @@ impl Proof {
             .filter(|p| p.resource_address() == resource_address && !p.is_restricted())
             .collect();
 
+        Self::compute_total_locked_of(&proofs, resource_type)
+    }
+
+    /// Computes the locked amount or non-fungible IDs of proofs of the same resource, in total
+    /// and per resource container.
+    ///
+    /// Containers referenced by several proofs are only counted once, with the max they lock.
+    pub fn compute_total_locked_of(
+        proofs: &[&Proof],
+        resource_type: ResourceType,
+    ) -> (
+        LockedAmountOrIds,
+        HashMap<ResourceContainerId, LockedAmountOrIds>,
+    ) {
         // calculate the max locked amount (or ids) of each container
         match resource_type {
             ResourceType::Fungible { .. } => {
                 let mut max = HashMap::<ResourceContainerId, Decimal>::new();
-                for proof in &proofs {
+                for proof in proofs {
                     for (container_id, (_, locked_amount_or_ids)) in &proof.evidence {
                         let new_amount = locked_amount_or_ids.amount();
                         if let Some(existing) = max.get_mut(&container_id) {
@@ -103,9 +119,9 @@ impl Proof {
                     .collect();
                 (LockedAmountOrIds::Amount(total), per_container)
             }
-            ResourceType::NonFungible => {
+            ResourceType::NonFungible { .. } => {
                 let mut max = HashMap::<ResourceContainerId, BTreeSet<NonFungibleId>>::new();
-                for proof in &proofs {
+                for proof in proofs {
                     for (container_id, (_, locked_amount_or_ids)) in &proof.evidence {
                         let new_ids = locked_amount_or_ids.ids().unwrap();
                         if let Some(ids) = max.get_mut(&container_id) {
@@ -128,6 +144,22 @@ impl Proof {
         }
     }
 
+    fn has_restricted(proofs: &[Proof], resource_address: ResourceAddress) -> bool {
+        proofs
+            .iter()
+            .any(|p| p.resource_address() == resource_address && p.is_restricted())
+    }
+
+    /// Restricted proofs are ignored when composing, so if there are any they're the likely
+    /// reason the base proofs fall short.
+    fn insufficient_base_proofs(proofs: &[Proof], resource_address: ResourceAddress) -> ProofError {
+        if Self::has_restricted(proofs, resource_address) {
+            ProofError::RestrictedProofNotComposable
+        } else {
+            ProofError::InsufficientBaseProofs
+        }
+    }
+
     /// Creates a composite proof from proofs. This method will generate a max proof.
     pub fn compose(
         proofs: &[Proof],
@@ -135,6 +167,9 @@ impl Proof {
         resource_type: ResourceType,
     ) -> Result<Proof, ProofError> {
         let (total, _) = Self::compute_total_locked(proofs, resource_address, resource_type);
+        if total.is_empty() && Self::has_restricted(proofs, resource_address) {
+            return Err(ProofError::RestrictedProofNotComposable);
+        }
         match total {
             LockedAmountOrIds::Amount(amount) => {
                 Self::compose_by_amount(proofs, amount, resource_address, resource_type)
@@ -157,7 +192,7 @@ impl Proof {
         match total_locked {
             LockedAmountOrIds::Amount(locked_amount) => {
                 if amount > locked_amount {
-                    return Err(ProofError::InsufficientBaseProofs);
+                    return Err(Self::insufficient_base_proofs(proofs, resource_address));
                 }
 
                 // Locked the max (or needed) amount from the containers, in the order that the containers were referenced.
@@ -194,7 +229,7 @@ impl Proof {
             }
             LockedAmountOrIds::Ids(locked_ids) => {
                 if amount > locked_ids.len().into() {
-                    Err(ProofError::InsufficientBaseProofs)
+                    Err(Self::insufficient_base_proofs(proofs, resource_address))
                 } else {
                     let n: usize = amount.to_string().parse().unwrap();
                     let ids: BTreeSet<NonFungibleId> = locked_ids.iter().cloned().take(n).collect();
@@ -217,7 +252,7 @@ impl Proof {
             LockedAmountOrIds::Amount(_) => Err(ProofError::NonFungibleOperationNotAllowed),
             LockedAmountOrIds::Ids(locked_ids) => {
                 if !locked_ids.is_superset(ids) {
-                    return Err(ProofError::InsufficientBaseProofs);
+                    return Err(Self::insufficient_base_proofs(proofs, resource_address));
                 }
 
                 // Locked the max (or needed) ids from the containers, in the order that the containers were referenced.
@@ -296,12 +331,9 @@ impl Proof {
         }
     }
 
-    // FIXME: this is a hack for now until we can get snode_state into process
-    // FIXME: and be able to determine which snode the proof is going into
-    pub fn change_to_unrestricted(&mut self) {
-        self.restricted = false;
-    }
-
+    /// Restricts this proof to the call frame holding it. A restricted proof can still be used
+    /// for authorization, but can't be passed to or returned from a blueprint function or
+    /// component method, nor be composed into new proofs. Restriction can't be undone.
     pub fn change_to_restricted(&mut self) {
         self.restricted = true;
     }
@@ -310,6 +342,14 @@ impl Proof {
         self.resource_address
     }
 
+    pub fn resource_type(&self) -> ResourceType {
+        self.resource_type
+    }
+
+    pub fn total_locked(&self) -> LockedAmountOrIds {
+        self.total_locked.clone()
+    }
+
     pub fn total_amount(&self) -> Decimal {
         self.total_locked.amount()
     }
@@ -337,6 +377,11 @@ impl Proof {
                 Ok(ScryptoValue::from_value(&ids))
             },
             "get_resource_address" => Ok(ScryptoValue::from_value(&self.resource_address())),
+            "is_restricted" => Ok(ScryptoValue::from_value(&self.is_restricted())),
+            "restrict" => {
+                self.change_to_restricted();
+                Ok(ScryptoValue::from_value(&()))
+            },
             "clone" => {
                 let cloned_proof = self.clone();
                 let proof_id = system_api.create_proof(cloned_proof).map_err(|_| ProofError::CouldNotCreateProof)?;
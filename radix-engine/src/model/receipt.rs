@@ -8,7 +8,7 @@ use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
-use crate::engine::CommitReceipt;
+use crate::engine::{CallFrame, CommitReceipt, ExecutionTrace};
 use crate::errors::*;
 use crate::model::*;
 
@@ -18,6 +18,10 @@ pub struct Receipt {
     pub validated_transaction: ValidatedTransaction,
     pub result: Result<(), RuntimeError>,
     pub outputs: Vec<ScryptoValue>,
+    /// The calls the error propagated through, innermost first.
+    pub call_stack: Vec<CallFrame>,
+    /// The execution trace, if the executor records one.
+    pub execution_trace: Option<ExecutionTrace>,
     pub logs: Vec<(Level, String)>,
     pub new_package_addresses: Vec<PackageAddress>,
     pub new_component_addresses: Vec<ComponentAddress>,
@@ -57,6 +61,76 @@ impl fmt::Debug for Receipt {
                 .unwrap_or(String::from("?"))
         )?;
 
+        if let Err(RuntimeError::AuthorizationError {
+            authorization,
+            trace,
+            ..
+        }) = &self.result
+        {
+            write!(f, "\n{}", "Authorization Trace:".bold().green())?;
+            match &trace.rule {
+                Some(rule) => write!(f, "\n{}", rule)?,
+                None => write!(f, "\n└─ {} {:?}", "✗".red(), authorization)?,
+            }
+
+            write!(f, "\n{}", "Auth Zone:".bold().green())?;
+            let total = trace.proofs.len() + trace.callers.len();
+            for (i, (resource_address, locked)) in trace.proofs.iter().enumerate() {
+                write!(
+                    f,
+                    "\n{} Proof: {}, {}",
+                    if i == total - 1 { "└─" } else { "├─" },
+                    resource_address,
+                    match locked {
+                        LockedAmountOrIds::Amount(amount) => format!("amount: {}", amount),
+                        LockedAmountOrIds::Ids(ids) => format!("ids: {:?}", ids),
+                    }
+                )?;
+            }
+            for (i, caller) in trace.callers.iter().enumerate() {
+                write!(
+                    f,
+                    "\n{} Caller: {:?}",
+                    if trace.proofs.len() + i == total - 1 {
+                        "└─"
+                    } else {
+                        "├─"
+                    },
+                    caller.actor()
+                )?;
+            }
+        }
+
+        if !self.call_stack.is_empty() {
+            write!(f, "\n{}", "Call Stack:".bold().green())?;
+            for (i, frame) in self.call_stack.iter().enumerate() {
+                write!(
+                    f,
+                    "\n{} #{} {:?}::{}",
+                    prefix!(i, self.call_stack),
+                    frame.depth,
+                    frame.snode_ref,
+                    frame.function
+                )?;
+                if let Some(actor) = &frame.actor {
+                    write!(
+                        f,
+                        "\n{}    at {}::{}",
+                        if i == self.call_stack.len() - 1 {
+                            "  "
+                        } else {
+                            "│ "
+                        },
+                        actor.package_address(),
+                        actor.blueprint_name()
+                    )?;
+                    if let Some(component_address) = actor.component_address() {
+                        write!(f, " ({})", component_address)?;
+                    }
+                }
+            }
+        }
+
         write!(f, "\n{}", "Instructions:".bold().green())?;
         for (i, inst) in self.validated_transaction.instructions.iter().enumerate() {
             write!(
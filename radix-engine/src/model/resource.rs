@@ -20,6 +20,8 @@ pub enum ResourceContainerError {
     NonFungibleOperationNotAllowed,
     /// Resource container is locked because there exists proof(s).
     ContainerLocked,
+    /// The non-fungible ID type does not match the resource.
+    InvalidNonFungibleIdType(NonFungibleId, NonFungibleIdType),
 }
 
 #[derive(Debug, TypeId, Encode, Decode)]
@@ -37,6 +39,8 @@ pub enum ResourceContainer {
     NonFungible {
         /// The resource address.
         resource_address: ResourceAddress,
+        /// The type of non-fungible ids.
+        id_type: NonFungibleIdType,
         /// The locked non-fungible ids and the corresponding times of being locked.
         locked_ids: HashMap<NonFungibleId, usize>,
         /// The liquid non-fungible ids.
@@ -47,7 +51,7 @@ pub enum ResourceContainer {
 /// The locked amount or non-fungible IDs.
 ///
 /// Invariant: always consistent with resource fungibility.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
 pub enum LockedAmountOrIds {
     Amount(Decimal),
     Ids(BTreeSet<NonFungibleId>),
@@ -96,13 +100,22 @@ impl ResourceContainer {
 
     pub fn new_non_fungible(
         resource_address: ResourceAddress,
+        id_type: NonFungibleIdType,
         ids: BTreeSet<NonFungibleId>,
-    ) -> Self {
-        Self::NonFungible {
+    ) -> Result<Self, ResourceContainerError> {
+        if let Some(id) = ids.iter().find(|id| id.id_type() != id_type) {
+            return Err(ResourceContainerError::InvalidNonFungibleIdType(
+                id.clone(),
+                id_type,
+            ));
+        }
+
+        Ok(Self::NonFungible {
             resource_address,
+            id_type,
             locked_ids: HashMap::new(),
-            liquid_ids: ids.clone(),
-        }
+            liquid_ids: ids,
+        })
     }
 
     pub fn new_empty(resource_address: ResourceAddress, resource_type: ResourceType) -> Self {
@@ -110,7 +123,12 @@ impl ResourceContainer {
             ResourceType::Fungible { divisibility } => {
                 Self::new_fungible(resource_address, divisibility, Decimal::zero())
             }
-            ResourceType::NonFungible => Self::new_non_fungible(resource_address, BTreeSet::new()),
+            ResourceType::NonFungible { id_type } => Self::NonFungible {
+                resource_address,
+                id_type,
+                locked_ids: HashMap::new(),
+                liquid_ids: BTreeSet::new(),
+            },
         }
     }
 
@@ -136,6 +154,15 @@ impl ResourceContainer {
     }
 
     pub fn take_by_amount(&mut self, amount: Decimal) -> Result<Self, ResourceContainerError> {
+        self.take_by_amount_excluding(amount, &BTreeSet::new())
+    }
+
+    /// Takes some amount of resource, leaving the excluded non-fungibles in this container.
+    pub fn take_by_amount_excluding(
+        &mut self,
+        amount: Decimal,
+        excluded_ids: &BTreeSet<NonFungibleId>,
+    ) -> Result<Self, ResourceContainerError> {
         // check amount granularity
         let divisibility = self.resource_type().divisibility();
         Self::check_amount(amount, divisibility)?;
@@ -158,7 +185,14 @@ impl ResourceContainer {
                     return Err(ResourceContainerError::InsufficientBalance);
                 }
                 let n: usize = amount.to_string().parse().unwrap();
-                let ids: BTreeSet<NonFungibleId> = liquid_ids.iter().cloned().take(n).collect();
+                let ids: BTreeSet<NonFungibleId> = liquid_ids
+                    .difference(excluded_ids)
+                    .cloned()
+                    .take(n)
+                    .collect();
+                if ids.len() < n {
+                    return Err(ResourceContainerError::InsufficientBalance);
+                }
                 self.take_by_ids(&ids)
             }
         }
@@ -170,13 +204,18 @@ impl ResourceContainer {
     ) -> Result<Self, ResourceContainerError> {
         match self {
             Self::Fungible { .. } => Err(ResourceContainerError::NonFungibleOperationNotAllowed),
-            Self::NonFungible { liquid_ids, .. } => {
+            Self::NonFungible {
+                resource_address,
+                id_type,
+                liquid_ids,
+                ..
+            } => {
                 for id in ids {
                     if !liquid_ids.remove(&id) {
                         return Err(ResourceContainerError::InsufficientBalance);
                     }
                 }
-                Ok(Self::new_non_fungible(self.resource_address(), ids.clone()))
+                Self::new_non_fungible(*resource_address, *id_type, ids.clone())
             }
         }
     }
@@ -387,7 +426,7 @@ impl ResourceContainer {
             Self::Fungible { divisibility, .. } => ResourceType::Fungible {
                 divisibility: *divisibility,
             },
-            Self::NonFungible { .. } => ResourceType::NonFungible,
+            Self::NonFungible { id_type, .. } => ResourceType::NonFungible { id_type: *id_type },
         }
     }
 
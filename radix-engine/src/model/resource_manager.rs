@@ -6,7 +6,9 @@ use scrypto::engine::types::*;
 use scrypto::prelude::AccessRule::{AllowAll, DenyAll};
 use scrypto::prelude::ResourceMethod::Withdraw;
 use scrypto::resource::Mutability::LOCKED;
-use scrypto::resource::ResourceMethod::{Burn, Mint, UpdateMetadata, UpdateNonFungibleData};
+use scrypto::resource::ResourceMethod::{
+    Burn, Freeze, Mint, Recall, UpdateMetadata, UpdateNonFungibleData,
+};
 use scrypto::resource::*;
 use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
@@ -14,7 +16,9 @@ use scrypto::rust::string::ToString;
 use scrypto::rust::vec::*;
 use scrypto::values::ScryptoValue;
 
-use crate::model::{convert, MethodAuthorization, ResourceContainer};
+use crate::model::{
+    convert, MethodAuthorization, ResourceContainer, ResourceContainerError, Vault, VaultError,
+};
 
 /// Converts soft authorization rule to a hard authorization rule.
 /// Currently required as all auth is defined by soft authorization rules.
@@ -33,12 +37,18 @@ pub enum ResourceManagerError {
     InvalidMintPermission,
     ResourceTypeDoesNotMatch,
     MaxMintAmountExceeded,
+    MaxSupplyExceeded,
     InvalidNonFungibleData,
     NonFungibleAlreadyExists(NonFungibleAddress),
     NonFungibleNotFound(NonFungibleAddress),
+    NonFungibleImmutableDataChanged(NonFungibleAddress),
     InvalidRequestData(DecodeError),
     MethodNotFound(String),
     CouldNotCreateBucket,
+    VaultNotFound(VaultId),
+    VaultResourceMismatch(VaultId),
+    VaultError(VaultError),
+    ResourceContainerError(ResourceContainerError),
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -108,6 +118,8 @@ pub struct ResourceManager {
     method_table: HashMap<String, Option<ResourceMethod>>,
     authorization: HashMap<ResourceMethod, MethodEntry>,
     total_supply: Decimal,
+    non_fungible_data_schema: Option<NonFungibleDataSchema>,
+    max_supply: Option<Decimal>,
 }
 
 impl ResourceManager {
@@ -115,15 +127,28 @@ impl ResourceManager {
         resource_type: ResourceType,
         metadata: HashMap<String, String>,
         mut auth: HashMap<ResourceMethod, (AccessRule, Mutability)>,
+        non_fungible_data_schema: Option<NonFungibleDataSchema>,
+        max_supply: Option<Decimal>,
     ) -> Result<Self, ResourceManagerError> {
+        if non_fungible_data_schema.is_some()
+            && !matches!(resource_type, ResourceType::NonFungible { .. })
+        {
+            return Err(ResourceManagerError::ResourceTypeDoesNotMatch);
+        }
+
         let mut method_table: HashMap<String, Option<ResourceMethod>> = HashMap::new();
         method_table.insert("mint".to_string(), Some(Mint));
         method_table.insert("burn".to_string(), Some(Burn));
         method_table.insert("take_from_vault".to_string(), Some(Withdraw));
+        method_table.insert("lock_fee".to_string(), Some(Withdraw));
         method_table.insert("put_into_vault".to_string(), Some(Deposit));
         method_table.insert("update_metadata".to_string(), Some(UpdateMetadata));
-        if let ResourceType::NonFungible = resource_type {
+        method_table.insert("recall".to_string(), Some(Recall));
+        method_table.insert("freeze_vault".to_string(), Some(Freeze));
+        method_table.insert("unfreeze_vault".to_string(), Some(Freeze));
+        if let ResourceType::NonFungible { .. } = resource_type {
             method_table.insert("take_non_fungibles_from_vault".to_string(), Some(Withdraw));
+            method_table.insert("recall_non_fungibles".to_string(), Some(Recall));
         }
 
         for pub_method in [
@@ -133,6 +158,7 @@ impl ResourceManager {
             "get_metadata",
             "get_resource_type",
             "get_total_supply",
+            "get_max_supply",
             "take_from_bucket",
             "put_into_bucket",
             "get_bucket_amount",
@@ -142,11 +168,15 @@ impl ResourceManager {
             "create_vault_proof",
             "create_vault_proof_by_amount",
             "create_vault_proof_by_ids",
+            "is_vault_frozen",
+            "lock_vault_until",
+            "add_vault_vesting_schedule",
+            "get_vault_time_locked_amount",
         ] {
             method_table.insert(pub_method.to_string(), None);
         }
 
-        if let ResourceType::NonFungible = resource_type {
+        if let ResourceType::NonFungible { .. } = resource_type {
             method_table.insert(
                 "update_non_fungible_mutable_data".to_string(),
                 Some(UpdateNonFungibleData),
@@ -157,6 +187,7 @@ impl ResourceManager {
                 "get_non_fungible",
                 "get_non_fungible_ids_in_bucket",
                 "get_non_fungible_ids_in_vault",
+                "lock_non_fungibles_in_vault_until",
             ] {
                 method_table.insert(pub_method.to_string(), None);
             }
@@ -170,6 +201,8 @@ impl ResourceManager {
             (Deposit, (AllowAll, LOCKED)),
             (UpdateMetadata, (DenyAll, LOCKED)),
             (UpdateNonFungibleData, (DenyAll, LOCKED)),
+            (Recall, (DenyAll, LOCKED)),
+            (Freeze, (DenyAll, LOCKED)),
         ] {
             let entry = auth.remove(&auth_entry_key).unwrap_or(default);
             authorization.insert(auth_entry_key, MethodEntry::new(entry));
@@ -181,8 +214,14 @@ impl ResourceManager {
             method_table,
             authorization,
             total_supply: 0.into(),
+            non_fungible_data_schema,
+            max_supply,
         };
 
+        if let Some(max_supply) = max_supply {
+            resource_manager.check_amount(max_supply)?;
+        }
+
         Ok(resource_manager)
     }
 
@@ -202,6 +241,9 @@ impl ResourceManager {
             }
         } else {
             match self.method_table.get(method_name) {
+                // Resource managers persisted before fees could be locked don't have `lock_fee`
+                // in their method table, so it's authorized as a withdrawal for them too
+                None if method_name == "lock_fee" => self.get_method_auth(&Withdraw),
                 None => &MethodAuthorization::Unsupported,
                 Some(None) => &MethodAuthorization::AllowAll,
                 Some(Some(method)) => self.authorization.get(method).unwrap().get_method_auth(),
@@ -209,6 +251,14 @@ impl ResourceManager {
         }
     }
 
+    /// Returns the authorization of a resource method.
+    pub fn get_method_auth(&self, method: &ResourceMethod) -> &MethodAuthorization {
+        match self.authorization.get(method) {
+            None => &MethodAuthorization::Unsupported,
+            Some(entry) => entry.get_method_auth(),
+        }
+    }
+
     pub fn resource_type(&self) -> ResourceType {
         self.resource_type
     }
@@ -221,6 +271,14 @@ impl ResourceManager {
         self.total_supply
     }
 
+    pub fn non_fungible_data_schema(&self) -> Option<&NonFungibleDataSchema> {
+        self.non_fungible_data_schema.as_ref()
+    }
+
+    pub fn max_supply(&self) -> Option<Decimal> {
+        self.max_supply
+    }
+
     fn mint<S: SystemApi>(
         &mut self,
         mint_params: MintParams,
@@ -249,6 +307,7 @@ impl ResourceManager {
             if amount > 100_000_000_000i128.into() {
                 return Err(ResourceManagerError::MaxMintAmountExceeded);
             }
+            self.check_max_supply(amount)?;
 
             self.total_supply += amount;
 
@@ -262,9 +321,17 @@ impl ResourceManager {
         }
     }
 
-    fn process_non_fungible_data(data: &[u8]) -> Result<ScryptoValue, ResourceManagerError> {
+    fn process_non_fungible_data(
+        data: &[u8],
+        schema: Option<&Type>,
+    ) -> Result<ScryptoValue, ResourceManagerError> {
         let validated = ScryptoValue::from_slice(data)
             .map_err(|_| ResourceManagerError::InvalidNonFungibleData)?;
+        if let Some(schema) = schema {
+            if !validated.matches_schema(schema) {
+                return Err(ResourceManagerError::InvalidNonFungibleData);
+            }
+        }
         if !validated.bucket_ids.is_empty() {
             return Err(ResourceManagerError::InvalidNonFungibleData);
         }
@@ -287,24 +354,35 @@ impl ResourceManager {
         system_api: &mut S,
     ) -> Result<ResourceContainer, ResourceManagerError> {
         // check resource type
-        if !matches!(self.resource_type, ResourceType::NonFungible) {
-            return Err(ResourceManagerError::ResourceTypeDoesNotMatch);
-        }
+        let id_type = match self.resource_type {
+            ResourceType::NonFungible { id_type } => id_type,
+            ResourceType::Fungible { .. } => {
+                return Err(ResourceManagerError::ResourceTypeDoesNotMatch)
+            }
+        };
 
         // check amount
         let amount = entries.len().into();
         self.check_amount(amount)?;
 
+        // check id types
+        let container = ResourceContainer::new_non_fungible(
+            self_address,
+            id_type,
+            entries.keys().cloned().collect(),
+        )
+        .map_err(ResourceManagerError::ResourceContainerError)?;
+
         // It takes `1,701,411,835` mint operations to reach `Decimal::MAX`,
         // which will be impossible with metering.
         if amount > 100_000_000_000i128.into() {
             return Err(ResourceManagerError::MaxMintAmountExceeded);
         }
+        self.check_max_supply(amount)?;
 
         self.total_supply += amount;
 
         // Allocate non-fungibles
-        let mut ids = BTreeSet::new();
         for (id, data) in entries {
             let non_fungible_address = NonFungibleAddress::new(self_address, id.clone());
             if system_api.get_non_fungible(&non_fungible_address).is_some() {
@@ -313,15 +391,17 @@ impl ResourceManager {
                 ));
             }
 
-            let immutable_data = Self::process_non_fungible_data(&data.0)?;
-            let mutable_data = Self::process_non_fungible_data(&data.1)?;
+            let schema = self.non_fungible_data_schema.as_ref();
+            let immutable_data =
+                Self::process_non_fungible_data(&data.0, schema.map(|s| &s.immutable))?;
+            let mutable_data =
+                Self::process_non_fungible_data(&data.1, schema.map(|s| &s.mutable))?;
             let non_fungible = NonFungible::new(immutable_data.raw, mutable_data.raw);
 
             system_api.set_non_fungible(non_fungible_address, Some(non_fungible));
-            ids.insert(id);
         }
 
-        Ok(ResourceContainer::new_non_fungible(self_address, ids))
+        Ok(container)
     }
 
     pub fn burn(&mut self, amount: Decimal) {
@@ -337,6 +417,23 @@ impl ResourceManager {
         Ok(())
     }
 
+    /// Borrows a vault of this resource, owned by the given component.
+    fn borrow_vault<S: SystemApi>(
+        resource_address: ResourceAddress,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        system_api: &mut S,
+    ) -> Result<Vault, ResourceManagerError> {
+        let vault = system_api
+            .borrow_global_mut_vault(component_address, vault_id)
+            .map_err(|_| ResourceManagerError::VaultNotFound(vault_id))?;
+        if vault.resource_address() != resource_address {
+            system_api.return_borrowed_global_vault(component_address, vault_id, vault);
+            return Err(ResourceManagerError::VaultResourceMismatch(vault_id));
+        }
+        Ok(vault)
+    }
+
     fn check_amount(&self, amount: Decimal) -> Result<(), ResourceManagerError> {
         let divisibility = self.resource_type.divisibility();
 
@@ -347,6 +444,15 @@ impl ResourceManager {
         }
     }
 
+    fn check_max_supply(&self, amount: Decimal) -> Result<(), ResourceManagerError> {
+        match self.max_supply {
+            Some(max_supply) if self.total_supply + amount > max_supply => {
+                Err(ResourceManagerError::MaxSupplyExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn static_main<S: SystemApi>(
         function: &str,
         args: Vec<ScryptoValue>,
@@ -362,7 +468,24 @@ impl ResourceManager {
                     .map_err(ResourceManagerError::InvalidRequestData)?;
                 let mint_params_maybe: Option<MintParams> = scrypto_decode(&args[3].raw)
                     .map_err(ResourceManagerError::InvalidRequestData)?;
-                let resource_manager = ResourceManager::new(resource_type, metadata, auth)?;
+                // Packages built against older Scrypto versions don't pass a schema
+                let non_fungible_data_schema: Option<NonFungibleDataSchema> = match args.get(4) {
+                    Some(arg) => scrypto_decode(&arg.raw)
+                        .map_err(ResourceManagerError::InvalidRequestData)?,
+                    None => None,
+                };
+                let max_supply: Option<Decimal> = match args.get(5) {
+                    Some(arg) => scrypto_decode(&arg.raw)
+                        .map_err(ResourceManagerError::InvalidRequestData)?,
+                    None => None,
+                };
+                let resource_manager = ResourceManager::new(
+                    resource_type,
+                    metadata,
+                    auth,
+                    non_fungible_data_schema,
+                    max_supply,
+                )?;
                 let resource_address = system_api.create_resource(resource_manager);
 
                 let bucket_id = if let Some(mint_params) = mint_params_maybe {
@@ -428,9 +551,55 @@ impl ResourceManager {
                     bucket_id,
                 )))
             }
+            "recall" | "recall_non_fungibles" => {
+                let component_address: ComponentAddress = scrypto_decode(&args[0].raw)
+                    .map_err(ResourceManagerError::InvalidRequestData)?;
+                let vault_id: VaultId = scrypto_decode(&args[1].raw)
+                    .map_err(ResourceManagerError::InvalidRequestData)?;
+                let mut vault =
+                    Self::borrow_vault(resource_address, component_address, vault_id, system_api)?;
+                // Recall ignores whether the vault is frozen, but not its time locks
+                let current_epoch = system_api.current_epoch();
+                let result = if function == "recall" {
+                    scrypto_decode(&args[2].raw)
+                        .map_err(ResourceManagerError::InvalidRequestData)
+                        .and_then(|amount| {
+                            vault
+                                .take(amount, current_epoch)
+                                .map_err(ResourceManagerError::VaultError)
+                        })
+                } else {
+                    scrypto_decode(&args[2].raw)
+                        .map_err(ResourceManagerError::InvalidRequestData)
+                        .and_then(|ids| {
+                            vault
+                                .take_non_fungibles(&ids, current_epoch)
+                                .map_err(ResourceManagerError::VaultError)
+                        })
+                };
+                system_api.return_borrowed_global_vault(component_address, vault_id, vault);
+                let bucket_id = system_api
+                    .create_bucket(result?)
+                    .map_err(|_| ResourceManagerError::CouldNotCreateBucket)?;
+                Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(
+                    bucket_id,
+                )))
+            }
+            "freeze_vault" | "unfreeze_vault" => {
+                let component_address: ComponentAddress = scrypto_decode(&args[0].raw)
+                    .map_err(ResourceManagerError::InvalidRequestData)?;
+                let vault_id: VaultId = scrypto_decode(&args[1].raw)
+                    .map_err(ResourceManagerError::InvalidRequestData)?;
+                let mut vault =
+                    Self::borrow_vault(resource_address, component_address, vault_id, system_api)?;
+                vault.set_frozen(function == "freeze_vault");
+                system_api.return_borrowed_global_vault(component_address, vault_id, vault);
+                Ok(ScryptoValue::from_value(&()))
+            }
             "get_metadata" => Ok(ScryptoValue::from_value(&self.metadata)),
             "get_resource_type" => Ok(ScryptoValue::from_value(&self.resource_type)),
             "get_total_supply" => Ok(ScryptoValue::from_value(&self.total_supply)),
+            "get_max_supply" => Ok(ScryptoValue::from_value(&self.max_supply)),
             "update_metadata" => {
                 let new_metadata: HashMap<String, String> = scrypto_decode(&args[0].raw)
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
@@ -442,16 +611,32 @@ impl ResourceManager {
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
                 let new_mutable_data: Vec<u8> = scrypto_decode(&args[1].raw)
                     .map_err(|e| ResourceManagerError::InvalidRequestData(e))?;
+                let immutable_data: Vec<u8> = scrypto_decode(
+                    &args
+                        .get(2)
+                        .ok_or(ResourceManagerError::InvalidNonFungibleData)?
+                        .raw,
+                )
+                .map_err(ResourceManagerError::InvalidRequestData)?;
 
                 let non_fungible_address =
                     NonFungibleAddress::new(resource_address.clone(), non_fungible_id);
-                let data = Self::process_non_fungible_data(&new_mutable_data)?;
+                let schema = self.non_fungible_data_schema.as_ref();
+                let immutable_data =
+                    Self::process_non_fungible_data(&immutable_data, schema.map(|s| &s.immutable))?;
+                let data =
+                    Self::process_non_fungible_data(&new_mutable_data, schema.map(|s| &s.mutable))?;
                 let mut non_fungible = system_api
                     .get_non_fungible(&non_fungible_address)
                     .cloned()
                     .ok_or(ResourceManagerError::NonFungibleNotFound(
                         non_fungible_address.clone(),
                     ))?;
+                if non_fungible.immutable_data() != immutable_data.raw {
+                    return Err(ResourceManagerError::NonFungibleImmutableDataChanged(
+                        non_fungible_address,
+                    ));
+                }
                 non_fungible.set_mutable_data(data.raw);
                 system_api.set_non_fungible(non_fungible_address, Some(non_fungible));
 
@@ -1,7 +1,8 @@
 use sbor::*;
-use scrypto::buffer::scrypto_encode;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
+use scrypto::resource::SchemaPath;
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::string::String;
 use scrypto::rust::vec;
@@ -10,15 +11,22 @@ use scrypto::values::*;
 
 use crate::engine::*;
 use crate::errors::*;
-use crate::model::{ValidatedInstruction, ValidatedTransaction};
+use crate::model::{
+    ValidatedInstruction, ValidatedSubintent, ValidatedTransaction, ValidationConfig,
+};
 
 /// Represents an unsigned transaction
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct Transaction {
+    /// The network the transaction is intended for
+    pub network_id: u8,
     pub instructions: Vec<Instruction>,
+    /// The subintents, referred to by index from `YieldToSubintent` instructions.
+    pub subintents: Vec<SignedSubintent>,
 }
 
 /// Represents a signed transaction
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct SignedTransaction {
     /// The unsigned transaction
     pub transaction: Transaction,
@@ -26,6 +34,40 @@ pub struct SignedTransaction {
     pub signatures: Vec<(EcdsaPublicKey, EcdsaSignature)>,
 }
 
+/// Represents a transaction which is being signed by its signers one at a time, possibly offline.
+///
+/// The SBOR encoding of this type is the file format exchanged between co-signers.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct PartiallySignedTransaction {
+    /// The file format version
+    pub version: u8,
+    /// The unsigned transaction, including nonce
+    pub transaction: Transaction,
+    /// The signatures attached so far
+    pub signatures: Vec<(EcdsaPublicKey, EcdsaSignature)>,
+}
+
+/// Represents a partial transaction intent, to be combined with others by a notary.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct Subintent {
+    pub instructions: Vec<Instruction>,
+    /// Distinguishes otherwise identical subintents of the same owner
+    pub nonce: u64,
+    /// The last epoch in which the subintent can be executed
+    pub expiry_epoch: u64,
+}
+
+/// Represents a subintent signed by its own party.
+///
+/// The signatures cover both the subintent and the intent hash of the parent transaction, so a
+/// subintent can't be yielded to by any other transaction.
+#[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
+pub struct SignedSubintent {
+    /// The unsigned subintent
+    pub subintent: Subintent,
+    /// The signatures of the subintent owner(s).
+    pub signatures: Vec<(EcdsaPublicKey, EcdsaSignature)>,
+}
 
 /// Represents an instruction
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
@@ -63,6 +105,64 @@ pub enum Instruction {
         resource_address: ResourceAddress,
     },
 
+    /// Asserts the amount in a vault, located by a path into the component state.
+    AssertVaultBalance {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        condition: AmountCondition,
+    },
+
+    /// Asserts the balance of an account, as reported by its `balance` method.
+    AssertAccountBalance {
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+    },
+
+    /// Asserts the total supply of a resource.
+    AssertResourceSupply {
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+    },
+
+    /// Asserts a component state field, located by a path, equals the given value.
+    AssertComponentState {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        value: Vec<u8>,
+    },
+
+    /// Asserts an entry of a lazy map, located by a path into the component state, equals
+    /// the given value, or does not exist if no value is given.
+    AssertLazyMapEntry {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        key: Vec<u8>,
+        value: Option<Vec<u8>>,
+    },
+
+    /// Recalls resource from a vault, located by a path into the component state, onto the worktop.
+    RecallFromVault {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    },
+
+    /// Freezes a vault, located by a path into the component state.
+    FreezeVault {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        resource_address: ResourceAddress,
+    },
+
+    /// Unfreezes a vault, located by a path into the component state.
+    UnfreezeVault {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        resource_address: ResourceAddress,
+    },
+
     /// Takes the last proof from the auth zone.
     PopFromAuthZone,
 
@@ -125,12 +225,44 @@ pub enum Instruction {
     /// Publishes a package.
     PublishPackage { code: Vec<u8> },
 
+    /// Creates the virtual account derived from a public key, if it doesn't exist yet.
+    CreateVirtualAccount { public_key: EcdsaPublicKey },
+
+    /// Moves buckets onto the worktop of a subintent and runs it to completion.
+    ///
+    /// Resources returned by the subintent are put onto the worktop.
+    YieldToSubintent {
+        subintent_index: u32,
+        bucket_ids: Vec<BucketId>,
+    },
+
+    /// Returns buckets to the parent transaction; must be the last instruction of a subintent.
+    ReturnToParent { bucket_ids: Vec<BucketId> },
+
     /// Specifies transaction nonce
     Nonce {
         nonce: u64, // TODO: may be replaced with substate id for entropy
     },
 }
 
+/// Represents a condition on an amount of resource.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, PartialEq, Eq)]
+pub enum AmountCondition {
+    Exactly(Decimal),
+    AtLeast(Decimal),
+    AtMost(Decimal),
+}
+
+impl AmountCondition {
+    pub fn is_satisfied_by(&self, amount: Decimal) -> bool {
+        match self {
+            AmountCondition::Exactly(expected) => amount == *expected,
+            AmountCondition::AtLeast(min) => amount >= *min,
+            AmountCondition::AtMost(max) => amount <= *max,
+        }
+    }
+}
+
 impl Transaction {
     pub fn to_vec(&self) -> Vec<u8> {
         scrypto_encode(self)
@@ -140,6 +272,14 @@ impl Transaction {
         hash(self.to_vec())
     }
 
+    /// Returns the hash of the network id and instructions, which the signatures of subintents are
+    /// bound to.
+    pub fn intent_hash(&self) -> Hash {
+        let mut payload = vec![self.network_id];
+        payload.extend(scrypto_encode(&self.instructions));
+        hash(payload)
+    }
+
     pub fn add_nonce(&mut self, nonce: u64) {
         self.instructions.push(Instruction::Nonce { nonce });
     }
@@ -160,23 +300,246 @@ impl Transaction {
     }
 }
 
+impl PartiallySignedTransaction {
+    /// The current file format version.
+    pub const VERSION: u8 = 2;
+
+    pub fn new(transaction: Transaction) -> Self {
+        Self {
+            version: Self::VERSION,
+            transaction,
+            signatures: Vec::new(),
+        }
+    }
+
+    pub fn from_slice(slice: &[u8]) -> Result<Self, DecodeError> {
+        scrypto_decode(slice)
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        scrypto_encode(self)
+    }
+
+    /// Attaches a signature, replacing any previous signature of the same key.
+    pub fn sign(&mut self, sk: &EcdsaPrivateKey) {
+        let pk = sk.public_key();
+        let signature = sk.sign(&self.transaction.to_vec());
+        self.signatures.retain(|(signer, _)| *signer != pk);
+        self.signatures.push((pk, signature));
+    }
+
+    /// Returns the public keys of all attached signatures, and whether each signature is valid.
+    pub fn signers(&self) -> Vec<(EcdsaPublicKey, bool)> {
+        let msg = self.transaction.to_vec();
+        self.signatures
+            .iter()
+            .map(|(pk, sig)| (*pk, EcdsaVerifier::verify(&msg, pk, sig)))
+            .collect()
+    }
+
+    pub fn into_signed(self) -> SignedTransaction {
+        SignedTransaction {
+            transaction: self.transaction,
+            signatures: self.signatures,
+        }
+    }
+}
+
+impl Subintent {
+    pub fn to_vec(&self) -> Vec<u8> {
+        scrypto_encode(self)
+    }
+
+    /// Returns the message signed by the subintent owner(s) for the given parent transaction.
+    pub fn signed_payload(&self, parent_intent_hash: Hash) -> Vec<u8> {
+        let mut msg = self.to_vec();
+        msg.extend(parent_intent_hash.to_vec());
+        msg
+    }
+
+    /// Signs the subintent, to be yielded to by the transaction of the given intent hash.
+    pub fn sign<'a, T: AsRef<[&'a EcdsaPrivateKey]>>(
+        self,
+        parent_intent_hash: Hash,
+        sks: T,
+    ) -> SignedSubintent {
+        let msg = self.signed_payload(parent_intent_hash);
+        let signatures = sks
+            .as_ref()
+            .iter()
+            .map(|sk| (sk.public_key(), sk.sign(&msg)))
+            .collect();
+
+        SignedSubintent {
+            subintent: self,
+            signatures,
+        }
+    }
+}
+
+impl SignedSubintent {
+    pub fn validate(
+        &self,
+        parent_intent_hash: Hash,
+    ) -> Result<ValidatedSubintent, TransactionValidationError> {
+        self.validate_with_config(parent_intent_hash, &ValidationConfig::default())
+    }
+
+    pub fn validate_with_config(
+        &self,
+        parent_intent_hash: Hash,
+        config: &ValidationConfig,
+    ) -> Result<ValidatedSubintent, TransactionValidationError> {
+        let signers = SignedTransaction::verify_signatures(
+            &self.subintent.signed_payload(parent_intent_hash),
+            &self.signatures,
+            config,
+        )?;
+        let instructions =
+            SignedTransaction::validate_instructions(&self.subintent.instructions, true, config)?;
+
+        Ok(ValidatedSubintent {
+            instructions,
+            signers,
+            expiry_epoch: self.subintent.expiry_epoch,
+        })
+    }
+}
+
 impl SignedTransaction {
+    pub fn to_vec(&self) -> Vec<u8> {
+        scrypto_encode(self)
+    }
+
+    /// Decodes a signed transaction received as raw bytes.
+    ///
+    /// The payload size is checked before decoding, and the nesting depth while decoding.
+    pub fn from_slice_with_config(
+        slice: &[u8],
+        config: &ValidationConfig,
+    ) -> Result<Self, TransactionValidationError> {
+        if slice.len() > config.max_payload_bytes {
+            return Err(TransactionValidationError::PayloadTooLarge {
+                size: slice.len(),
+                max: config.max_payload_bytes,
+            });
+        }
+
+        let mut decoder = Decoder::with_type(slice).with_max_depth(config.max_sbor_depth);
+        let transaction = Self::decode(&mut decoder)
+            .and_then(|transaction| decoder.check_end().map(|_| transaction))
+            .map_err(|e| match e {
+                DecodeError::MaxDepthExceeded(max) => {
+                    TransactionValidationError::SborDepthExceeded(max)
+                }
+                e => TransactionValidationError::DecodeError(e),
+            })?;
+        Ok(transaction)
+    }
+
     pub fn validate(&self) -> Result<ValidatedTransaction, TransactionValidationError> {
-        let mut instructions = vec![];
-        let mut signers = vec![];
+        self.validate_with_config(&ValidationConfig::default())
+    }
+
+    pub fn validate_with_config(
+        &self,
+        config: &ValidationConfig,
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
+        if self.transaction.network_id != config.network_id {
+            return Err(TransactionValidationError::NetworkMismatch {
+                expected: config.network_id,
+                actual: self.transaction.network_id,
+            });
+        }
+
+        let size = self.to_vec().len();
+        if size > config.max_payload_bytes {
+            return Err(TransactionValidationError::PayloadTooLarge {
+                size,
+                max: config.max_payload_bytes,
+            });
+        }
+
+        let payload = self.transaction.to_vec();
 
         // verify signature (may defer to runtime)
-        let msg = self.transaction.to_vec();
-        for (pk, sig) in &self.signatures {
-            if !EcdsaVerifier::verify(&msg, pk, sig) {
+        let signers = Self::verify_signatures(&payload, &self.signatures, config)?;
+
+        // semantic analysis
+        let instructions =
+            Self::validate_instructions(&self.transaction.instructions, false, config)?;
+        let intent_hash = self.transaction.intent_hash();
+        let mut subintents = vec![];
+        for subintent in &self.transaction.subintents {
+            subintents.push(subintent.validate_with_config(intent_hash, config)?);
+        }
+
+        // every subintent must be yielded to exactly once
+        let mut yielded = BTreeSet::new();
+        for inst in &instructions {
+            if let ValidatedInstruction::YieldToSubintent { subintent_index, .. } = inst {
+                if *subintent_index as usize >= subintents.len() {
+                    return Err(TransactionValidationError::SubintentNotFound(*subintent_index));
+                }
+                if !yielded.insert(*subintent_index) {
+                    return Err(TransactionValidationError::SubintentYieldedMoreThanOnce(
+                        *subintent_index,
+                    ));
+                }
+            }
+        }
+        if yielded.len() != subintents.len() {
+            let index = (0..subintents.len() as u32)
+                .find(|i| !yielded.contains(i))
+                .unwrap();
+            return Err(TransactionValidationError::SubintentNotYielded(index));
+        }
+
+        Ok(ValidatedTransaction {
+            raw_hash: hash(payload),
+            instructions,
+            signers,
+            subintents,
+        })
+    }
+
+    fn verify_signatures(
+        msg: &[u8],
+        signatures: &[(EcdsaPublicKey, EcdsaSignature)],
+        config: &ValidationConfig,
+    ) -> Result<Vec<EcdsaPublicKey>, TransactionValidationError> {
+        if signatures.len() > config.max_signatures {
+            return Err(TransactionValidationError::TooManySignatures {
+                count: signatures.len(),
+                max: config.max_signatures,
+            });
+        }
+
+        let mut signers = vec![];
+        for (pk, sig) in signatures {
+            if !EcdsaVerifier::verify(msg, pk, sig) {
                 return Err(TransactionValidationError::InvalidSignature);
             }
             signers.push(pk.clone());
         }
+        Ok(signers)
+    }
+
+    fn validate_instructions(
+        insts: &[Instruction],
+        is_subintent: bool,
+        config: &ValidationConfig,
+    ) -> Result<Vec<ValidatedInstruction>, TransactionValidationError> {
+        if insts.len() > config.max_instructions {
+            return Err(TransactionValidationError::TooManyInstructions {
+                count: insts.len(),
+                max: config.max_instructions,
+            });
+        }
 
-        // semantic analysis
+        let mut instructions = vec![];
         let mut id_validator = IdValidator::new();
-        for inst in &self.transaction.instructions {
+        for (i, inst) in insts.iter().enumerate() {
             match inst.clone() {
                 Instruction::TakeFromWorktop { resource_address } => {
                     id_validator
@@ -236,6 +599,98 @@ impl SignedTransaction {
                         resource_address,
                     });
                 }
+                Instruction::AssertVaultBalance {
+                    component_address,
+                    path,
+                    condition,
+                } => {
+                    instructions.push(ValidatedInstruction::AssertVaultBalance {
+                        component_address,
+                        path,
+                        condition,
+                    });
+                }
+                Instruction::AssertAccountBalance {
+                    component_address,
+                    resource_address,
+                    condition,
+                } => {
+                    instructions.push(ValidatedInstruction::AssertAccountBalance {
+                        component_address,
+                        resource_address,
+                        condition,
+                    });
+                }
+                Instruction::AssertResourceSupply {
+                    resource_address,
+                    condition,
+                } => {
+                    instructions.push(ValidatedInstruction::AssertResourceSupply {
+                        resource_address,
+                        condition,
+                    });
+                }
+                Instruction::AssertComponentState {
+                    component_address,
+                    path,
+                    value,
+                } => {
+                    instructions.push(ValidatedInstruction::AssertComponentState {
+                        component_address,
+                        path,
+                        value: Self::validate_assertion_value(value, config)?.dom,
+                    });
+                }
+                Instruction::AssertLazyMapEntry {
+                    component_address,
+                    path,
+                    key,
+                    value,
+                } => {
+                    instructions.push(ValidatedInstruction::AssertLazyMapEntry {
+                        component_address,
+                        path,
+                        key: Self::validate_assertion_value(key, config)?.raw,
+                        value: value
+                            .map(|v| Self::validate_assertion_value(v, config).map(|v| v.dom))
+                            .transpose()?,
+                    });
+                }
+                Instruction::RecallFromVault {
+                    component_address,
+                    path,
+                    amount,
+                    resource_address,
+                } => {
+                    instructions.push(ValidatedInstruction::RecallFromVault {
+                        component_address,
+                        path,
+                        amount,
+                        resource_address,
+                    });
+                }
+                Instruction::FreezeVault {
+                    component_address,
+                    path,
+                    resource_address,
+                } => {
+                    instructions.push(ValidatedInstruction::FreezeVault {
+                        component_address,
+                        path,
+                        resource_address,
+                    });
+                }
+                Instruction::UnfreezeVault {
+                    component_address,
+                    path,
+                    resource_address,
+                } => {
+                    instructions.push(ValidatedInstruction::UnfreezeVault {
+                        component_address,
+                        path,
+                        resource_address,
+                    });
+                }
                 Instruction::PopFromAuthZone => {
                     id_validator
                         .new_proof(ProofKind::AuthZoneProof)
@@ -310,7 +765,7 @@ impl SignedTransaction {
                         package_address,
                         blueprint_name,
                         function,
-                        args: Self::validate_args(args, &mut id_validator)?,
+                        args: Self::validate_args(args, &mut id_validator, config)?,
                     });
                 }
                 Instruction::CallMethod {
@@ -321,7 +776,7 @@ impl SignedTransaction {
                     instructions.push(ValidatedInstruction::CallMethod {
                         component_address,
                         method,
-                        args: Self::validate_args(args, &mut id_validator)?,
+                        args: Self::validate_args(args, &mut id_validator, config)?,
                     });
                 }
                 Instruction::CallMethodWithAllResources {
@@ -337,29 +792,80 @@ impl SignedTransaction {
                     });
                 }
                 Instruction::PublishPackage { code } => {
+                    if code.len() > config.max_package_size {
+                        return Err(TransactionValidationError::PackageTooLarge {
+                            size: code.len(),
+                            max: config.max_package_size,
+                        });
+                    }
                     instructions.push(ValidatedInstruction::PublishPackage { code });
                 }
+                Instruction::CreateVirtualAccount { public_key } => {
+                    instructions.push(ValidatedInstruction::CreateVirtualAccount { public_key });
+                }
+                Instruction::YieldToSubintent {
+                    subintent_index,
+                    bucket_ids,
+                } => {
+                    if is_subintent {
+                        return Err(TransactionValidationError::NestedSubintentNotAllowed);
+                    }
+                    for bucket_id in &bucket_ids {
+                        id_validator
+                            .drop_bucket(*bucket_id)
+                            .map_err(TransactionValidationError::IdValidatorError)?;
+                    }
+                    instructions.push(ValidatedInstruction::YieldToSubintent {
+                        subintent_index,
+                        bucket_ids,
+                    });
+                }
+                Instruction::ReturnToParent { bucket_ids } => {
+                    if !is_subintent {
+                        return Err(TransactionValidationError::ReturnToParentNotAllowed);
+                    }
+                    if insts[i + 1..]
+                        .iter()
+                        .any(|inst| !matches!(inst, Instruction::Nonce { .. }))
+                    {
+                        return Err(TransactionValidationError::InstructionAfterReturnToParent);
+                    }
+                    for bucket_id in &bucket_ids {
+                        id_validator
+                            .drop_bucket(*bucket_id)
+                            .map_err(TransactionValidationError::IdValidatorError)?;
+                    }
+                    instructions.push(ValidatedInstruction::ReturnToParent { bucket_ids });
+                }
                 Instruction::Nonce { .. } => {
                     // TODO: validate nonce
                 }
             }
         }
 
-        Ok(ValidatedTransaction {
-            raw_hash: self.transaction.raw_hash(),
-            instructions,
-            signers,
+        Ok(instructions)
+    }
+
+    fn parse_value(
+        slice: &[u8],
+        config: &ValidationConfig,
+    ) -> Result<ScryptoValue, TransactionValidationError> {
+        ScryptoValue::from_slice_with_max_depth(slice, config.max_sbor_depth).map_err(|e| match e {
+            ParseScryptoValueError::DecodeError(DecodeError::MaxDepthExceeded(max)) => {
+                TransactionValidationError::SborDepthExceeded(max)
+            }
+            e => TransactionValidationError::ParseScryptoValueError(e),
         })
     }
 
     fn validate_args(
         args: Vec<Vec<u8>>,
         id_validator: &mut IdValidator,
+        config: &ValidationConfig,
     ) -> Result<Vec<ScryptoValue>, TransactionValidationError> {
         let mut result = vec![];
         for arg in args {
-            let validated_arg = ScryptoValue::from_slice(&arg)
-                .map_err(TransactionValidationError::ParseScryptoValueError)?;
+            let validated_arg = Self::parse_value(&arg, config)?;
             id_validator
                 .move_resources(&validated_arg)
                 .map_err(TransactionValidationError::IdValidatorError)?;
@@ -377,12 +883,27 @@ impl SignedTransaction {
         }
         Ok(result)
     }
+
+    fn validate_assertion_value(
+        value: Vec<u8>,
+        config: &ValidationConfig,
+    ) -> Result<ScryptoValue, TransactionValidationError> {
+        let validated_value = Self::parse_value(&value, config)?;
+        if let Some(bucket_id) = validated_value.bucket_ids.keys().nth(0) {
+            return Err(TransactionValidationError::BucketNotAllowed(*bucket_id));
+        }
+        if let Some(proof_id) = validated_value.proof_ids.keys().nth(0) {
+            return Err(TransactionValidationError::ProofNotAllowed(*proof_id));
+        }
+        Ok(validated_value)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::model::LOCAL_SIMULATOR_NETWORK_ID;
     use scrypto::engine::types::ComponentAddress;
     use scrypto::rust::borrow::ToOwned;
     use scrypto::rust::marker::PhantomData;
@@ -392,6 +913,7 @@ mod tests {
         assert_eq!(
             SignedTransaction {
                 transaction: Transaction {
+                    network_id: LOCAL_SIMULATOR_NETWORK_ID,
                     instructions: vec![Instruction::CallMethod {
                         component_address: ComponentAddress([1u8; 26]),
                         method: "test".to_owned(),
@@ -400,6 +922,7 @@ mod tests {
                             0,
                         )))],
                     }],
+                    subintents: Vec::new(),
                 },
                 signatures: Vec::new(),
             }
@@ -416,6 +939,7 @@ mod tests {
         assert_eq!(
             SignedTransaction {
                 transaction: Transaction {
+                    network_id: LOCAL_SIMULATOR_NETWORK_ID,
                     instructions: vec![Instruction::CallMethod {
                         component_address: ComponentAddress([1u8; 26]),
                         method: "test".to_owned(),
@@ -425,6 +949,7 @@ mod tests {
                             value: PhantomData,
                         })],
                     }],
+                    subintents: Vec::new(),
                 },
                 signatures: Vec::new()
             }
@@ -435,4 +960,202 @@ mod tests {
             ))),
         );
     }
+
+    #[test]
+    fn should_reject_subintent_not_yielded_to() {
+        assert_eq!(
+            SignedTransaction {
+                transaction: Transaction {
+                    network_id: LOCAL_SIMULATOR_NETWORK_ID,
+                    instructions: Vec::new(),
+                    subintents: vec![Subintent {
+                        instructions: Vec::new(),
+                        nonce: 0,
+                        expiry_epoch: 0,
+                    }
+                    .sign(Hash([0u8; 32]), [])],
+                },
+                signatures: Vec::new()
+            }
+            .validate(),
+            Err(TransactionValidationError::SubintentNotYielded(0)),
+        );
+    }
+
+    #[test]
+    fn should_reject_return_to_parent_outside_subintent() {
+        assert_eq!(
+            SignedTransaction {
+                transaction: Transaction {
+                    network_id: LOCAL_SIMULATOR_NETWORK_ID,
+                    instructions: vec![Instruction::ReturnToParent {
+                        bucket_ids: Vec::new()
+                    }],
+                    subintents: Vec::new(),
+                },
+                signatures: Vec::new()
+            }
+            .validate(),
+            Err(TransactionValidationError::ReturnToParentNotAllowed),
+        );
+    }
+
+    #[test]
+    fn should_reject_nested_subintent() {
+        assert_eq!(
+            SignedTransaction {
+                transaction: Transaction {
+                    network_id: LOCAL_SIMULATOR_NETWORK_ID,
+                    instructions: vec![Instruction::YieldToSubintent {
+                        subintent_index: 0,
+                        bucket_ids: Vec::new()
+                    }],
+                    subintents: vec![Subintent {
+                        instructions: vec![Instruction::YieldToSubintent {
+                            subintent_index: 0,
+                            bucket_ids: Vec::new()
+                        }],
+                        nonce: 0,
+                        expiry_epoch: 0,
+                    }
+                    .sign(Hash([0u8; 32]), [])],
+                },
+                signatures: Vec::new()
+            }
+            .validate(),
+            Err(TransactionValidationError::NestedSubintentNotAllowed),
+        );
+    }
+
+    #[test]
+    fn should_collect_signatures_one_at_a_time() {
+        let sk1 = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let sk2 = EcdsaPrivateKey::from_bytes(&[2u8; 32]).unwrap();
+        let mut transaction = Transaction {
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
+            instructions: Vec::new(),
+            subintents: Vec::new(),
+        };
+        transaction.add_nonce(0);
+
+        let mut partial = PartiallySignedTransaction::new(transaction);
+        partial.sign(&sk1);
+        let mut partial = PartiallySignedTransaction::from_slice(&partial.to_vec()).unwrap();
+        partial.sign(&sk2);
+        partial.sign(&sk1);
+
+        assert_eq!(
+            partial.signers(),
+            vec![(sk2.public_key(), true), (sk1.public_key(), true)]
+        );
+        assert_eq!(
+            partial.into_signed().validate().unwrap().signers,
+            vec![sk2.public_key(), sk1.public_key()]
+        );
+    }
+
+    #[test]
+    fn should_reject_transaction_exceeding_limits() {
+        let config = ValidationConfig {
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
+            max_payload_bytes: 1024,
+            max_instructions: 2,
+            max_sbor_depth: 4,
+            max_signatures: 1,
+            max_package_size: 16,
+        };
+        let sk1 = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let sk2 = EcdsaPrivateKey::from_bytes(&[2u8; 32]).unwrap();
+        let transaction = |instructions| Transaction {
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
+            instructions,
+            subintents: Vec::new(),
+        };
+
+        assert_eq!(
+            transaction(vec![Instruction::Nonce { nonce: 0 }; 3])
+                .sign([])
+                .validate_with_config(&config),
+            Err(TransactionValidationError::TooManyInstructions { count: 3, max: 2 })
+        );
+        assert_eq!(
+            transaction(Vec::new())
+                .sign([&sk1, &sk2])
+                .validate_with_config(&config),
+            Err(TransactionValidationError::TooManySignatures { count: 2, max: 1 })
+        );
+        assert!(matches!(
+            transaction(vec![Instruction::PublishPackage {
+                code: vec![0u8; 2048]
+            }])
+            .sign([])
+            .validate_with_config(&config),
+            Err(TransactionValidationError::PayloadTooLarge { max: 1024, .. })
+        ));
+        assert_eq!(
+            transaction(vec![Instruction::PublishPackage {
+                code: vec![0u8; 17]
+            }])
+            .sign([])
+            .validate_with_config(&config),
+            Err(TransactionValidationError::PackageTooLarge { size: 17, max: 16 })
+        );
+        assert!(matches!(
+            SignedTransaction::from_slice_with_config(
+                &transaction(vec![Instruction::PublishPackage {
+                    code: vec![0u8; 2048]
+                }])
+                .sign([])
+                .to_vec(),
+                &config
+            ),
+            Err(TransactionValidationError::PayloadTooLarge { max: 1024, .. })
+        ));
+        assert_eq!(
+            transaction(vec![Instruction::CallMethod {
+                component_address: ComponentAddress([1u8; 26]),
+                method: "test".to_owned(),
+                args: vec![scrypto_encode(&Some(Some(Some(Some(Some(()))))))],
+            }])
+            .sign([])
+            .validate_with_config(&config),
+            Err(TransactionValidationError::SborDepthExceeded(4))
+        );
+    }
+
+    #[test]
+    fn should_reject_transaction_for_another_network() {
+        assert_eq!(
+            Transaction {
+                network_id: 1,
+                instructions: Vec::new(),
+                subintents: Vec::new(),
+            }
+            .sign([])
+            .validate(),
+            Err(TransactionValidationError::NetworkMismatch {
+                expected: LOCAL_SIMULATOR_NETWORK_ID,
+                actual: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn should_decode_signed_transaction_from_slice() {
+        let sk = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let transaction = Transaction {
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
+            instructions: vec![Instruction::Nonce { nonce: 0 }],
+            subintents: Vec::new(),
+        }
+        .sign([&sk]);
+
+        assert_eq!(
+            SignedTransaction::from_slice_with_config(
+                &transaction.to_vec(),
+                &ValidationConfig::default()
+            ),
+            Ok(transaction)
+        );
+    }
 }
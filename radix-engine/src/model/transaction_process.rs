@@ -1,6 +1,13 @@
+use sbor::any::Value;
+use scrypto::buffer::{scrypto_decode, scrypto_encode};
+use scrypto::component::LazyMap;
 use scrypto::core::SNodeRef;
 use scrypto::engine::types::*;
 use scrypto::prelude::ScryptoActor;
+use scrypto::resource::SchemaPath;
+use scrypto::rust::boxed::Box;
+use scrypto::rust::convert::TryFrom;
+use scrypto::types::ScryptoType;
 use scrypto::rust::collections::{HashMap};
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
@@ -8,11 +15,12 @@ use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 use crate::engine::{IdAllocator, IdSpace, SystemApi};
 use crate::errors::RuntimeError::{ProofNotFound};
-use crate::errors::RuntimeError;
-use crate::model::{ValidatedInstruction, ValidatedTransaction};
+use crate::errors::{AssertionFailure, RuntimeError};
+use crate::model::{AmountCondition, ValidatedInstruction, ValidatedSubintent, ValidatedTransaction};
 
 pub struct TransactionProcess {
-    transaction: ValidatedTransaction,
+    instructions: Vec<ValidatedInstruction>,
+    subintents: Vec<Option<ValidatedSubintent>>,
     proof_id_mapping: HashMap<ProofId, ProofId>,
     bucket_id_mapping: HashMap<BucketId, BucketId>,
     outputs: Vec<ScryptoValue>,
@@ -22,7 +30,20 @@ pub struct TransactionProcess {
 impl TransactionProcess {
     pub fn new(transaction: ValidatedTransaction) -> Self {
         Self {
-            transaction,
+            instructions: transaction.instructions,
+            subintents: transaction.subintents.into_iter().map(Some).collect(),
+            proof_id_mapping: HashMap::new(),
+            bucket_id_mapping: HashMap::new(),
+            outputs: Vec::new(),
+            id_allocator: IdAllocator::new(IdSpace::Transaction),
+        }
+    }
+
+    /// Creates a process which runs a subintent on behalf of its parent transaction.
+    pub fn new_subintent(subintent: ValidatedSubintent) -> Self {
+        Self {
+            instructions: subintent.instructions,
+            subintents: Vec::new(),
             proof_id_mapping: HashMap::new(),
             bucket_id_mapping: HashMap::new(),
             outputs: Vec::new(),
@@ -44,12 +65,154 @@ impl TransactionProcess {
         Ok(values)
     }
 
+    fn decode_amount(value: &ScryptoValue) -> Result<Decimal, RuntimeError> {
+        scrypto_decode(&value.raw).map_err(|_| RuntimeError::InvalidReturnType)
+    }
+
+    /// Resolves a path into the component state to a custom value of the given type.
+    fn read_custom_value<S: SystemApi>(
+        system_api: &mut S,
+        component_address: ComponentAddress,
+        path: &SchemaPath,
+        ty: ScryptoType,
+    ) -> Result<Vec<u8>, RuntimeError> {
+        let value = system_api
+            .read_component_state(component_address, path)?
+            .ok_or(RuntimeError::AssertionFailed(Box::new(AssertionFailure::SchemaPathNotFound {
+                component_address,
+                path: path.clone(),
+            })))?;
+        match value.dom {
+            Value::Custom { type_id, bytes } if type_id == ty.id() => Ok(bytes),
+            _ => Err(RuntimeError::AssertionFailed(Box::new(AssertionFailure::InvalidSchemaPathTarget {
+                component_address,
+                path: path.clone(),
+            }))),
+        }
+    }
+
+    /// Resolves a path into the component state to a vault.
+    fn read_vault_id<S: SystemApi>(
+        system_api: &mut S,
+        component_address: ComponentAddress,
+        path: &SchemaPath,
+    ) -> Result<VaultId, RuntimeError> {
+        let bytes = Self::read_custom_value(system_api, component_address, path, ScryptoType::Vault)?;
+        let vault = scrypto::resource::Vault::try_from(bytes.as_slice())
+            .map_err(|_| RuntimeError::AssertionFailed(Box::new(AssertionFailure::InvalidSchemaPathTarget {
+                component_address,
+                path: path.clone(),
+            })))?;
+        Ok(vault.0)
+    }
+
+    fn assert_vault_balance<S: SystemApi>(
+        system_api: &mut S,
+        component_address: ComponentAddress,
+        path: &SchemaPath,
+        condition: &AmountCondition,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        let vault_id = Self::read_vault_id(system_api, component_address, path)?;
+        let actual = system_api.read_vault_amount(component_address, &vault_id)?;
+        if condition.is_satisfied_by(actual) {
+            Ok(ScryptoValue::from_value(&()))
+        } else {
+            Err(RuntimeError::AssertionFailed(Box::new(AssertionFailure::VaultBalance {
+                component_address,
+                path: path.clone(),
+                condition: *condition,
+                actual,
+            })))
+        }
+    }
+
+    fn assert_account_balance<S: SystemApi>(
+        system_api: &mut S,
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+        condition: &AmountCondition,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        // Accounts keep a vault per resource in their `vaults` lazy map
+        let mut path = SchemaPath::new();
+        path.field("vaults");
+        let invalid_target = || RuntimeError::AssertionFailed(Box::new(AssertionFailure::InvalidSchemaPathTarget {
+            component_address,
+            path: path.clone(),
+        }));
+        let bytes = Self::read_custom_value(system_api, component_address, &path, ScryptoType::LazyMap)?;
+        let vaults = LazyMap::<(), ()>::try_from(bytes.as_slice()).map_err(|_| invalid_target())?;
+        let actual = match system_api.read_lazy_map_entry(
+            component_address,
+            &vaults.id,
+            &scrypto_encode(&resource_address),
+        ) {
+            Some(entry) => {
+                let vault: scrypto::resource::Vault = scrypto_decode(&entry).map_err(|_| invalid_target())?;
+                system_api.read_vault_amount(component_address, &vault.0)?
+            }
+            None => Decimal::zero(),
+        };
+        if condition.is_satisfied_by(actual) {
+            Ok(ScryptoValue::from_value(&()))
+        } else {
+            Err(RuntimeError::AssertionFailed(Box::new(AssertionFailure::AccountBalance {
+                component_address,
+                resource_address,
+                condition: *condition,
+                actual,
+            })))
+        }
+    }
+
+    fn assert_lazy_map_entry<S: SystemApi>(
+        system_api: &mut S,
+        component_address: ComponentAddress,
+        path: &SchemaPath,
+        key: &[u8],
+        expected: &Option<Value>,
+    ) -> Result<ScryptoValue, RuntimeError> {
+        let bytes = Self::read_custom_value(system_api, component_address, path, ScryptoType::LazyMap)?;
+        let lazy_map = LazyMap::<(), ()>::try_from(bytes.as_slice())
+            .map_err(|_| RuntimeError::AssertionFailed(Box::new(AssertionFailure::InvalidSchemaPathTarget {
+                component_address,
+                path: path.clone(),
+            })))?;
+        let actual = system_api
+            .read_lazy_map_entry(component_address, &lazy_map.id, key)
+            .map(|entry| ScryptoValue::from_slice(&entry).map(|v| v.dom))
+            .transpose()
+            .map_err(RuntimeError::ParseScryptoValueError)?;
+        if actual == *expected {
+            Ok(ScryptoValue::from_value(&()))
+        } else {
+            Err(RuntimeError::AssertionFailed(Box::new(AssertionFailure::LazyMapEntry {
+                component_address,
+                path: path.clone(),
+                key: key.to_vec(),
+                expected: expected.clone(),
+                actual,
+            })))
+        }
+    }
+
+    fn take_bucket_ids(&mut self, bucket_ids: &[BucketId]) -> Result<Vec<BucketId>, RuntimeError> {
+        bucket_ids
+            .iter()
+            .map(|bucket_id| {
+                self.bucket_id_mapping
+                    .remove(bucket_id)
+                    .ok_or(RuntimeError::BucketNotFound(*bucket_id))
+            })
+            .collect()
+    }
+
     pub fn outputs(&self) -> &[ScryptoValue] {
         &self.outputs
     }
 
     pub fn main<S: SystemApi>(&mut self, system_api: &mut S) -> Result<ScryptoValue, RuntimeError> {
-        for inst in &self.transaction.instructions.clone() {
+        let mut rtn = ScryptoValue::from_value(&());
+        for inst in &self.instructions.clone() {
             let result = match inst {
                 ValidatedInstruction::TakeFromWorktop { resource_address } => {
                     self.id_allocator.new_bucket_id()
@@ -158,6 +321,127 @@ impl TransactionProcess {
                         ]
                     )
                 },
+                ValidatedInstruction::AssertVaultBalance {
+                    component_address,
+                    path,
+                    condition,
+                } => Self::assert_vault_balance(system_api, *component_address, path, condition),
+                ValidatedInstruction::AssertAccountBalance {
+                    component_address,
+                    resource_address,
+                    condition,
+                } => Self::assert_account_balance(system_api, *component_address, *resource_address, condition),
+                ValidatedInstruction::AssertResourceSupply {
+                    resource_address,
+                    condition,
+                } => {
+                    system_api
+                        .invoke_snode(
+                            SNodeRef::ResourceRef(*resource_address),
+                            "get_total_supply".to_string(),
+                            vec![],
+                        )
+                        .and_then(|rtn| Self::decode_amount(&rtn))
+                        .and_then(|actual| {
+                            if condition.is_satisfied_by(actual) {
+                                Ok(ScryptoValue::from_value(&()))
+                            } else {
+                                Err(RuntimeError::AssertionFailed(Box::new(AssertionFailure::ResourceSupply {
+                                    resource_address: *resource_address,
+                                    condition: *condition,
+                                    actual,
+                                })))
+                            }
+                        })
+                },
+                ValidatedInstruction::AssertComponentState {
+                    component_address,
+                    path,
+                    value,
+                } => {
+                    system_api.read_component_state(*component_address, path)
+                        .and_then(|actual| {
+                            let actual = actual.ok_or(RuntimeError::AssertionFailed(Box::new(
+                                AssertionFailure::SchemaPathNotFound {
+                                    component_address: *component_address,
+                                    path: path.clone(),
+                                }
+                            )))?;
+                            if actual.dom == *value {
+                                Ok(ScryptoValue::from_value(&()))
+                            } else {
+                                Err(RuntimeError::AssertionFailed(Box::new(AssertionFailure::ComponentState {
+                                    component_address: *component_address,
+                                    path: path.clone(),
+                                    expected: value.clone(),
+                                    actual: actual.dom,
+                                })))
+                            }
+                        })
+                },
+                ValidatedInstruction::AssertLazyMapEntry {
+                    component_address,
+                    path,
+                    key,
+                    value,
+                } => Self::assert_lazy_map_entry(system_api, *component_address, path, key, value),
+                ValidatedInstruction::RecallFromVault {
+                    component_address,
+                    path,
+                    amount,
+                    resource_address,
+                } => {
+                    Self::read_vault_id(system_api, *component_address, path)
+                        .and_then(|vault_id| {
+                            system_api.invoke_snode(
+                                SNodeRef::ResourceRef(*resource_address),
+                                "recall".to_string(),
+                                vec![
+                                    ScryptoValue::from_value(component_address),
+                                    ScryptoValue::from_value(&vault_id),
+                                    ScryptoValue::from_value(amount),
+                                ],
+                            )
+                        })
+                        .and_then(|result| {
+                            // Auto move into worktop
+                            for bucket_id in result.bucket_ids.keys() {
+                                system_api.invoke_snode(
+                                    SNodeRef::WorktopRef,
+                                    "put".to_string(),
+                                    vec![ScryptoValue::from_value(&scrypto::resource::Bucket(*bucket_id))]
+                                )?;
+                            }
+                            Ok(ScryptoValue::from_value(&()))
+                        })
+                },
+                ValidatedInstruction::FreezeVault {
+                    component_address,
+                    path,
+                    resource_address,
+                }
+                | ValidatedInstruction::UnfreezeVault {
+                    component_address,
+                    path,
+                    resource_address,
+                } => {
+                    let function = if matches!(inst, ValidatedInstruction::FreezeVault { .. }) {
+                        "freeze_vault"
+                    } else {
+                        "unfreeze_vault"
+                    };
+                    Self::read_vault_id(system_api, *component_address, path)
+                        .and_then(|vault_id| {
+                            system_api.invoke_snode(
+                                SNodeRef::ResourceRef(*resource_address),
+                                function.to_string(),
+                                vec![
+                                    ScryptoValue::from_value(component_address),
+                                    ScryptoValue::from_value(&vault_id),
+                                ],
+                            )
+                        })
+                },
                 ValidatedInstruction::PopFromAuthZone {} => {
                     self.id_allocator.new_proof_id()
                         .map_err(RuntimeError::IdAllocatorError)
@@ -397,10 +681,53 @@ impl TransactionProcess {
                         vec![ScryptoValue::from_value(code)],
                     )
                 },
+                ValidatedInstruction::CreateVirtualAccount { public_key } => {
+                    system_api
+                        .create_virtual_account(*public_key)
+                        .map(|component_address| ScryptoValue::from_value(&component_address))
+                },
+                ValidatedInstruction::YieldToSubintent {
+                    subintent_index,
+                    bucket_ids,
+                } => {
+                    self.subintents
+                        .get_mut(*subintent_index as usize)
+                        .and_then(Option::take)
+                        .ok_or(RuntimeError::SubintentNotFound(*subintent_index))
+                        .and_then(|subintent| {
+                            if system_api.current_epoch() > subintent.expiry_epoch {
+                                return Err(RuntimeError::SubintentExpired(*subintent_index));
+                            }
+                            let real_ids = self.take_bucket_ids(bucket_ids)?;
+                            system_api.invoke_subintent(subintent, real_ids)
+                        })
+                        .and_then(|result| {
+                            // Auto move into worktop
+                            for bucket_id in result.bucket_ids.keys() {
+                                system_api.invoke_snode(
+                                    SNodeRef::WorktopRef,
+                                    "put".to_string(),
+                                    vec![ScryptoValue::from_value(&scrypto::resource::Bucket(*bucket_id))]
+                                )?;
+                            }
+                            Ok(result)
+                        })
+                },
+                ValidatedInstruction::ReturnToParent { bucket_ids } => {
+                    self.take_bucket_ids(bucket_ids)
+                        .map(|real_ids| {
+                            let buckets: Vec<scrypto::resource::Bucket> = real_ids
+                                .into_iter()
+                                .map(scrypto::resource::Bucket)
+                                .collect();
+                            rtn = ScryptoValue::from_value(&buckets);
+                            rtn.clone()
+                        })
+                },
             }?;
             self.outputs.push(result);
         }
 
-        Ok(ScryptoValue::from_value(&()))
+        Ok(rtn)
     }
 }
\ No newline at end of file
@@ -1,16 +1,29 @@
+use sbor::any::Value;
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
+use scrypto::resource::SchemaPath;
 use scrypto::rust::collections::{BTreeSet};
 use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
 use scrypto::values::*;
 
+use crate::model::AmountCondition;
+
 /// Represents a validated transaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidatedTransaction {
     pub raw_hash: Hash,
     pub instructions: Vec<ValidatedInstruction>,
     pub signers: Vec<EcdsaPublicKey>,
+    pub subintents: Vec<ValidatedSubintent>,
+}
+
+/// Represents a validated subintent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedSubintent {
+    pub instructions: Vec<ValidatedInstruction>,
+    pub signers: Vec<EcdsaPublicKey>,
+    pub expiry_epoch: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -40,6 +53,47 @@ pub enum ValidatedInstruction {
         ids: BTreeSet<NonFungibleId>,
         resource_address: ResourceAddress,
     },
+    AssertVaultBalance {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        condition: AmountCondition,
+    },
+    AssertAccountBalance {
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+    },
+    AssertResourceSupply {
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+    },
+    AssertComponentState {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        value: Value,
+    },
+    AssertLazyMapEntry {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        key: Vec<u8>,
+        value: Option<Value>,
+    },
+    RecallFromVault {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    },
+    FreezeVault {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        resource_address: ResourceAddress,
+    },
+    UnfreezeVault {
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        resource_address: ResourceAddress,
+    },
     PopFromAuthZone,
     PushToAuthZone {
         proof_id: ProofId,
@@ -83,4 +137,14 @@ pub enum ValidatedInstruction {
     PublishPackage {
         code: Vec<u8>,
     },
+    CreateVirtualAccount {
+        public_key: EcdsaPublicKey,
+    },
+    YieldToSubintent {
+        subintent_index: u32,
+        bucket_ids: Vec<BucketId>,
+    },
+    ReturnToParent {
+        bucket_ids: Vec<BucketId>,
+    },
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+/// The id of the network simulated locally, e.g. by resim and in tests.
+pub const LOCAL_SIMULATOR_NETWORK_ID: u8 = 0xf2;
+
+/// Limits enforced when validating a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationConfig {
+    /// The network transactions must be intended for.
+    pub network_id: u8,
+    /// The maximum size of a signed transaction received as raw bytes.
+    pub max_payload_bytes: usize,
+    /// The maximum number of instructions, of the transaction and of each subintent.
+    pub max_instructions: usize,
+    /// The maximum nesting depth of SBOR values, in a raw transaction and passed to instructions.
+    pub max_sbor_depth: usize,
+    /// The maximum number of signatures, of the transaction and of each subintent.
+    pub max_signatures: usize,
+    /// The maximum size of a published package, in bytes.
+    pub max_package_size: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
+            max_payload_bytes: 4 * 1024 * 1024,
+            max_instructions: 1024,
+            max_sbor_depth: 32,
+            max_signatures: 16,
+            max_package_size: 2 * 1024 * 1024,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use sbor::*;
 use scrypto::buffer::scrypto_decode;
+use scrypto::constants::RADIX_TOKEN;
 use scrypto::engine::types::*;
 use scrypto::rust::cell::{Ref, RefCell, RefMut};
 use scrypto::rust::collections::BTreeSet;
@@ -12,7 +13,8 @@ use scrypto::values::ScryptoValue;
 use crate::engine::SystemApi;
 
 use crate::model::{
-    Bucket, Proof, ProofError, ResourceContainer, ResourceContainerError, ResourceContainerId,
+    Bucket, LockedAmountOrIds, Proof, ProofError, ResourceContainer, ResourceContainerError,
+    ResourceContainerId,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,18 +26,112 @@ pub enum VaultError {
     CouldNotTakeBucket,
     ProofError(ProofError),
     CouldNotCreateProof,
+    VaultFrozen,
+    InvalidTimeLock,
+    ResourceTimeLocked,
+    LockFeeNotRadixToken,
+    LockFeeFromTransientVault,
+    LockFeeFromUncommittedVault,
+}
+
+/// A lock on vault resources which expires with time.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub enum TimeLock {
+    /// The amount or non-fungibles are locked until the given epoch.
+    Until {
+        locked: LockedAmountOrIds,
+        epoch: u64,
+    },
+    /// The amount is released linearly, starting at `start_epoch` and fully released at `end_epoch`.
+    Vesting {
+        amount: Decimal,
+        start_epoch: u64,
+        end_epoch: u64,
+    },
+}
+
+impl TimeLock {
+    pub fn is_expired(&self, current_epoch: u64) -> bool {
+        match self {
+            Self::Until { epoch, .. } => current_epoch >= *epoch,
+            Self::Vesting { end_epoch, .. } => current_epoch >= *end_epoch,
+        }
+    }
+
+    /// Returns the amount which is still locked at the given epoch.
+    pub fn locked_amount(&self, current_epoch: u64) -> Decimal {
+        if self.is_expired(current_epoch) {
+            return Decimal::zero();
+        }
+
+        match self {
+            Self::Until { locked, .. } => locked.amount(),
+            Self::Vesting {
+                amount,
+                start_epoch,
+                end_epoch,
+            } => {
+                if current_epoch <= *start_epoch {
+                    *amount
+                } else {
+                    *amount * (*end_epoch - current_epoch) / (*end_epoch - *start_epoch)
+                }
+            }
+        }
+    }
+
+    /// Returns the non-fungible IDs which are still locked at the given epoch.
+    pub fn locked_ids(&self, current_epoch: u64) -> BTreeSet<NonFungibleId> {
+        match self {
+            Self::Until {
+                locked: LockedAmountOrIds::Ids(ids),
+                ..
+            } if !self.is_expired(current_epoch) => ids.clone(),
+            _ => BTreeSet::new(),
+        }
+    }
 }
 
 /// A persistent resource container.
-#[derive(Debug, TypeId, Encode, Decode)]
+#[derive(Debug, TypeId, Encode)]
 pub struct Vault {
     container: Rc<RefCell<ResourceContainer>>,
+    frozen: bool,
+    time_locks: Vec<TimeLock>,
+}
+
+impl Decode for Vault {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        // Vaults persisted before resources could be frozen or time locked don't have those fields
+        let len = decoder.read_len()?;
+        if len < 1 || len > 3 {
+            return Err(DecodeError::InvalidLength {
+                expected: 3,
+                actual: len,
+            });
+        }
+        Ok(Self {
+            container: Rc::<RefCell<ResourceContainer>>::decode(decoder)?,
+            frozen: if len >= 2 {
+                bool::decode(decoder)?
+            } else {
+                false
+            },
+            time_locks: if len == 3 {
+                Vec::<TimeLock>::decode(decoder)?
+            } else {
+                Vec::new()
+            },
+        })
+    }
 }
 
 impl Vault {
     pub fn new(container: ResourceContainer) -> Self {
         Self {
             container: Rc::new(RefCell::new(container)),
+            frozen: false,
+            time_locks: Vec::new(),
         }
     }
 
@@ -43,15 +139,26 @@ impl Vault {
         self.borrow_container_mut().put(other.into_container()?)
     }
 
-    fn take(&mut self, amount: Decimal) -> Result<ResourceContainer, VaultError> {
-        let container = self
-            .borrow_container_mut()
-            .take_by_amount(amount)
-            .map_err(VaultError::ResourceContainerError)?;
-        Ok(container)
+    pub fn take(&mut self, amount: Decimal, current_epoch: u64) -> Result<ResourceContainer, VaultError> {
+        self.check_time_locks(amount, current_epoch)?;
+
+        // Non-fungibles which are time locked must be left in the vault
+        let locked_ids = self.time_locked_ids(current_epoch);
+        self.borrow_container_mut()
+            .take_by_amount_excluding(amount, &locked_ids)
+            .map_err(VaultError::ResourceContainerError)
     }
 
-    fn take_non_fungibles(&mut self, ids: &BTreeSet<NonFungibleId>) -> Result<ResourceContainer, VaultError> {
+    pub fn take_non_fungibles(
+        &mut self,
+        ids: &BTreeSet<NonFungibleId>,
+        current_epoch: u64,
+    ) -> Result<ResourceContainer, VaultError> {
+        if !self.time_locked_ids(current_epoch).is_disjoint(ids) {
+            return Err(VaultError::ResourceTimeLocked);
+        }
+        self.check_time_locks(ids.len().into(), current_epoch)?;
+
         let container = self
             .borrow_container_mut()
             .take_by_ids(ids)
@@ -59,12 +166,90 @@ impl Vault {
         Ok(container)
     }
 
+    /// Adds a time lock, which must be covered by the resources in this vault not already time locked.
+    pub fn add_time_lock(&mut self, time_lock: TimeLock, current_epoch: u64) -> Result<(), VaultError> {
+        match &time_lock {
+            TimeLock::Until {
+                locked: LockedAmountOrIds::Amount(amount),
+                ..
+            } => {
+                if amount.is_negative() {
+                    return Err(VaultError::InvalidTimeLock);
+                }
+            }
+            TimeLock::Until {
+                locked: LockedAmountOrIds::Ids(ids),
+                ..
+            } => {
+                let total_ids = self.total_ids().map_err(VaultError::ResourceContainerError)?;
+                if !ids.is_subset(&total_ids) {
+                    return Err(VaultError::ResourceContainerError(ResourceContainerError::InsufficientBalance));
+                }
+                if !self.time_locked_ids(current_epoch).is_disjoint(ids) {
+                    return Err(VaultError::ResourceTimeLocked);
+                }
+            }
+            TimeLock::Vesting {
+                amount,
+                start_epoch,
+                end_epoch,
+            } => {
+                if let ResourceType::NonFungible { .. } = self.resource_type() {
+                    return Err(VaultError::ResourceContainerError(
+                        ResourceContainerError::FungibleOperationNotAllowed,
+                    ));
+                }
+                if amount.is_negative() || start_epoch >= end_epoch {
+                    return Err(VaultError::InvalidTimeLock);
+                }
+            }
+        }
+
+        self.time_locks.retain(|t| !t.is_expired(current_epoch));
+        if self.time_locked_amount(current_epoch) + time_lock.locked_amount(current_epoch)
+            > self.total_amount()
+        {
+            return Err(VaultError::ResourceContainerError(ResourceContainerError::InsufficientBalance));
+        }
+        self.time_locks.push(time_lock);
+        Ok(())
+    }
+
+    pub fn time_locks(&self) -> &[TimeLock] {
+        &self.time_locks
+    }
+
+    /// Returns the amount which can't be taken from this vault at the given epoch.
+    pub fn time_locked_amount(&self, current_epoch: u64) -> Decimal {
+        self.time_locks
+            .iter()
+            .fold(Decimal::zero(), |sum, t| sum + t.locked_amount(current_epoch))
+    }
+
+    /// Returns the non-fungible IDs which can't be taken from this vault at the given epoch.
+    pub fn time_locked_ids(&self, current_epoch: u64) -> BTreeSet<NonFungibleId> {
+        let mut ids = BTreeSet::new();
+        for time_lock in &self.time_locks {
+            ids.extend(time_lock.locked_ids(current_epoch));
+        }
+        ids
+    }
+
+    fn check_time_locks(&self, amount: Decimal, current_epoch: u64) -> Result<(), VaultError> {
+        let total_amount = self.total_amount();
+        if amount <= total_amount && total_amount - amount < self.time_locked_amount(current_epoch) {
+            Err(VaultError::ResourceTimeLocked)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn create_proof(&mut self, container_id: ResourceContainerId) -> Result<Proof, ProofError> {
         match self.resource_type() {
             ResourceType::Fungible { .. } => {
                 self.create_proof_by_amount(self.total_amount(), container_id)
             }
-            ResourceType::NonFungible => {
+            ResourceType::NonFungible { .. } => {
                 self.create_proof_by_ids(&self.total_ids().unwrap(), container_id)
             }
         }
@@ -144,6 +329,22 @@ impl Vault {
         self.borrow_container().is_empty()
     }
 
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    fn check_not_frozen(&self) -> Result<(), VaultError> {
+        if self.frozen {
+            Err(VaultError::VaultFrozen)
+        } else {
+            Ok(())
+        }
+    }
+
     fn borrow_container(&self) -> Ref<ResourceContainer> {
         self.container.borrow()
     }
@@ -155,12 +356,14 @@ impl Vault {
     pub fn main<S: SystemApi>(
         &mut self,
         vault_id: VaultId,
+        component_address: Option<ComponentAddress>,
         function: &str,
         args: Vec<ScryptoValue>,
         system_api: &mut S
     ) -> Result<ScryptoValue, VaultError> {
         match function {
             "put_into_vault" => {
+                self.check_not_frozen()?;
                 let bucket: scrypto::resource::Bucket =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
                 let bucket = system_api.take_bucket(bucket.0).map_err(|_| VaultError::CouldNotTakeBucket)?;
@@ -168,19 +371,78 @@ impl Vault {
                 Ok(ScryptoValue::from_value(&()))
             }
             "take_from_vault" => {
+                self.check_not_frozen()?;
                 let amount: Decimal =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
-                let container = self.take(amount)?;
+                let container = self.take(amount, system_api.current_epoch())?;
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
             "take_non_fungibles_from_vault" => {
+                self.check_not_frozen()?;
                 let non_fungible_ids: BTreeSet<NonFungibleId> =
                     scrypto_decode(&args[0].raw).map_err(|e| VaultError::InvalidRequestData(e))?;
-                let container = self.take_non_fungibles(&non_fungible_ids)?;
+                let container = self.take_non_fungibles(&non_fungible_ids, system_api.current_epoch())?;
                 let bucket_id = system_api.create_bucket(container).map_err(|_| VaultError::CouldNotCreateBucket)?;
                 Ok(ScryptoValue::from_value(&scrypto::resource::Bucket(bucket_id)))
             }
+            "lock_fee" => {
+                self.check_not_frozen()?;
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                if self.resource_address() != RADIX_TOKEN {
+                    return Err(VaultError::LockFeeNotRadixToken);
+                }
+                // Fees are paid from the committed state of the vault, if the transaction fails
+                let component_address =
+                    component_address.ok_or(VaultError::LockFeeFromTransientVault)?;
+                let fee = self.take(amount, system_api.current_epoch())?;
+                system_api.lock_fee(component_address, vault_id, fee)?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "lock_vault_until" => {
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                let epoch: u64 =
+                    scrypto_decode(&args[1].raw).map_err(VaultError::InvalidRequestData)?;
+                let time_lock = TimeLock::Until {
+                    locked: LockedAmountOrIds::Amount(amount),
+                    epoch,
+                };
+                self.add_time_lock(time_lock, system_api.current_epoch())?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "lock_non_fungibles_in_vault_until" => {
+                let ids: BTreeSet<NonFungibleId> =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                let epoch: u64 =
+                    scrypto_decode(&args[1].raw).map_err(VaultError::InvalidRequestData)?;
+                let time_lock = TimeLock::Until {
+                    locked: LockedAmountOrIds::Ids(ids),
+                    epoch,
+                };
+                self.add_time_lock(time_lock, system_api.current_epoch())?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "add_vault_vesting_schedule" => {
+                let amount: Decimal =
+                    scrypto_decode(&args[0].raw).map_err(VaultError::InvalidRequestData)?;
+                let start_epoch: u64 =
+                    scrypto_decode(&args[1].raw).map_err(VaultError::InvalidRequestData)?;
+                let end_epoch: u64 =
+                    scrypto_decode(&args[2].raw).map_err(VaultError::InvalidRequestData)?;
+                let time_lock = TimeLock::Vesting {
+                    amount,
+                    start_epoch,
+                    end_epoch,
+                };
+                self.add_time_lock(time_lock, system_api.current_epoch())?;
+                Ok(ScryptoValue::from_value(&()))
+            }
+            "get_vault_time_locked_amount" => {
+                let amount = self.time_locked_amount(system_api.current_epoch());
+                Ok(ScryptoValue::from_value(&amount))
+            }
             "get_vault_amount" => {
                 let amount = self.total_amount();
                 Ok(ScryptoValue::from_value(&amount))
@@ -189,6 +451,7 @@ impl Vault {
                 let resource_address = self.resource_address();
                 Ok(ScryptoValue::from_value(&resource_address))
             }
+            "is_vault_frozen" => Ok(ScryptoValue::from_value(&self.is_frozen())),
             "get_non_fungible_ids_in_vault" => {
                 let ids = self.total_ids().map_err(VaultError::ResourceContainerError)?;
                 Ok(ScryptoValue::from_value(&ids))
@@ -215,3 +478,46 @@ impl Vault {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::buffer::scrypto_encode;
+    use scrypto::constants::RADIX_TOKEN;
+
+    #[derive(TypeId, Encode)]
+    struct VaultWithoutFrozenFlag {
+        container: Rc<RefCell<ResourceContainer>>,
+    }
+
+    #[test]
+    fn test_decode_vault_without_frozen_flag() {
+        let container = ResourceContainer::new_fungible(RADIX_TOKEN, 18, Decimal::from(5));
+        let bytes = scrypto_encode(&VaultWithoutFrozenFlag {
+            container: Rc::new(RefCell::new(container)),
+        });
+
+        let vault: Vault = scrypto_decode(&bytes).unwrap();
+        assert_eq!(vault.total_amount(), Decimal::from(5));
+        assert!(!vault.is_frozen());
+    }
+
+    #[derive(TypeId, Encode)]
+    struct VaultWithoutTimeLocks {
+        container: Rc<RefCell<ResourceContainer>>,
+        frozen: bool,
+    }
+
+    #[test]
+    fn test_decode_vault_without_time_locks() {
+        let container = ResourceContainer::new_fungible(RADIX_TOKEN, 18, Decimal::from(5));
+        let bytes = scrypto_encode(&VaultWithoutTimeLocks {
+            container: Rc::new(RefCell::new(container)),
+            frozen: true,
+        });
+
+        let vault: Vault = scrypto_decode(&bytes).unwrap();
+        assert!(vault.is_frozen());
+        assert_eq!(vault.time_locked_amount(0), Decimal::zero());
+    }
+}
@@ -1,10 +1,11 @@
 use sbor::describe::*;
 use sbor::*;
 use scrypto::buffer::*;
+use scrypto::component::{AccountDepositMode, ResourceDepositRule};
 use scrypto::crypto::*;
 use scrypto::engine::types::*;
 use scrypto::prelude::{AccessRuleNode, Burn, AccessRule, Mint, Withdraw};
-use scrypto::resource::{require, LOCKED};
+use scrypto::resource::{require, SchemaPath, LOCKED};
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::collections::*;
@@ -26,6 +27,8 @@ use crate::transaction::*;
 pub struct TransactionBuilder {
     /// ID validator for calculating transaction object id
     id_validator: IdValidator,
+    /// The network the transaction is intended for.
+    network_id: u8,
     /// Instructions generated.
     instructions: Vec<Instruction>,
 }
@@ -35,10 +38,17 @@ impl TransactionBuilder {
     pub fn new() -> Self {
         Self {
             id_validator: IdValidator::new(),
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
             instructions: Vec::new(),
         }
     }
 
+    /// Sets the network the transaction is intended for, the local simulator by default.
+    pub fn network_id(&mut self, network_id: u8) -> &mut Self {
+        self.network_id = network_id;
+        self
+    }
+
     /// Adds a raw instruction.
     pub fn add_instruction(
         &mut self,
@@ -58,7 +68,15 @@ impl TransactionBuilder {
             }
             Instruction::AssertWorktopContains { .. }
             | Instruction::AssertWorktopContainsByAmount { .. }
-            | Instruction::AssertWorktopContainsByIds { .. } => {}
+            | Instruction::AssertWorktopContainsByIds { .. }
+            | Instruction::AssertVaultBalance { .. }
+            | Instruction::AssertAccountBalance { .. }
+            | Instruction::AssertResourceSupply { .. }
+            | Instruction::AssertComponentState { .. }
+            | Instruction::AssertLazyMapEntry { .. } => {}
+            Instruction::RecallFromVault { .. }
+            | Instruction::FreezeVault { .. }
+            | Instruction::UnfreezeVault { .. } => {}
             Instruction::PopFromAuthZone { .. } => {
                 new_proof_id = Some(
                     self.id_validator
@@ -101,7 +119,15 @@ impl TransactionBuilder {
             Instruction::CallMethodWithAllResources { .. } => {
                 self.id_validator.move_all_resources().unwrap();
             }
-            Instruction::PublishPackage { .. } | Instruction::Nonce { .. } => {}
+            Instruction::PublishPackage { .. }
+            | Instruction::CreateVirtualAccount { .. }
+            | Instruction::Nonce { .. } => {}
+            Instruction::YieldToSubintent { bucket_ids, .. }
+            | Instruction::ReturnToParent { bucket_ids } => {
+                for bucket_id in bucket_ids {
+                    self.id_validator.drop_bucket(bucket_id).unwrap();
+                }
+            }
         }
 
         self.instructions.push(inst);
@@ -191,6 +217,128 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Asserts the amount in a vault of a component.
+    pub fn assert_vault_balance(
+        &mut self,
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        condition: AmountCondition,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertVaultBalance {
+            component_address,
+            path,
+            condition,
+        })
+        .0
+    }
+
+    /// Asserts the balance of an account.
+    pub fn assert_account_balance(
+        &mut self,
+        component_address: ComponentAddress,
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertAccountBalance {
+            component_address,
+            resource_address,
+            condition,
+        })
+        .0
+    }
+
+    /// Asserts the total supply of a resource.
+    pub fn assert_resource_supply(
+        &mut self,
+        resource_address: ResourceAddress,
+        condition: AmountCondition,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertResourceSupply {
+            resource_address,
+            condition,
+        })
+        .0
+    }
+
+    /// Asserts the value of a component state field.
+    pub fn assert_component_state<T: Encode>(
+        &mut self,
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        value: &T,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertComponentState {
+            component_address,
+            path,
+            value: scrypto_encode(value),
+        })
+        .0
+    }
+
+    /// Asserts the value of a lazy map entry, or its absence if no value is given.
+    pub fn assert_lazy_map_entry<K: Encode, V: Encode>(
+        &mut self,
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        key: &K,
+        value: Option<&V>,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertLazyMapEntry {
+            component_address,
+            path,
+            key: scrypto_encode(key),
+            value: value.map(scrypto_encode),
+        })
+        .0
+    }
+
+    /// Recalls resource from a vault of a component, onto the worktop.
+    pub fn recall_from_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        amount: Decimal,
+        resource_address: ResourceAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::RecallFromVault {
+            component_address,
+            path,
+            amount,
+            resource_address,
+        })
+        .0
+    }
+
+    /// Freezes a vault of a component.
+    pub fn freeze_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        resource_address: ResourceAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::FreezeVault {
+            component_address,
+            path,
+            resource_address,
+        })
+        .0
+    }
+
+    /// Unfreezes a vault of a component.
+    pub fn unfreeze_vault(
+        &mut self,
+        component_address: ComponentAddress,
+        path: SchemaPath,
+        resource_address: ResourceAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::UnfreezeVault {
+            component_address,
+            path,
+            resource_address,
+        })
+        .0
+    }
+
     /// Pops the most recent proof from auth zone.
     pub fn pop_from_auth_zone<F>(&mut self, then: F) -> &mut Self
     where
@@ -413,12 +561,38 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Yields the given buckets to a subintent, by its index in the transaction.
+    ///
+    /// Subintents are signed for the built transaction and then added to it, see
+    /// `Transaction::intent_hash`. Resources returned by the subintent are put onto the worktop.
+    pub fn yield_to_subintent(
+        &mut self,
+        subintent_index: u32,
+        bucket_ids: Vec<BucketId>,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::YieldToSubintent {
+            subintent_index,
+            bucket_ids,
+        })
+        .0
+    }
+
+    /// Returns buckets to the parent transaction. Only valid as the last instruction of a subintent.
+    pub fn return_to_parent(&mut self, bucket_ids: Vec<BucketId>) -> &mut Self {
+        self.add_instruction(Instruction::ReturnToParent { bucket_ids })
+            .0
+    }
+
     /// Builds a transaction with the given nonce.
     pub fn build(&self, nonce: u64) -> Transaction {
         let mut instructions = self.instructions.clone();
         instructions.push(Instruction::Nonce { nonce });
 
-        Transaction { instructions }
+        Transaction {
+            network_id: self.network_id,
+            instructions,
+            subintents: Vec::new(),
+        }
     }
 
     /// Builds a transaction with no nonce
@@ -426,7 +600,18 @@ impl TransactionBuilder {
     /// Nonce can be later filled by a third party or wallet.
     pub fn build_with_no_nonce(&self) -> Transaction {
         Transaction {
+            network_id: self.network_id,
             instructions: self.instructions.clone(),
+            subintents: Vec::new(),
+        }
+    }
+
+    /// Builds a subintent, to be signed by its owner and yielded to by another transaction.
+    pub fn build_subintent(&self, nonce: u64, expiry_epoch: u64) -> Subintent {
+        Subintent {
+            instructions: self.instructions.clone(),
+            nonce,
+            expiry_epoch,
         }
     }
 
@@ -615,6 +800,22 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Creates the virtual account of a public key, unless it already exists.
+    pub fn create_virtual_account(&mut self, public_key: EcdsaPublicKey) -> &mut Self {
+        self.add_instruction(Instruction::CreateVirtualAccount { public_key })
+            .0
+    }
+
+    /// Deposits all resources on the worktop into the virtual account of a public key.
+    ///
+    /// The account is created by the deposit if it doesn't exist yet.
+    pub fn deposit_to_virtual_account(&mut self, public_key: EcdsaPublicKey) -> &mut Self {
+        self.call_method_with_all_resources(
+            IdAllocator::new_virtual_account_address(&public_key),
+            "deposit_batch",
+        )
+    }
+
     /// Withdraws resource from an account.
     pub fn withdraw_from_account(
         &mut self,
@@ -659,6 +860,40 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Withdraws resources from an account, by amount.
+    pub fn withdraw_batch_from_account(
+        &mut self,
+        resources: &[(Decimal, ResourceAddress)],
+        account: ComponentAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "withdraw_batch".to_owned(),
+            args: vec![scrypto_encode(&resources.to_vec())],
+        })
+        .0
+    }
+
+    /// Locks some amount of XRD in an account to pay the transaction fee.
+    pub fn lock_fee(&mut self, amount: Decimal, account: ComponentAddress) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "lock_fee".to_owned(),
+            args: vec![scrypto_encode(&amount)],
+        })
+        .0
+    }
+
+    /// Replaces the virtual key of an account with an owner badge, which is put onto the worktop.
+    pub fn securify_account(&mut self, account: ComponentAddress) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "securify".to_owned(),
+            args: vec![],
+        })
+        .0
+    }
+
     /// Creates resource proof from an account.
     pub fn create_proof_from_account(
         &mut self,
@@ -703,6 +938,49 @@ impl TransactionBuilder {
         .0
     }
 
+    /// Sets which resources an account accepts as deposits.
+    pub fn set_account_deposit_mode(
+        &mut self,
+        deposit_mode: AccountDepositMode,
+        account: ComponentAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "set_deposit_mode".to_owned(),
+            args: vec![scrypto_encode(&deposit_mode)],
+        })
+        .0
+    }
+
+    /// Allows or denies deposits of a resource into an account.
+    pub fn set_account_resource_deposit_rule(
+        &mut self,
+        resource_address: ResourceAddress,
+        rule: ResourceDepositRule,
+        account: ComponentAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "set_resource_deposit_rule".to_owned(),
+            args: vec![scrypto_encode(&resource_address), scrypto_encode(&rule)],
+        })
+        .0
+    }
+
+    /// Removes the deposit rule of a resource from an account.
+    pub fn remove_account_resource_deposit_rule(
+        &mut self,
+        resource_address: ResourceAddress,
+        account: ComponentAddress,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::CallMethod {
+            component_address: account,
+            method: "remove_resource_deposit_rule".to_owned(),
+            args: vec![scrypto_encode(&resource_address)],
+        })
+        .0
+    }
+
     //===============================
     // private methods below
     //===============================
@@ -899,7 +1177,7 @@ fn parse_resource_specifier(input: &str) -> Result<ResourceSpecifier, ParseResou
         let mut ids = BTreeSet::<NonFungibleId>::new();
         for id in &tokens[..tokens.len() - 1] {
             ids.insert(
-                id[1..].parse().map_err(|_| {
+                NonFungibleId::from_natural_str(&id[1..]).map_err(|_| {
                     ParseResourceSpecifierError::InvalidNonFungibleId(id.to_string())
                 })?,
             );
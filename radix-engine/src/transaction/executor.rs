@@ -16,6 +16,8 @@ use crate::transaction::*;
 pub struct TransactionExecutor<'l, L: SubstateStore> {
     substate_store: &'l mut L,
     trace: bool,
+    record_execution_trace: bool,
+    observer: Option<&'l mut dyn ExecutionObserver>,
 }
 
 impl<'l, L: SubstateStore> NonceProvider for TransactionExecutor<'l, L> {
@@ -66,9 +68,25 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         Self {
             substate_store,
             trace,
+            record_execution_trace: false,
+            observer: None,
         }
     }
 
+    /// Sets whether to record an execution trace of every transaction, attached to its receipt.
+    ///
+    /// This is independent of `trace`, which prints trace messages to stdout.
+    pub fn with_execution_trace(mut self, record_execution_trace: bool) -> Self {
+        self.record_execution_trace = record_execution_trace;
+        self
+    }
+
+    /// Attaches an observer, to be notified of the execution of every transaction.
+    pub fn with_observer(mut self, observer: &'l mut dyn ExecutionObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
     /// Returns an immutable reference to the ledger.
     pub fn substate_store(&self) -> &L {
         self.substate_store
@@ -171,13 +189,22 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
             validated.raw_hash.clone(),
             validated.signers.clone(),
         );
+        if let Some(observer) = self.observer.as_deref_mut() {
+            track.set_observer(observer);
+        }
+        if self.record_execution_trace {
+            track.start_execution_trace();
+        }
         let mut proc = track.start_process(self.trace);
 
         let txn_process = TransactionProcess::new(validated.clone());
         let mut txn_snode = SNodeState::Transaction(txn_process);
-        let error = match proc.run(&mut txn_snode, "execute".to_string(), vec![]) {
-            Ok(_) => None,
-            Err(e) => Some(e),
+        let (error, call_stack) = match proc.run(&mut txn_snode, "execute".to_string(), vec![]) {
+            Ok(_) => (None, Vec::new()),
+            Err(e) => {
+                let (error, call_stack) = e.into_call_stack();
+                (Some(error), call_stack)
+            }
         };
         let outputs = if let SNodeState::Transaction(txn_process) = txn_snode {
             txn_process.outputs().to_vec()
@@ -190,14 +217,20 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         let new_component_addresses = track.new_component_addresses();
         let new_resource_addresses = track.new_resource_addresses();
         let logs = track.logs().clone();
+        let execution_trace = track.take_execution_trace();
 
-        // commit state updates
-        let commit_receipt = if error.is_none() {
-            let receipt = track.commit();
-            self.substate_store.increase_nonce();
-            Some(receipt)
-        } else {
-            None
+        // commit state updates, or only the fee payment if the transaction failed
+        let (commit_receipt, error) = match error {
+            None => {
+                let receipt = track.commit();
+                self.substate_store.increase_nonce();
+                (Some(receipt), None)
+            }
+            Some(error) if track.locked_fee().is_zero() => (None, Some(error)),
+            Some(error) => match track.commit_fee_payment() {
+                Ok(receipt) => (Some(receipt), Some(error)),
+                Err(fee_error) => (None, Some(RuntimeError::VaultError(fee_error))),
+            },
         };
 
 
@@ -215,6 +248,8 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
                 None => Ok(()),
             },
             outputs,
+            call_stack,
+            execution_trace,
             logs,
             new_package_addresses,
             new_component_addresses,
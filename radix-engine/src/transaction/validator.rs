@@ -0,0 +1,216 @@
+use lru::LruCache;
+use scrypto::crypto::*;
+use scrypto::rust::vec::Vec;
+
+use crate::errors::*;
+use crate::model::*;
+
+/// The default number of validated transactions remembered by a `TransactionValidator`.
+pub const DEFAULT_VALIDATION_CACHE_SIZE: usize = 4096;
+
+/// A validator for checking many transactions, e.g. when admitting them into a mempool.
+///
+/// Transactions which have been validated before, with the same signatures, are served from a cache
+/// keyed by `raw_hash`.
+pub struct TransactionValidator {
+    config: ValidationConfig,
+    cache: LruCache<Hash, (Vec<(EcdsaPublicKey, EcdsaSignature)>, ValidatedTransaction)>,
+}
+
+impl TransactionValidator {
+    pub fn new(config: ValidationConfig) -> Self {
+        Self::with_cache_size(config, DEFAULT_VALIDATION_CACHE_SIZE)
+    }
+
+    pub fn with_cache_size(config: ValidationConfig, cache_size: usize) -> Self {
+        Self {
+            config,
+            cache: LruCache::new(cache_size),
+        }
+    }
+
+    pub fn config(&self) -> &ValidationConfig {
+        &self.config
+    }
+
+    /// Validates a single transaction.
+    pub fn validate(
+        &mut self,
+        transaction: &SignedTransaction,
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
+        let raw_hash = transaction.transaction.raw_hash();
+        if let Some(validated) = self.lookup(&raw_hash, transaction) {
+            return Ok(validated);
+        }
+
+        let result = transaction.validate_with_config(&self.config);
+        self.remember(raw_hash, transaction, &result);
+        result
+    }
+
+    /// Decodes and validates a single transaction received as raw bytes.
+    pub fn validate_slice(
+        &mut self,
+        slice: &[u8],
+    ) -> Result<ValidatedTransaction, TransactionValidationError> {
+        let transaction = SignedTransaction::from_slice_with_config(slice, &self.config)?;
+        self.validate(&transaction)
+    }
+
+    /// Validates a batch of transactions, returning the results in the same order.
+    ///
+    /// With the `std` feature, transactions not found in the cache are validated on multiple threads.
+    pub fn validate_batch(
+        &mut self,
+        transactions: &[SignedTransaction],
+    ) -> Vec<Result<ValidatedTransaction, TransactionValidationError>> {
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut pending = Vec::new();
+        for (i, transaction) in transactions.iter().enumerate() {
+            let raw_hash = transaction.transaction.raw_hash();
+            match self.lookup(&raw_hash, transaction) {
+                Some(validated) => results.push(Some(Ok(validated))),
+                None => {
+                    results.push(None);
+                    pending.push((i, raw_hash));
+                }
+            }
+        }
+
+        let pending_transactions: Vec<&SignedTransaction> =
+            pending.iter().map(|(i, _)| &transactions[*i]).collect();
+        let validated = Self::validate_all(&self.config, &pending_transactions);
+
+        for ((i, raw_hash), result) in pending.into_iter().zip(validated) {
+            self.remember(raw_hash, &transactions[i], &result);
+            results[i] = Some(result);
+        }
+
+        results.into_iter().map(Option::unwrap).collect()
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn validate_all(
+        config: &ValidationConfig,
+        transactions: &[&SignedTransaction],
+    ) -> Vec<Result<ValidatedTransaction, TransactionValidationError>> {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        if threads <= 1 || transactions.len() <= 1 {
+            return transactions
+                .iter()
+                .map(|transaction| transaction.validate_with_config(config))
+                .collect();
+        }
+
+        let chunk_size = transactions.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = transactions
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|transaction| transaction.validate_with_config(config))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Validation thread panicked"))
+                .collect()
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn validate_all(
+        config: &ValidationConfig,
+        transactions: &[&SignedTransaction],
+    ) -> Vec<Result<ValidatedTransaction, TransactionValidationError>> {
+        transactions
+            .iter()
+            .map(|transaction| transaction.validate_with_config(config))
+            .collect()
+    }
+
+    fn lookup(
+        &mut self,
+        raw_hash: &Hash,
+        transaction: &SignedTransaction,
+    ) -> Option<ValidatedTransaction> {
+        match self.cache.get(raw_hash) {
+            Some((signatures, validated)) if *signatures == transaction.signatures => {
+                Some(validated.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn remember(
+        &mut self,
+        raw_hash: Hash,
+        transaction: &SignedTransaction,
+        result: &Result<ValidatedTransaction, TransactionValidationError>,
+    ) {
+        if let Ok(validated) = result {
+            self.cache
+                .put(raw_hash, (transaction.signatures.clone(), validated.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrypto::rust::vec;
+
+    fn transaction(nonce: u64, sks: &[&EcdsaPrivateKey]) -> SignedTransaction {
+        Transaction {
+            network_id: LOCAL_SIMULATOR_NETWORK_ID,
+            instructions: vec![Instruction::Nonce { nonce }],
+            subintents: Vec::new(),
+        }
+        .sign(sks)
+    }
+
+    #[test]
+    fn batch_validation_matches_single_validation() {
+        let sk = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let mut transactions: Vec<SignedTransaction> =
+            (0..16).map(|nonce| transaction(nonce, &[&sk])).collect();
+        transactions[3].signatures[0].1 = transactions[4].signatures[0].1;
+
+        let mut validator = TransactionValidator::new(ValidationConfig::default());
+        let results = validator.validate_batch(&transactions);
+
+        assert_eq!(results.len(), transactions.len());
+        for (transaction, result) in transactions.iter().zip(results) {
+            assert_eq!(result, transaction.validate());
+        }
+        assert_eq!(
+            validator.validate_batch(&transactions[3..4]),
+            vec![Err(TransactionValidationError::InvalidSignature)]
+        );
+    }
+
+    #[test]
+    fn cached_transaction_requires_same_signatures() {
+        let sk1 = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let sk2 = EcdsaPrivateKey::from_bytes(&[2u8; 32]).unwrap();
+        let mut validator = TransactionValidator::new(ValidationConfig::default());
+
+        let signed = transaction(0, &[&sk1]);
+        assert_eq!(validator.validate(&signed), signed.validate());
+        assert_eq!(validator.validate(&signed), signed.validate());
+
+        let mut forged = transaction(0, &[&sk1]);
+        forged.signatures[0].0 = sk2.public_key();
+        assert_eq!(
+            validator.validate(&forged),
+            Err(TransactionValidationError::InvalidSignature)
+        );
+    }
+}
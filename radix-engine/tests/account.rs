@@ -2,6 +2,7 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
+use radix_engine::engine::IdAllocator;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
 use radix_engine::model::*;
@@ -122,3 +123,448 @@ fn test_account_balance() {
         ScryptoValue::from_value(&Decimal::from(1000000))
     );
 }
+
+fn transfer_to_account_with_deposit_policy(
+    deposit_mode: AccountDepositMode,
+    rule: Option<ResourceDepositRule>,
+) -> Receipt {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let mut builder = test_runner.new_transaction_builder();
+    builder.set_account_deposit_mode(deposit_mode, other_account);
+    if let Some(rule) = rule {
+        builder.set_account_resource_deposit_rule(RADIX_TOKEN, rule, other_account);
+    }
+    let transaction = builder
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(Decimal::from(100), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner.validate_and_execute(&transaction)
+}
+
+#[test]
+fn allow_list_account_should_reject_unlisted_resource() {
+    let receipt = transfer_to_account_with_deposit_policy(AccountDepositMode::AllowList, None);
+
+    assert!(receipt.result.is_err());
+}
+
+#[test]
+fn allow_list_account_should_accept_allowed_resource() {
+    let receipt = transfer_to_account_with_deposit_policy(
+        AccountDepositMode::AllowList,
+        Some(ResourceDepositRule::Allow),
+    );
+
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn deny_list_account_should_reject_denied_resource() {
+    let receipt = transfer_to_account_with_deposit_policy(
+        AccountDepositMode::DenyList,
+        Some(ResourceDepositRule::Deny),
+    );
+
+    assert!(receipt.result.is_err());
+}
+
+#[test]
+fn accept_all_account_should_reject_denied_resource() {
+    let receipt = transfer_to_account_with_deposit_policy(
+        AccountDepositMode::AcceptAll,
+        Some(ResourceDepositRule::Deny),
+    );
+
+    assert!(receipt.result.is_err());
+}
+
+#[test]
+fn deny_list_account_should_accept_unlisted_resource() {
+    let receipt = transfer_to_account_with_deposit_policy(AccountDepositMode::DenyList, None);
+
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn cannot_set_deposit_mode_of_other_account() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, _) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .set_account_deposit_mode(AccountDepositMode::AllowList, other_account)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert!(matches!(error, RuntimeError::AuthorizationError { .. }));
+}
+
+#[test]
+fn can_withdraw_batch_from_my_account() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let resource_address = test_runner.create_non_fungible_resource(account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_batch_from_account(
+            &[
+                (Decimal::from(100), RADIX_TOKEN),
+                (Decimal::from(1), resource_address),
+            ],
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn locked_fee_should_be_paid() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .lock_fee(Decimal::from(10), account)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert_balance(&mut test_runner, account, Decimal::from(999_990));
+}
+
+#[test]
+fn locked_fee_should_be_paid_if_transaction_fails() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .lock_fee(Decimal::from(10), account)
+        .withdraw_from_account_by_amount(Decimal::from(2_000_000), RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect_err("Should be an error");
+    assert_balance(&mut test_runner, account, Decimal::from(999_990));
+}
+
+#[test]
+fn cannot_lock_fee_not_covered_by_committed_balance() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .lock_fee(Decimal::from(1_500_000), account)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::VaultError(
+            VaultError::ResourceContainerError(ResourceContainerError::InsufficientBalance)
+        ))
+    );
+    assert_balance(&mut test_runner, account, Decimal::from(1_000_000));
+}
+
+#[test]
+fn cannot_lock_fee_from_other_account() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let (other_pk, other_sk, _) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .lock_fee(Decimal::from(10), account)
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+    assert_balance(&mut test_runner, account, Decimal::from(1_000_000));
+}
+
+fn assert_balance(test_runner: &mut TestRunner, account: ComponentAddress, amount: Decimal) {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_account_balance(account, RADIX_TOKEN, AmountCondition::Exactly(amount))
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Balance should match");
+}
+
+#[test]
+fn securified_account_should_require_owner_badge() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .securify_account(account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let owner_badge = receipt.new_resource_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let key_receipt = test_runner.validate_and_execute(&transaction);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(owner_badge, other_account)
+        .withdraw_from_account(RADIX_TOKEN, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    let badge_receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = key_receipt.result.expect_err("Should be an error");
+    assert!(matches!(error, RuntimeError::AuthorizationError { .. }));
+    badge_receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn cannot_securify_account_twice() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .securify_account(account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let owner_badge = receipt.new_resource_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(owner_badge, other_account)
+        .securify_account(account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert!(receipt.result.is_err());
+}
+
+#[test]
+fn can_deposit_to_virtual_account_and_withdraw_with_its_key() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk) = test_runner.new_key_pair();
+    let virtual_account = IdAllocator::new_virtual_account_address(&other_pk);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(Decimal::from(10), RADIX_TOKEN, account)
+        .deposit_to_virtual_account(other_pk)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let deposit_receipt = test_runner.validate_and_execute(&transaction);
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(RADIX_TOKEN, virtual_account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([other_pk]))
+        .sign([&other_sk]);
+    let withdraw_receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    deposit_receipt.result.expect("Should be okay");
+    assert_eq!(
+        deposit_receipt.new_component_addresses,
+        vec![virtual_account]
+    );
+    withdraw_receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn create_virtual_account_should_be_idempotent() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, _) = test_runner.new_key_pair();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_virtual_account(other_pk)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(Decimal::from(10), RADIX_TOKEN, account)
+        .deposit_to_virtual_account(other_pk)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+    assert!(receipt.new_component_addresses.is_empty());
+}
+
+#[test]
+fn cannot_withdraw_from_other_virtual_account() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, _) = test_runner.new_key_pair();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(Decimal::from(10), RADIX_TOKEN, account)
+        .deposit_to_virtual_account(other_pk)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account(
+            RADIX_TOKEN,
+            IdAllocator::new_virtual_account_address(&other_pk),
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert!(matches!(error, RuntimeError::AuthorizationError { .. }));
+}
+
+#[test]
+fn cannot_lock_fee_from_virtual_account_created_in_same_transaction() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk) = test_runner.new_key_pair();
+    let virtual_account = IdAllocator::new_virtual_account_address(&pk);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .call_method_with_all_resources(virtual_account, "deposit_batch")
+        .lock_fee(Decimal::from(10), virtual_account)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::VaultError(
+            VaultError::LockFeeFromUncommittedVault
+        ))
+    );
+}
+
+#[test]
+fn cannot_call_missing_component_outside_virtual_account_addresses() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, _) = test_runner.new_account();
+    let missing_component = ComponentAddress([1u8; 26]);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(missing_component, "balance", args![RADIX_TOKEN])
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result,
+        Err(RuntimeError::ComponentNotFound(missing_component))
+    );
+}
@@ -0,0 +1,217 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::{AssertionFailure, RuntimeError};
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::*;
+use scrypto::prelude::*;
+
+#[test]
+fn can_assert_resource_supply_and_account_balance() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address = test_runner.create_fungible_resource(100.into(), 0, account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_resource_supply(resource_address, AmountCondition::Exactly(100.into()))
+        .assert_account_balance(
+            account,
+            resource_address,
+            AmountCondition::AtLeast(100.into()),
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn failed_resource_supply_assertion_reports_actual_supply() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address = test_runner.create_fungible_resource(100.into(), 0, account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_resource_supply(resource_address, AmountCondition::AtLeast(101.into()))
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_eq!(
+        error,
+        RuntimeError::AssertionFailed(Box::new(AssertionFailure::ResourceSupply {
+            resource_address,
+            condition: AmountCondition::AtLeast(101.into()),
+            actual: 100.into(),
+        }))
+    );
+}
+
+#[test]
+fn failed_account_balance_assertion_reports_actual_balance() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address = test_runner.create_fungible_resource(100.into(), 0, account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_account_balance(
+            account,
+            resource_address,
+            AmountCondition::AtMost(50.into()),
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_eq!(
+        error,
+        RuntimeError::AssertionFailed(Box::new(AssertionFailure::AccountBalance {
+            component_address: account,
+            resource_address,
+            condition: AmountCondition::AtMost(50.into()),
+            actual: 100.into(),
+        }))
+    );
+}
+
+#[test]
+fn can_assert_vault_balance() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_vault_balance(
+            component_address,
+            SchemaPath::from_str("test_vault").unwrap(),
+            AmountCondition::Exactly(1000.into()),
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn can_assert_component_state() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "ComponentTest",
+            "create_component",
+            args![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    let component_address = receipt.new_component_addresses[0];
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_component_state(
+            component_address,
+            SchemaPath::from_str("secret").unwrap(),
+            &"Secret".to_owned(),
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn can_assert_lazy_map_entry_absence() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (_, _, other_account) = test_runner.new_account();
+    let resource_address = test_runner.create_fungible_resource(100.into(), 0, other_account);
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_lazy_map_entry::<ResourceAddress, ()>(
+            account,
+            SchemaPath::from_str("vaults").unwrap(),
+            &resource_address,
+            None,
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn asserting_missing_component_field_fails() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let path = SchemaPath::from_str("does_not_exist").unwrap();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .assert_component_state(account, path.clone(), &())
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_eq!(
+        error,
+        RuntimeError::AssertionFailed(Box::new(AssertionFailure::SchemaPathNotFound {
+            component_address: account,
+            path,
+        }))
+    );
+}
@@ -6,6 +6,10 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{
+    evaluate_component_method_auth, AuthRuleTraceNode, HardDecimal, HypotheticalProof,
+    LockedAmountOrIds,
+};
 use scrypto::prelude::*;
 
 fn test_auth_rule(
@@ -328,3 +332,123 @@ fn cannot_withdraw_from_my_any_xrd_auth_account_with_less_than_amount_of_proof()
     let error = receipt.result.expect_err("Should be an error");
     assert_auth_error!(error);
 }
+
+#[test]
+fn authorization_error_explains_which_amount_was_missing() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let xrd_auth = rule!(require_amount(Decimal::from(100), RADIX_TOKEN));
+    let account = test_runner.new_account_with_auth_rule(&xrd_auth);
+    let (_, _, other_account) = test_runner.new_account();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(SYSTEM_COMPONENT, "free_xrd", args![])
+        .take_from_worktop_by_amount(Decimal::from(60), RADIX_TOKEN, |builder, bucket_id| {
+            builder.create_proof_from_bucket(bucket_id, |builder, proof_id| {
+                builder.push_to_auth_zone(proof_id);
+                builder.withdraw_from_account(RADIX_TOKEN, account);
+                builder.pop_from_auth_zone(|builder, proof_id| builder.drop_proof(proof_id));
+                builder
+            });
+            builder
+        })
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    let trace = match error {
+        RuntimeError::AuthorizationError { trace, .. } => trace,
+        e => panic!("Expected auth error but got: {:?}", e),
+    };
+    let rule = trace.rule.expect("Should have evaluated a rule");
+    assert!(!rule.passed);
+    match rule.node {
+        AuthRuleTraceNode::AmountOf {
+            required,
+            available,
+            ..
+        } => {
+            assert_eq!(required, HardDecimal::Amount(Decimal::from(100)));
+            assert_eq!(available, Decimal::from(60));
+        }
+        node => panic!("Expected an amount rule but got: {:?}", node),
+    }
+    assert_eq!(
+        trace.proofs,
+        vec![(RADIX_TOKEN, LockedAmountOrIds::Amount(Decimal::from(60)))]
+    );
+}
+
+#[test]
+fn can_evaluate_account_withdraw_auth_offline() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, _, account) = test_runner.new_account();
+    let (other_pk, _) = test_runner.new_key_pair();
+    let signer_proof = |pk: EcdsaPublicKey| {
+        HypotheticalProof::Ids(
+            BTreeSet::from([NonFungibleId::from_bytes(pk.to_vec())]),
+            ECDSA_TOKEN,
+        )
+    };
+
+    // Act
+    let with_owner_key =
+        evaluate_component_method_auth(&substate_store, account, "withdraw", &[signer_proof(pk)])
+            .unwrap();
+    let with_other_key = evaluate_component_method_auth(
+        &substate_store,
+        account,
+        "withdraw",
+        &[signer_proof(other_pk)],
+    )
+    .unwrap();
+
+    // Assert
+    assert!(with_owner_key.is_authorized());
+    assert!(!with_other_key.is_authorized());
+}
+
+#[test]
+fn can_evaluate_amount_auth_offline_against_several_proofs() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let xrd_auth = rule!(require_amount(Decimal::from(100), RADIX_TOKEN));
+    let account = test_runner.new_account_with_auth_rule(&xrd_auth);
+
+    // Act
+    let enough = evaluate_component_method_auth(
+        &substate_store,
+        account,
+        "withdraw",
+        &[
+            HypotheticalProof::Amount(Decimal::from(60), RADIX_TOKEN),
+            HypotheticalProof::Amount(Decimal::from(40), RADIX_TOKEN),
+        ],
+    )
+    .unwrap();
+    let not_enough = evaluate_component_method_auth(
+        &substate_store,
+        account,
+        "withdraw",
+        &[HypotheticalProof::Amount(Decimal::from(60), RADIX_TOKEN)],
+    )
+    .unwrap();
+
+    // Assert
+    assert!(enough.is_authorized());
+    assert!(!not_enough.is_authorized());
+    let (_, _, trace) = &not_enough.layers[0];
+    assert!(matches!(
+        &trace.rule.as_ref().unwrap().node,
+        AuthRuleTraceNode::AmountOf { available, .. } if *available == Decimal::from(60)
+    ));
+}
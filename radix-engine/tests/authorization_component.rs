@@ -129,3 +129,294 @@ fn can_make_cross_component_call_with_authorization() {
     // Assert
     receipt.result.expect("Should be okay");
 }
+
+fn test_cross_component_call_with_caller_rule<F>(
+    caller_rule: F,
+    call_directly: bool,
+    expect_err: bool,
+) where
+    F: FnOnce(PackageAddress, ComponentAddress) -> AccessRule,
+{
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component",
+            vec![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay.");
+    let my_component = receipt.new_component_addresses[0];
+
+    let authorization = AccessRules::new().method(
+        "get_component_state",
+        caller_rule(package_address, my_component),
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component_with_auth",
+            vec![scrypto_encode(&authorization)],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    let secured_component = receipt.new_component_addresses[0];
+
+    // Act
+    let mut builder = test_runner.new_transaction_builder();
+    if call_directly {
+        builder.call_method(secured_component, "get_component_state", vec![]);
+    } else {
+        builder.call_method(
+            my_component,
+            "cross_component_call",
+            vec![scrypto_encode(&secured_component)],
+        );
+    }
+    let transaction = builder.build(test_runner.get_nonce([])).sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    if expect_err {
+        let error = receipt.result.expect_err("Should be error");
+        assert_auth_error!(error);
+    } else {
+        receipt.result.expect("Should be okay");
+    }
+}
+
+#[test]
+fn can_make_cross_component_call_from_required_component() {
+    test_cross_component_call_with_caller_rule(
+        |_, component| rule!(require_caller(component)),
+        false,
+        false,
+    );
+}
+
+#[test]
+fn can_make_cross_component_call_from_required_package_or_blueprint() {
+    test_cross_component_call_with_caller_rule(
+        |package, _| rule!(require_caller(package)),
+        false,
+        false,
+    );
+    test_cross_component_call_with_caller_rule(
+        |package, _| rule!(require_caller((package, "CrossComponent"))),
+        false,
+        false,
+    );
+}
+
+#[test]
+fn cannot_make_cross_component_call_from_other_blueprint() {
+    test_cross_component_call_with_caller_rule(
+        |package, _| rule!(require_caller((package, "AuthComponent"))),
+        false,
+        true,
+    );
+}
+
+#[test]
+fn cannot_call_caller_protected_method_from_transaction() {
+    test_cross_component_call_with_caller_rule(
+        |_, component| rule!(require_caller(component)),
+        true,
+        true,
+    );
+}
+
+fn test_resource_call_with_caller_rule(through_other_component: bool, expect_err: bool) {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let package_address = test_runner.publish_package("component");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component",
+            vec![],
+        )
+        .call_function(
+            package_address,
+            "CrossComponent",
+            "create_component",
+            vec![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay.");
+    let my_component = receipt.new_component_addresses[0];
+    let other_component = receipt.new_component_addresses[1];
+
+    let mut resource_auth = HashMap::new();
+    resource_auth.insert(
+        ResourceMethod::UpdateMetadata,
+        (rule!(require_caller(my_component)), LOCKED),
+    );
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            SYSTEM_PACKAGE,
+            "System",
+            "new_resource",
+            vec![
+                scrypto_encode(&ResourceType::Fungible { divisibility: 18 }),
+                scrypto_encode(&HashMap::<String, String>::new()),
+                scrypto_encode(&resource_auth),
+                scrypto_encode::<Option<MintParams>>(&None),
+            ],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay.");
+    let resource_address = receipt.new_resource_addresses[0];
+
+    // Act
+    let mut builder = test_runner.new_transaction_builder();
+    if through_other_component {
+        builder.call_method(
+            my_component,
+            "cross_component_update_resource_metadata",
+            vec![
+                scrypto_encode(&other_component),
+                scrypto_encode(&resource_address),
+            ],
+        );
+    } else {
+        builder.call_method(
+            my_component,
+            "update_resource_metadata",
+            vec![scrypto_encode(&resource_address)],
+        );
+    }
+    let transaction = builder.build(test_runner.get_nonce([])).sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    if expect_err {
+        let error = receipt.result.expect_err("Should be error");
+        assert_auth_error!(error);
+    } else {
+        receipt.result.expect("Should be okay");
+    }
+}
+
+#[test]
+fn can_update_resource_from_required_component() {
+    test_resource_call_with_caller_rule(false, false);
+}
+
+#[test]
+fn cannot_update_resource_from_component_called_by_required_component() {
+    test_resource_call_with_caller_rule(true, true);
+}
+
+fn create_auth_chain_component(
+    test_runner: &mut TestRunner,
+    package_address: PackageAddress,
+    function_name: &str,
+    args: Vec<Vec<u8>>,
+) -> ComponentAddress {
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package_address, "AuthChain", function_name, args)
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    receipt.new_component_addresses[0]
+}
+
+fn test_call_chain_with_proof(function_name: &str, should_succeed: bool) {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let badge = test_runner.create_non_fungible_resource(account);
+    let package_address = test_runner.publish_package("component");
+    let guarded_component = create_auth_chain_component(
+        &mut test_runner,
+        package_address,
+        "create_guarded",
+        args![badge],
+    );
+    let middle_component = create_auth_chain_component(
+        &mut test_runner,
+        package_address,
+        "create",
+        args![guarded_component],
+    );
+    let first_component = create_auth_chain_component(
+        &mut test_runner,
+        package_address,
+        "create",
+        args![middle_component],
+    );
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(badge, account)
+        .pop_from_auth_zone(|builder, proof_id| {
+            builder.call_method(first_component, function_name, args![Proof(proof_id)])
+        })
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    if should_succeed {
+        receipt.result.expect("Should be okay");
+    } else {
+        let error = receipt.result.expect_err("Should be error");
+        assert_auth_error!(error);
+        let call_stack: Vec<(usize, &str, Option<ComponentAddress>)> = receipt
+            .call_stack
+            .iter()
+            .map(|frame| {
+                (
+                    frame.depth,
+                    frame.function.as_str(),
+                    frame
+                        .actor
+                        .as_ref()
+                        .and_then(|actor| actor.component_address()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            call_stack,
+            vec![
+                (3, "guarded", Some(guarded_component)),
+                (2, "call_guarded", Some(middle_component)),
+                (1, function_name, Some(first_component)),
+            ]
+        );
+    }
+}
+
+#[test]
+fn can_call_guarded_component_through_chain_with_lent_proof() {
+    test_call_chain_with_proof("lend_proof_to_next", true);
+}
+
+#[test]
+fn cannot_call_guarded_component_through_chain_without_lent_proof() {
+    test_call_chain_with_proof("call_next_without_lending", false);
+}
@@ -4,6 +4,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::AccessRulesError;
 use scrypto::prelude::*;
 
 fn test_dynamic_auth(
@@ -140,6 +141,41 @@ fn test_dynamic_authlist(
     }
 }
 
+fn test_invalid_dynamic_authlist(authorization: AccessRules) -> RuntimeError {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, address) = test_runner.new_key_pair_with_pk_address();
+    let package = test_runner.publish_package("component");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "AuthListComponent",
+            "create_component",
+            args!(2u8, vec![address], authorization),
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect_err("Should be an error.")
+}
+
+fn assert_invalid_access_rule_path(auth_rule: AccessRule, expected_path: &str) {
+    let error = test_invalid_dynamic_authlist(AccessRules::new().method("get_secret", auth_rule));
+    assert!(matches!(
+        error,
+        RuntimeError::InvalidAccessRules(AccessRulesError::InvalidPath {
+            method: Some(method),
+            path,
+        }) if method == "get_secret" && path.to_string() == expected_path
+    ));
+}
+
 #[test]
 fn dynamic_auth_should_allow_me_to_call_method_when_signed() {
     test_dynamic_auth(1, 0, None, &[0], true);
@@ -162,12 +198,12 @@ fn dynamic_auth_should_allow_me_to_call_method_when_change_auth() {
 
 #[test]
 fn dynamic_require_should_fail_on_dynamic_list() {
-    test_dynamic_authlist(3, rule!(require("auth")), &[0, 1, 2], false);
+    assert_invalid_access_rule_path(rule!(require("auth")), "auth");
 }
 
 #[test]
 fn dynamic_all_of_should_fail_on_nonexistent_resource() {
-    test_dynamic_authlist(3, rule!(require("does_not_exist")), &[0, 1, 2], false);
+    assert_invalid_access_rule_path(rule!(require("does_not_exist")), "does_not_exist");
 }
 
 #[test]
@@ -196,7 +232,41 @@ fn dynamic_min_n_of_should_fail_if_not_signed_enough() {
 
 #[test]
 fn dynamic_min_n_of_should_fail_if_path_does_not_exist() {
-    test_dynamic_authlist(3, rule!(require_n_of(1, "does_not_exist")), &[0, 1], false);
+    assert_invalid_access_rule_path(rule!(require_n_of(1, "does_not_exist")), "does_not_exist");
+}
+
+#[test]
+fn dynamic_min_n_of_should_fail_on_call_if_count_exceeds_list() {
+    // The count path is valid, so the component is created, but its value of 2 can't be met by a
+    // list of one
+    test_dynamic_authlist(1, rule!(require_n_of("count", "auth")), &[0], false);
+}
+
+#[test]
+fn dynamic_min_n_of_should_fail_if_count_path_is_not_u8() {
+    assert_invalid_access_rule_path(rule!(require_n_of("auth", "auth")), "auth");
+}
+
+#[test]
+fn access_rules_should_fail_on_unknown_method() {
+    let authorization = AccessRules::new().method("get_secrets", rule!(require_all_of("auth")));
+    let error = test_invalid_dynamic_authlist(authorization);
+    assert_eq!(
+        error,
+        RuntimeError::InvalidAccessRules(AccessRulesError::MethodNotFound(
+            "get_secrets".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn access_rules_should_fail_on_invalid_default_rule_path() {
+    let authorization = AccessRules::new().default(rule!(require("count")));
+    let error = test_invalid_dynamic_authlist(authorization);
+    assert!(matches!(
+        error,
+        RuntimeError::InvalidAccessRules(AccessRulesError::InvalidPath { method: None, .. })
+    ));
 }
 
 #[test]
@@ -211,7 +281,7 @@ fn dynamic_all_of_should_fail_if_not_signed_enough() {
 
 #[test]
 fn dynamic_all_of_should_fail_if_path_does_not_exist() {
-    test_dynamic_authlist(3, rule!(require_all_of("does_not_exist")), &[0, 1], false);
+    assert_invalid_access_rule_path(rule!(require_all_of("does_not_exist")), "does_not_exist");
 }
 
 #[test]
@@ -221,7 +291,7 @@ fn dynamic_any_of_should_allow_me_to_call_method() {
 
 #[test]
 fn dynamic_any_of_should_fail_if_path_does_not_exist() {
-    test_dynamic_authlist(3, rule!(require_any_of("does_not_exist")), &[0, 1], false);
+    assert_invalid_access_rule_path(rule!(require_any_of("does_not_exist")), "does_not_exist");
 }
 
 #[test]
@@ -311,3 +381,104 @@ fn chess_should_allow_second_player_to_move_after_first_player() {
     // Assert
     receipt.result.expect("Should be okay.");
 }
+
+fn test_lazy_map_auth(
+    guarded_by: &str,
+    fail_on_unresolved_access_rules: bool,
+    sign: bool,
+    should_succeed: bool,
+) {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, address) = test_runner.new_key_pair_with_pk_address();
+    let package = test_runner.publish_package("component");
+    let transaction1 = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "LazyMapAuthComponent",
+            "create_component",
+            args![
+                "alice".to_owned(),
+                address,
+                guarded_by.to_owned(),
+                fail_on_unresolved_access_rules
+            ],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt1 = test_runner.validate_and_execute(&transaction1);
+    receipt1.result.expect("Should be okay.");
+    let component = receipt1.new_component_addresses[0];
+
+    // Act
+    let pks = if sign { vec![pk] } else { vec![] };
+    let sks = if sign { vec![&sk] } else { vec![] };
+    let transaction2 = test_runner
+        .new_transaction_builder()
+        .call_method(component, "get_secret", args![])
+        .build(test_runner.get_nonce(&pks))
+        .sign(sks);
+    let receipt2 = test_runner.validate_and_execute(&transaction2);
+
+    // Assert
+    if should_succeed {
+        receipt2.result.expect("Should be okay.");
+    } else {
+        let error = receipt2.result.expect_err("Should be an error.");
+        assert_auth_error!(error);
+    }
+}
+
+#[test]
+fn can_call_method_guarded_by_lazy_map_entry_with_right_badge() {
+    test_lazy_map_auth("alice", false, true, true);
+}
+
+#[test]
+fn cannot_call_method_guarded_by_lazy_map_entry_without_badge() {
+    test_lazy_map_auth("alice", false, false, false);
+}
+
+#[test]
+fn cannot_call_method_guarded_by_missing_lazy_map_entry() {
+    test_lazy_map_auth("bob", false, true, false);
+}
+
+#[test]
+fn can_globalize_component_with_resolved_access_rule_path_when_strict() {
+    test_lazy_map_auth("alice", true, true, true);
+}
+
+#[test]
+fn cannot_globalize_component_with_unresolved_access_rule_path_when_strict() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, address) = test_runner.new_key_pair_with_pk_address();
+    let package = test_runner.publish_package("component");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "LazyMapAuthComponent",
+            "create_component",
+            args!["alice".to_owned(), address, "bob".to_owned(), true],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error.");
+    assert!(matches!(
+        error,
+        RuntimeError::UnresolvedAccessRulePath {
+            method: Some(method),
+            ..
+        } if method == "get_secret"
+    ));
+}
@@ -6,6 +6,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{evaluate_resource_method_auth, HypotheticalProof};
 use scrypto::prelude::*;
 
 enum Action {
@@ -136,3 +137,142 @@ fn cannot_deposit_with_wrong_auth() {
 fn can_deposit_with_right_auth() {
     test_resource_auth(Action::Deposit, true, true, false);
 }
+
+fn test_composite_amount_proof(use_separate_vaults: bool, expect_err: bool) {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let badge = test_runner.create_fungible_resource(Decimal::from(120), 0, account);
+    let protected_account =
+        test_runner.new_account_with_auth_rule(&rule!(require_amount(Decimal::from(100), badge)));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(Decimal::from(60), badge, account)
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .withdraw_from_account_by_amount(Decimal::from(10), RADIX_TOKEN, account)
+        .call_method_with_all_resources(protected_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let second_proof_account = if use_separate_vaults {
+        other_account
+    } else {
+        account
+    };
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account_by_amount(Decimal::from(60), badge, account)
+        .create_proof_from_account_by_amount(Decimal::from(60), badge, second_proof_account)
+        .withdraw_from_account_by_amount(Decimal::one(), RADIX_TOKEN, protected_account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk, other_pk]))
+        .sign([&sk, &other_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    if expect_err {
+        let err = receipt.result.expect_err("Should be a runtime error");
+        assert_auth_error!(err);
+    } else {
+        receipt.result.expect("Should be okay.");
+    }
+}
+
+#[test]
+fn can_withdraw_with_amount_proven_by_multiple_proofs() {
+    test_composite_amount_proof(true, false);
+}
+
+#[test]
+fn cannot_withdraw_with_amount_proven_twice_by_the_same_vault() {
+    test_composite_amount_proof(false, true);
+}
+
+#[test]
+fn can_withdraw_with_non_fungibles_proven_by_multiple_proofs() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let (other_pk, other_sk, other_account) = test_runner.new_account();
+    let badge = test_runner.create_non_fungible_resource(account);
+    let protected_account =
+        test_runner.new_account_with_auth_rule(&rule!(require_amount(Decimal::from(2), badge)));
+    let transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_ids(
+            &BTreeSet::from([NonFungibleId::from_u32(3)]),
+            badge,
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .withdraw_from_account_by_amount(Decimal::from(10), RADIX_TOKEN, account)
+        .call_method_with_all_resources(protected_account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account_by_ids(
+            &BTreeSet::from([NonFungibleId::from_u32(1)]),
+            badge,
+            account,
+        )
+        .create_proof_from_account_by_ids(
+            &BTreeSet::from([NonFungibleId::from_u32(3)]),
+            badge,
+            other_account,
+        )
+        .withdraw_from_account_by_amount(Decimal::one(), RADIX_TOKEN, protected_account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk, other_pk]))
+        .sign([&sk, &other_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn can_evaluate_mint_auth_offline() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let (token_address, mint_auth, burn_auth, _, _) = test_runner.create_restricted_token(account);
+    let badge_proof = |badge: ResourceAddress| {
+        HypotheticalProof::Ids(BTreeSet::from([NonFungibleId::from_u32(1)]), badge)
+    };
+
+    // Act
+    let with_mint_auth = evaluate_resource_method_auth(
+        &substate_store,
+        token_address,
+        ResourceMethod::Mint,
+        &[badge_proof(mint_auth)],
+    )
+    .unwrap();
+    let with_burn_auth = evaluate_resource_method_auth(
+        &substate_store,
+        token_address,
+        ResourceMethod::Mint,
+        &[badge_proof(burn_auth)],
+    )
+    .unwrap();
+
+    // Assert
+    assert!(with_mint_auth.is_authorized());
+    assert!(!with_burn_auth.is_authorized());
+}
@@ -0,0 +1,45 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct AuthChain {
+        next: Option<ComponentAddress>,
+    }
+
+    impl AuthChain {
+        pub fn create_guarded(badge: ResourceAddress) -> ComponentAddress {
+            Self { next: None }
+                .instantiate()
+                .add_access_check(
+                    AccessRules::new()
+                        .method("guarded", rule!(require(badge)))
+                        .default(rule!(allow_all)),
+                )
+                .globalize()
+        }
+
+        pub fn create(next: ComponentAddress) -> ComponentAddress {
+            Self { next: Some(next) }.instantiate().globalize()
+        }
+
+        pub fn guarded(&self) {}
+
+        pub fn call_guarded(&self) {
+            Runtime::call_method(self.next.unwrap(), "guarded", args![]);
+        }
+
+        pub fn lend_proof_to_next(&self, proof: Proof) {
+            let proofs = vec![proof];
+            Runtime::call_method_with_proofs(self.next.unwrap(), "call_guarded", args![], &proofs);
+
+            // The lent proofs are given back
+            for proof in proofs {
+                proof.drop();
+            }
+        }
+
+        pub fn call_next_without_lending(&self, proof: Proof) {
+            Runtime::call_method(self.next.unwrap(), "call_guarded", args![]);
+            proof.drop();
+        }
+    }
+}
@@ -47,5 +47,20 @@ blueprint! {
         pub fn get_component_state(&self) -> String {
             self.secret.clone()
         }
+
+        pub fn update_resource_metadata(&mut self, resource_address: ResourceAddress) {
+            borrow_resource_manager!(resource_address).update_metadata(HashMap::new());
+        }
+
+        pub fn cross_component_update_resource_metadata(
+            &mut self,
+            component_address: ComponentAddress,
+            resource_address: ResourceAddress,
+        ) {
+            borrow_component!(component_address).call::<()>(
+                "update_resource_metadata",
+                vec![scrypto_encode(&resource_address)],
+            );
+        }
     }
 }
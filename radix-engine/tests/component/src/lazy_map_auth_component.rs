@@ -0,0 +1,42 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct LazyMapAuthComponent {
+        admins: LazyMap<String, NonFungibleAddress>,
+    }
+
+    impl LazyMapAuthComponent {
+        pub fn create_component(
+            admin_name: String,
+            admin: NonFungibleAddress,
+            guarded_by: String,
+            fail_on_unresolved_access_rules: bool,
+        ) -> ComponentAddress {
+            let admins = LazyMap::new();
+            admins.insert(admin_name, admin);
+
+            let mut admin_path = SchemaPath::new();
+            admin_path.field("admins");
+            admin_path.key(&guarded_by);
+
+            let component = Self { admins }.instantiate().add_access_check(
+                AccessRules::new()
+                    .method("get_secret", rule!(require(admin_path)))
+                    .default(rule!(allow_all)),
+            );
+            if fail_on_unresolved_access_rules {
+                component.fail_on_unresolved_access_rules().globalize()
+            } else {
+                component.globalize()
+            }
+        }
+
+        pub fn get_secret(&self) -> String {
+            "Secret".to_owned()
+        }
+
+        pub fn set_admin(&mut self, admin_name: String, admin: NonFungibleAddress) {
+            self.admins.insert(admin_name, admin);
+        }
+    }
+}
@@ -1,8 +1,10 @@
+pub mod auth_chain;
 pub mod auth_component;
 pub mod auth_list_component;
 pub mod chess;
 pub mod component;
 pub mod cross_component;
+pub mod lazy_map_auth_component;
 pub mod package;
 pub mod reentrant_component;
 pub mod typed_cross_component_call;
@@ -1,6 +1,11 @@
+use radix_engine::engine::{ExecutionObserver, SubstateAccess, SubstateId};
+use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
+use radix_engine::model::Bucket;
 use radix_engine::transaction::*;
+use scrypto::engine::types::BucketId;
 use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
 
 #[test]
 fn test_process_and_transaction() {
@@ -36,3 +41,130 @@ fn test_call() {
     let receipt = executor.validate_and_execute(&transaction).unwrap();
     receipt.result.expect("Should be okay.");
 }
+
+#[test]
+fn test_blueprint_panic_message_is_reported() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "PanicTest", "panic_with_message", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+
+    let error = receipt.result.expect_err("Should be an error.");
+    match error {
+        RuntimeError::BlueprintPanic { actor, message } => {
+            assert_eq!(actor.blueprint_name(), "PanicTest");
+            assert!(message.contains("Something went wrong: 42"));
+        }
+        e => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_execution_trace_records_calls_and_resource_flows() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_execution_trace(true);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "MoveTest", "move_bucket", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result.expect("Should be okay.");
+
+    let execution_trace = receipt.execution_trace.expect("Should be traced.");
+    let move_bucket = execution_trace
+        .calls
+        .iter()
+        .find(|call| call.function == "move_bucket")
+        .expect("Should trace the function call.");
+    assert_eq!(move_bucket.depth, 1);
+    assert!(move_bucket.output.is_some());
+    let receive_bucket = move_bucket
+        .calls
+        .iter()
+        .find(|call| call.function == "receive_bucket")
+        .expect("Should trace the nested method call.");
+    assert_eq!(receive_bucket.depth, 2);
+    assert_eq!(receive_bucket.buckets_in.len(), 1);
+    assert_eq!(receive_bucket.buckets_in[0].amount, Decimal::from(1000));
+    assert!(receive_bucket
+        .substates
+        .iter()
+        .any(|access| matches!(access, SubstateAccess::Write(SubstateId::Component(_)))));
+}
+
+#[derive(Default)]
+struct CountingObserver {
+    invocations_entered: usize,
+    invocations_exited: usize,
+    host_calls: usize,
+    substate_reads: usize,
+    buckets_created: usize,
+}
+
+impl ExecutionObserver for CountingObserver {
+    fn on_invoke_enter(
+        &mut self,
+        _depth: usize,
+        _snode_ref: &SNodeRef,
+        _function: &str,
+        _args: &[ScryptoValue],
+    ) {
+        self.invocations_entered += 1;
+    }
+
+    fn on_invoke_exit(
+        &mut self,
+        _depth: usize,
+        _snode_ref: &SNodeRef,
+        _function: &str,
+        _result: Result<&ScryptoValue, &RuntimeError>,
+    ) {
+        self.invocations_exited += 1;
+    }
+
+    fn on_host_call(&mut self, _depth: usize, _operation: u32) {
+        self.host_calls += 1;
+    }
+
+    fn on_substate_read(&mut self, _substate_id: &SubstateId) {
+        self.substate_reads += 1;
+    }
+
+    fn on_bucket_created(&mut self, _bucket_id: BucketId, _bucket: &Bucket) {
+        self.buckets_created += 1;
+    }
+}
+
+#[test]
+fn test_execution_observer_is_notified() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut observer = CountingObserver::default();
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_observer(&mut observer);
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "core")))
+        .unwrap();
+
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "MoveTest", "move_bucket", args![])
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result.expect("Should be okay.");
+
+    assert!(observer.invocations_entered > 0);
+    assert_eq!(observer.invocations_entered, observer.invocations_exited);
+    assert!(observer.host_calls > 0);
+    assert!(observer.substate_reads > 0);
+    assert!(observer.buckets_created > 0);
+}
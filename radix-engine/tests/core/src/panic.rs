@@ -0,0 +1,11 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct PanicTest {}
+
+    impl PanicTest {
+        pub fn panic_with_message() {
+            panic!("Something went wrong: {}", 42);
+        }
+    }
+}
@@ -2,7 +2,9 @@
 pub mod test_runner;
 
 use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
+use radix_engine::model::{ResourceContainerError, ResourceManagerError};
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -78,6 +80,127 @@ fn can_burn_non_fungible() {
     receipt.result.expect("Should be okay.");
 }
 
+#[test]
+fn can_create_non_fungible_with_string_ids() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "create_non_fungible_with_string_ids",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay.");
+}
+
+#[test]
+fn cannot_mint_non_fungible_with_wrong_id_type() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "create_non_fungible_with_wrong_id_type",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result.expect_err("Should be an error"),
+        RuntimeError::ResourceManagerError(ResourceManagerError::ResourceContainerError(
+            ResourceContainerError::InvalidNonFungibleIdType(
+                NonFungibleId::from_u64(1),
+                NonFungibleIdType::U32
+            )
+        ))
+    );
+}
+
+#[test]
+fn cannot_mint_non_fungible_with_invalid_data() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "mint_non_fungible_with_invalid_data",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    assert_eq!(
+        receipt.result.expect_err("Should be an error"),
+        RuntimeError::ResourceManagerError(ResourceManagerError::InvalidNonFungibleData)
+    );
+}
+
+#[test]
+fn cannot_update_immutable_non_fungible_data() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, account) = test_runner.new_account();
+    let package = test_runner.publish_package("non_fungible");
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "update_immutable_non_fungible_data",
+            vec![],
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert!(matches!(
+        error,
+        RuntimeError::ResourceManagerError(ResourceManagerError::NonFungibleImmutableDataChanged(
+            ..
+        ))
+    ));
+}
+
 #[test]
 fn test_non_fungible() {
     let mut ledger = InMemorySubstateStore::with_bootstrap();
@@ -7,6 +7,13 @@ pub struct Sandwich {
     pub available: bool,
 }
 
+#[derive(NonFungibleData)]
+pub struct Pizza {
+    pub size: u32,
+    #[scrypto(mutable)]
+    pub toppings: Vec<String>,
+}
+
 blueprint! {
     struct NonFungibleTest {
         vault: Vault,
@@ -22,6 +29,7 @@ blueprint! {
             // Create non-fungible resource with mutable supply
             let resource_address = ResourceBuilder::new_non_fungible()
                 .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::U32)
                 .mintable(rule!(require(mint_badge.resource_address())), LOCKED)
                 .burnable(rule!(allow_all), LOCKED)
                 .updateable_non_fungible_data(rule!(require(mint_badge.resource_address())), LOCKED)
@@ -44,6 +52,7 @@ blueprint! {
         pub fn create_burnable_non_fungible() -> Bucket {
             ResourceBuilder::new_non_fungible()
                 .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::U32)
                 .burnable(rule!(allow_all), LOCKED)
                 .initial_supply([
                     (
@@ -66,6 +75,7 @@ blueprint! {
         pub fn create_non_fungible_fixed() -> Bucket {
             ResourceBuilder::new_non_fungible()
                 .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::U32)
                 .initial_supply([
                     (
                         NonFungibleId::from_u32(1),
@@ -91,6 +101,79 @@ blueprint! {
                 ])
         }
 
+        pub fn create_non_fungible_with_string_ids() -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::String)
+                .initial_supply([
+                    (
+                        NonFungibleId::from_string("pastrami"),
+                        Sandwich {
+                            name: "Pastrami".to_owned(),
+                            available: true,
+                        },
+                    ),
+                    (
+                        NonFungibleId::from_string("reuben"),
+                        Sandwich {
+                            name: "Reuben".to_owned(),
+                            available: true,
+                        },
+                    ),
+                ])
+        }
+
+        pub fn create_non_fungible_with_wrong_id_type() -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::U32)
+                .initial_supply([(
+                    NonFungibleId::from_u64(1),
+                    Sandwich {
+                        name: "One".to_owned(),
+                        available: true,
+                    },
+                )])
+        }
+
+        pub fn mint_non_fungible_with_invalid_data() -> (Bucket, Bucket) {
+            let mint_badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let resource_address = ResourceBuilder::new_non_fungible()
+                .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::U32)
+                .mintable(rule!(require(mint_badge.resource_address())), LOCKED)
+                .data_schema::<Sandwich>()
+                .no_initial_supply();
+
+            let non_fungible = mint_badge.authorize(|| {
+                borrow_resource_manager!(resource_address).mint_non_fungible(
+                    &NonFungibleId::from_u32(0),
+                    Pizza {
+                        size: 12,
+                        toppings: vec!["Pastrami".to_owned()],
+                    },
+                )
+            });
+
+            (mint_badge, non_fungible)
+        }
+
+        pub fn update_immutable_non_fungible_data() -> (Bucket, Bucket) {
+            let (mint_badge, resource_address, bucket) = Self::create_non_fungible_mutable();
+            mint_badge.authorize(|| {
+                borrow_resource_manager!(resource_address).update_non_fungible_data(
+                    &NonFungibleId::from_u32(0),
+                    Sandwich {
+                        name: "Changed".to_owned(),
+                        available: true,
+                    },
+                );
+            });
+            (mint_badge, bucket)
+        }
+
         pub fn verify_does_not_exist(address: NonFungibleAddress) {
             assert_eq!(
                 borrow_resource_manager!(address.resource_address())
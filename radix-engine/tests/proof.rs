@@ -4,6 +4,7 @@ pub mod test_runner;
 use crate::test_runner::TestRunner;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::{AuthZoneError, ProofError};
 use scrypto::prelude::*;
 
 #[test]
@@ -257,8 +258,7 @@ fn can_create_proof_from_account_and_pass_on() {
     receipt.result.expect("Should be okay.");
 }
 
-#[test]
-fn cant_move_restricted_proof() {
+fn pass_proof_from_account(function_name: &str) -> Result<(), RuntimeError> {
     // Arrange
     let mut substate_store = InMemorySubstateStore::with_bootstrap();
     let mut test_runner = TestRunner::new(&mut substate_store);
@@ -273,8 +273,8 @@ fn cant_move_restricted_proof() {
         .call_function_with_abi(
             package_address,
             "VaultProof",
-            "receive_proof_and_push_to_auth_zone",
-            vec![format!("1,{}", resource_address), "1".to_owned()],
+            function_name,
+            vec![format!("1,{}", resource_address)],
             Some(account),
             &test_runner.export_abi(package_address, "VaultProof"),
         )
@@ -283,12 +283,89 @@ fn cant_move_restricted_proof() {
         .sign([&sk]);
     let receipt = test_runner.validate_and_execute(&transaction);
     println!("{:?}", receipt);
+    receipt.result
+}
+
+#[test]
+fn restricted_proof_stays_restricted_in_auth_zone() {
+    let result = pass_proof_from_account("receive_proof_and_push_to_auth_zone");
+
+    // Assert
+    result.expect("Should be okay.");
+}
+
+#[test]
+fn cant_move_restricted_proof() {
+    let result = pass_proof_from_account("receive_proof_and_pass_on");
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(RuntimeError::CantMoveRestrictedProof(_))
+    ));
+}
+
+#[test]
+fn cant_return_restricted_proof() {
+    let result = pass_proof_from_account("receive_proof_and_return");
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(RuntimeError::CantMoveRestrictedProof(_))
+    ));
+}
+
+#[test]
+fn cant_create_proof_from_restricted_proof_in_auth_zone() {
+    let result = pass_proof_from_account("receive_proof_and_create_proof_from_auth_zone");
 
     // Assert
     assert_eq!(
-        receipt.result,
-        Err(RuntimeError::CantMoveRestrictedProof(1025))
+        result,
+        Err(RuntimeError::AuthZoneError(AuthZoneError::ProofError(
+            ProofError::RestrictedProofNotComposable
+        )))
+    );
+}
+
+#[test]
+fn cant_move_proof_restricted_on_purpose() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let resource_address =
+        test_runner.create_fungible_resource(100.into(), DIVISIBILITY_MAXIMUM, account);
+    let package_address = test_runner.publish_package("proof");
+    let component_address = test_runner.instantiate_component(
+        package_address,
+        "VaultProof",
+        "new",
+        vec![format!("1,{}", resource_address)],
+        account,
+        pk,
+        &sk,
     );
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_method(
+            component_address,
+            "restrict_vault_proof_and_pass_on",
+            vec![],
+        )
+        .build(test_runner.get_nonce([]))
+        .sign([]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    println!("{:?}", receipt);
+
+    // Assert
+    assert!(matches!(
+        receipt.result,
+        Err(RuntimeError::CantMoveRestrictedProof(_))
+    ));
 }
 
 #[test]
@@ -69,7 +69,51 @@ blueprint! {
         }
 
         pub fn receive_proof_and_push_to_auth_zone(proof: Proof) {
-            ComponentAuthZone::push(proof); // should fail here
+            assert!(proof.is_restricted());
+            ComponentAuthZone::push(proof);
+            let proof = ComponentAuthZone::pop();
+            assert!(proof.is_restricted());
+            proof.drop();
+        }
+
+        pub fn receive_proof_and_pass_on(proof: Proof) {
+            let amount = proof.amount();
+            let resource_address = proof.resource_address();
+            Runtime::call_function(
+                Runtime::package_address(),
+                "Receiver",
+                "assert_amount",
+                args![proof, amount, resource_address],
+            ); // should fail here
+        }
+
+        pub fn receive_proof_and_return(proof: Proof) -> Proof {
+            proof // should fail here
+        }
+
+        pub fn receive_proof_and_create_proof_from_auth_zone(proof: Proof) {
+            let resource_address = proof.resource_address();
+            ComponentAuthZone::push(proof);
+            ComponentAuthZone::create_proof(resource_address).drop(); // should fail here
+        }
+
+        pub fn restrict_vault_proof_and_pass_on(&self) {
+            let proof = self.vault.create_proof();
+            assert!(!proof.is_restricted());
+            proof.restrict();
+            assert!(proof.is_restricted());
+            let clone = proof.clone();
+            assert!(clone.is_restricted());
+            clone.drop();
+
+            let amount = proof.amount();
+            let resource_address = proof.resource_address();
+            Runtime::call_function(
+                Runtime::package_address(),
+                "Receiver",
+                "assert_amount",
+                args![proof, amount, resource_address],
+            ); // should fail here
         }
 
         pub fn compose_vault_and_bucket_proof(&mut self, bucket: Bucket) {
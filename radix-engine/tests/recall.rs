@@ -0,0 +1,193 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::RuntimeError;
+use radix_engine::ledger::InMemorySubstateStore;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+struct TokenHolder {
+    component_address: ComponentAddress,
+    issuer_badge: ResourceAddress,
+    token: ResourceAddress,
+}
+
+fn new_token_holder(
+    test_runner: &mut TestRunner,
+    (pk, sk, account): (EcdsaPublicKey, &EcdsaPrivateKey, ComponentAddress),
+) -> TokenHolder {
+    let package = test_runner.publish_package("resource_creator");
+    let transaction = test_runner
+        .new_transaction_builder()
+        .call_function(package, "TokenHolder", "new", args![])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+    receipt.result.expect("Should be okay");
+    TokenHolder {
+        component_address: receipt.new_component_addresses[0],
+        issuer_badge: receipt.new_resource_addresses[0],
+        token: receipt.new_resource_addresses[1],
+    }
+}
+
+#[test]
+fn issuer_can_recall_from_vault() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let holder = new_token_holder(&mut test_runner, (pk, &sk, account));
+    let path = SchemaPath::from_str("vault").unwrap();
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(holder.issuer_badge, account)
+        .recall_from_vault(
+            holder.component_address,
+            path.clone(),
+            40.into(),
+            holder.token,
+        )
+        .assert_worktop_contains_by_amount(40.into(), holder.token)
+        .assert_vault_balance(
+            holder.component_address,
+            path,
+            AmountCondition::Exactly(60.into()),
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn cannot_recall_without_issuer_badge() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let holder = new_token_holder(&mut test_runner, (pk, &sk, account));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .recall_from_vault(
+            holder.component_address,
+            SchemaPath::from_str("vault").unwrap(),
+            40.into(),
+            holder.token,
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn cannot_freeze_without_issuer_badge() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let holder = new_token_holder(&mut test_runner, (pk, &sk, account));
+
+    // Act
+    let transaction = test_runner
+        .new_transaction_builder()
+        .freeze_vault(
+            holder.component_address,
+            SchemaPath::from_str("vault").unwrap(),
+            holder.token,
+        )
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn frozen_vault_rejects_withdraw_and_deposit_until_unfrozen() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (pk, sk, account) = test_runner.new_account();
+    let holder = new_token_holder(&mut test_runner, (pk, &sk, account));
+    let path = SchemaPath::from_str("vault").unwrap();
+    let transaction = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(holder.issuer_badge, account)
+        .freeze_vault(holder.component_address, path.clone(), holder.token)
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    test_runner
+        .validate_and_execute(&transaction)
+        .result
+        .expect("Should be okay");
+
+    // Act
+    let withdraw = test_runner
+        .new_transaction_builder()
+        .call_method(holder.component_address, "withdraw", args![Decimal::one()])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let withdraw_receipt = test_runner.validate_and_execute(&withdraw);
+    let recall_and_deposit = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(holder.issuer_badge, account)
+        .recall_from_vault(
+            holder.component_address,
+            path.clone(),
+            1.into(),
+            holder.token,
+        )
+        .take_from_worktop(holder.token, |builder, bucket_id| {
+            builder.call_method(
+                holder.component_address,
+                "deposit",
+                args![scrypto::resource::Bucket(bucket_id)],
+            )
+        })
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let recall_and_deposit_receipt = test_runner.validate_and_execute(&recall_and_deposit);
+    let unfreeze_and_withdraw = test_runner
+        .new_transaction_builder()
+        .create_proof_from_account(holder.issuer_badge, account)
+        .unfreeze_vault(holder.component_address, path, holder.token)
+        .call_method(holder.component_address, "withdraw", args![Decimal::one()])
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(test_runner.get_nonce([pk]))
+        .sign([&sk]);
+    let unfreeze_and_withdraw_receipt = test_runner.validate_and_execute(&unfreeze_and_withdraw);
+
+    // Assert
+    assert_eq!(
+        withdraw_receipt.result.expect_err("Should be an error"),
+        RuntimeError::VaultError(VaultError::VaultFrozen)
+    );
+    assert_eq!(
+        recall_and_deposit_receipt
+            .result
+            .expect_err("Should be an error"),
+        RuntimeError::VaultError(VaultError::VaultFrozen)
+    );
+    unfreeze_and_withdraw_receipt
+        .result
+        .expect("Should be okay");
+}
@@ -34,6 +34,25 @@ blueprint! {
             (badge, tokens, token_address)
         }
 
+        pub fn create_capped_fungible_and_mint(
+            max_supply: Decimal,
+            amount: Decimal,
+        ) -> (Bucket, Bucket, ResourceAddress) {
+            let badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let token_address = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_MAXIMUM)
+                .metadata("name", "TestToken")
+                .max_supply(max_supply)
+                .mintable(rule!(require(badge.resource_address())), LOCKED)
+                .no_initial_supply();
+            let resource_manager = borrow_resource_manager!(token_address);
+            assert_eq!(resource_manager.max_supply(), Some(max_supply));
+            let tokens = badge.authorize(|| resource_manager.mint(amount));
+            (badge, tokens, token_address)
+        }
+
         pub fn create_fungible_wrong_resource_flags_should_fail() -> ResourceAddress {
             let token_address = ResourceBuilder::new_fungible()
                 .divisibility(DIVISIBILITY_MAXIMUM)
@@ -1 +1,2 @@
 pub mod resource_creator;
+pub mod token_holder;
@@ -77,6 +77,7 @@ blueprint! {
         pub fn create_non_fungible_fixed() -> Bucket {
             ResourceBuilder::new_non_fungible()
                 .metadata("name", "Katz's Sandwiches")
+                .id_type(NonFungibleIdType::U32)
                 .initial_supply([
                     (
                         NonFungibleId::from_u32(1),
@@ -0,0 +1,40 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct TokenHolder {
+        vault: Vault,
+    }
+
+    impl TokenHolder {
+        /// Creates a holder of recallable and freezable tokens, returning the issuer badge.
+        pub fn new() -> (ComponentAddress, Bucket) {
+            let issuer_badge = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .initial_supply(1);
+            let tokens = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .recallable(rule!(require(issuer_badge.resource_address())), LOCKED)
+                .freezable(rule!(require(issuer_badge.resource_address())), LOCKED)
+                .initial_supply(100);
+
+            let component_address = Self {
+                vault: Vault::with_bucket(tokens),
+            }
+            .instantiate()
+            .globalize();
+            (component_address, issuer_badge)
+        }
+
+        pub fn withdraw(&mut self, amount: Decimal) -> Bucket {
+            self.vault.take(amount)
+        }
+
+        pub fn deposit(&mut self, bucket: Bucket) {
+            self.vault.put(bucket)
+        }
+
+        pub fn is_frozen(&self) -> bool {
+            self.vault.is_frozen()
+        }
+    }
+}
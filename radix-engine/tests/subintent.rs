@@ -0,0 +1,172 @@
+#[rustfmt::skip]
+pub mod test_runner;
+
+use crate::test_runner::TestRunner;
+use radix_engine::errors::{RuntimeError, TransactionValidationError};
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn can_swap_resources_through_subintent() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, alice_sk, alice_account) = test_runner.new_account();
+    let (bob_pk, bob_sk, bob_account) = test_runner.new_account();
+    let alice_token = test_runner.create_fungible_resource(100.into(), 0, alice_account);
+    let bob_token = test_runner.create_fungible_resource(100.into(), 0, bob_account);
+
+    // Alice offers 10 of her tokens in exchange for 20 of Bob's tokens
+    let subintent = TransactionBuilder::new()
+        .take_from_worktop(bob_token, |builder, bob_bucket_id| {
+            builder.call_method(
+                alice_account,
+                "deposit",
+                args![scrypto::resource::Bucket(bob_bucket_id)],
+            )
+        })
+        .assert_account_balance(
+            alice_account,
+            bob_token,
+            AmountCondition::AtLeast(20.into()),
+        )
+        .withdraw_from_account_by_amount(10.into(), alice_token, alice_account)
+        .take_from_worktop(alice_token, |builder, alice_bucket_id| {
+            builder.return_to_parent(vec![alice_bucket_id])
+        })
+        .build_subintent(0, 100);
+
+    // Act
+    let mut transaction = test_runner
+        .new_transaction_builder()
+        .withdraw_from_account_by_amount(20.into(), bob_token, bob_account)
+        .take_from_worktop(bob_token, |builder, bob_bucket_id| {
+            builder.yield_to_subintent(0, vec![bob_bucket_id])
+        })
+        .call_method_with_all_resources(bob_account, "deposit_batch")
+        .build(test_runner.get_nonce([bob_pk]));
+    let signed_subintent = subintent.sign(transaction.intent_hash(), [&alice_sk]);
+    transaction.subintents.push(signed_subintent);
+    let transaction = transaction.sign([&bob_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    receipt.result.expect("Should be okay");
+}
+
+#[test]
+fn subintent_cannot_withdraw_without_its_own_signature() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, _, alice_account) = test_runner.new_account();
+    let (bob_pk, bob_sk, bob_account) = test_runner.new_account();
+    let alice_token = test_runner.create_fungible_resource(100.into(), 0, alice_account);
+
+    // Subintent is only signed by Bob, who does not own Alice's account
+    let subintent = TransactionBuilder::new()
+        .withdraw_from_account(alice_token, alice_account)
+        .take_from_worktop(alice_token, |builder, bucket_id| {
+            builder.return_to_parent(vec![bucket_id])
+        })
+        .build_subintent(0, 100);
+
+    // Act
+    let mut transaction = test_runner
+        .new_transaction_builder()
+        .yield_to_subintent(0, Vec::new())
+        .call_method_with_all_resources(bob_account, "deposit_batch")
+        .build(test_runner.get_nonce([bob_pk]));
+    let signed_subintent = subintent.sign(transaction.intent_hash(), [&bob_sk]);
+    transaction.subintents.push(signed_subintent);
+    let transaction = transaction.sign([&bob_sk]);
+    let receipt = test_runner.validate_and_execute(&transaction);
+
+    // Assert
+    let error = receipt.result.expect_err("Should be an error");
+    assert_auth_error!(error);
+}
+
+#[test]
+fn subintent_must_be_yielded_to() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, alice_sk) = test_runner.new_key_pair();
+    let subintent = TransactionBuilder::new()
+        .return_to_parent(Vec::new())
+        .build_subintent(0, 100);
+    let mut transaction = test_runner.new_transaction_builder().build(0);
+    let signed_subintent = subintent.sign(transaction.intent_hash(), [&alice_sk]);
+    transaction.subintents.push(signed_subintent);
+
+    // Act
+    let result = transaction.sign([]).validate();
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::SubintentNotYielded(0))
+    ));
+}
+
+#[test]
+fn subintent_cannot_be_replayed_in_another_transaction() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, alice_sk) = test_runner.new_key_pair();
+    let subintent = TransactionBuilder::new()
+        .return_to_parent(Vec::new())
+        .build_subintent(0, 100);
+    let mut transaction = test_runner
+        .new_transaction_builder()
+        .yield_to_subintent(0, Vec::new())
+        .build(0);
+    let signed_subintent = subintent.sign(transaction.intent_hash(), [&alice_sk]);
+    transaction.subintents.push(signed_subintent.clone());
+
+    // Act
+    let mut other_transaction = test_runner
+        .new_transaction_builder()
+        .yield_to_subintent(0, Vec::new())
+        .build(1);
+    other_transaction.subintents.push(signed_subintent);
+    let result = other_transaction.sign([]).validate();
+
+    // Assert
+    assert!(transaction.sign([]).validate().is_ok());
+    assert_eq!(
+        result.err(),
+        Some(TransactionValidationError::InvalidSignature)
+    );
+}
+
+#[test]
+fn expired_subintent_cannot_be_yielded_to() {
+    // Arrange
+    let mut substate_store = InMemorySubstateStore::with_bootstrap();
+    substate_store.set_epoch(101);
+    let mut test_runner = TestRunner::new(&mut substate_store);
+    let (_, alice_sk) = test_runner.new_key_pair();
+    let subintent = TransactionBuilder::new()
+        .return_to_parent(Vec::new())
+        .build_subintent(0, 100);
+    let mut transaction = test_runner
+        .new_transaction_builder()
+        .yield_to_subintent(0, Vec::new())
+        .build(test_runner.get_nonce([]));
+    let signed_subintent = subintent.sign(transaction.intent_hash(), [&alice_sk]);
+    transaction.subintents.push(signed_subintent);
+
+    // Act
+    let receipt = test_runner.validate_and_execute(&transaction.sign([]));
+
+    // Assert
+    assert_eq!(
+        receipt.result.expect_err("Should be an error"),
+        RuntimeError::SubintentExpired(0)
+    );
+}
@@ -269,7 +269,8 @@ macro_rules! assert_auth_error {
             RuntimeError::AuthorizationError {
                 authorization: _,
                 function: _,
-                error: ::radix_engine::model::MethodAuthorizationError::NotAuthorized
+                error: ::radix_engine::model::MethodAuthorizationError::NotAuthorized,
+                trace: _
             }
         ) {
             panic!("Expected auth error but got: {:?}", $error);
@@ -1,6 +1,7 @@
 use radix_engine::errors::ResourceFailure;
 use radix_engine::errors::RuntimeError;
 use radix_engine::ledger::*;
+use radix_engine::model::VaultError;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -144,7 +145,12 @@ fn dangling_vault_should_fail() {
 
     // Assert
     let runtime_error = receipt.result.expect_err("Should be runtime error");
-    assert_eq!(runtime_error, RuntimeError::ResourceCheckFailure(ResourceFailure::Resource(receipt.new_resource_addresses[0])));
+    assert_eq!(
+        runtime_error,
+        RuntimeError::ResourceCheckFailure(ResourceFailure::Resource(
+            receipt.new_resource_addresses[0]
+        ))
+    );
 }
 
 #[test]
@@ -436,3 +442,154 @@ fn create_mutable_vault_with_get_resource_manager() {
     // Assert
     receipt.result.expect("Should be okay");
 }
+
+fn take_from_time_locked_vault(
+    constructor: &str,
+    args: Vec<Vec<u8>>,
+    epoch: u64,
+    amount: Decimal,
+) -> Result<(), RuntimeError> {
+    call_time_locked_vault(constructor, args, epoch, "take", args![amount])
+}
+
+fn call_time_locked_vault(
+    constructor: &str,
+    args: Vec<Vec<u8>>,
+    epoch: u64,
+    method: &str,
+    method_args: Vec<Vec<u8>>,
+) -> Result<(), RuntimeError> {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, true);
+    let (pk, sk, account) = executor.new_account();
+    let package = executor
+        .publish_package(&compile_package!(format!("./tests/{}", "vault")))
+        .unwrap();
+    let transaction = TransactionBuilder::new()
+        .call_function(package, "TimeLockTest", constructor, args)
+        .build(executor.get_nonce([]))
+        .sign([]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    let component_address = receipt.new_component_addresses[0];
+    executor.substate_store_mut().set_epoch(epoch);
+
+    // Act
+    let transaction = TransactionBuilder::new()
+        .call_method(component_address, method, method_args)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(executor.get_nonce([pk]))
+        .sign([&sk]);
+    let receipt = executor.validate_and_execute(&transaction).unwrap();
+    receipt.result
+}
+
+#[test]
+fn take_unlocked_amount_from_time_locked_vault_should_succeed() {
+    let result = take_from_time_locked_vault("new_locked", args![dec!("60"), 10u64], 0, dec!("40"));
+
+    result.expect("Should be okay");
+}
+
+#[test]
+fn take_locked_amount_from_time_locked_vault_should_fail() {
+    let result = take_from_time_locked_vault("new_locked", args![dec!("60"), 10u64], 9, dec!("41"));
+
+    let runtime_error = result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::VaultError(VaultError::ResourceTimeLocked)
+    );
+}
+
+#[test]
+fn take_from_vault_after_time_lock_expired_should_succeed() {
+    let result =
+        take_from_time_locked_vault("new_locked", args![dec!("60"), 10u64], 10, dec!("100"));
+
+    result.expect("Should be okay");
+}
+
+#[test]
+fn take_vested_amount_from_vault_should_succeed() {
+    let result = take_from_time_locked_vault(
+        "new_vesting",
+        args![dec!("100"), 0u64, 10u64],
+        5,
+        dec!("50"),
+    );
+
+    result.expect("Should be okay");
+}
+
+#[test]
+fn take_unvested_amount_from_vault_should_fail() {
+    let result = take_from_time_locked_vault(
+        "new_vesting",
+        args![dec!("100"), 0u64, 10u64],
+        5,
+        dec!("51"),
+    );
+
+    let runtime_error = result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::VaultError(VaultError::ResourceTimeLocked)
+    );
+}
+
+#[test]
+fn take_unlocked_non_fungibles_from_time_locked_vault_should_succeed() {
+    let result =
+        take_from_time_locked_vault("new_locked_non_fungibles", args![10u64], 0, dec!("1"));
+    result.expect("Should be okay");
+
+    let result = call_time_locked_vault(
+        "new_locked_non_fungibles",
+        args![10u64],
+        0,
+        "take_non_fungibles",
+        args![BTreeSet::from([NonFungibleId::from_u32(3)])],
+    );
+    result.expect("Should be okay");
+}
+
+#[test]
+fn take_locked_non_fungibles_from_time_locked_vault_should_fail() {
+    let result =
+        take_from_time_locked_vault("new_locked_non_fungibles", args![10u64], 9, dec!("2"));
+    let runtime_error = result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::VaultError(VaultError::ResourceTimeLocked)
+    );
+
+    let result = call_time_locked_vault(
+        "new_locked_non_fungibles",
+        args![10u64],
+        9,
+        "take_non_fungibles",
+        args![BTreeSet::from([NonFungibleId::from_u32(1)])],
+    );
+    let runtime_error = result.expect_err("Should be runtime error");
+    assert_eq!(
+        runtime_error,
+        RuntimeError::VaultError(VaultError::ResourceTimeLocked)
+    );
+}
+
+#[test]
+fn take_non_fungibles_after_time_lock_expired_should_succeed() {
+    let result =
+        take_from_time_locked_vault("new_locked_non_fungibles", args![10u64], 10, dec!("3"));
+    result.expect("Should be okay");
+
+    let result = call_time_locked_vault(
+        "new_locked_non_fungibles",
+        args![10u64],
+        10,
+        "take_non_fungibles",
+        args![BTreeSet::from([NonFungibleId::from_u32(1)])],
+    );
+    result.expect("Should be okay");
+}
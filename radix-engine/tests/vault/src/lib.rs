@@ -1,2 +1,3 @@
 pub mod non_existent_vault;
+pub mod time_lock;
 pub mod vault;
@@ -0,0 +1,60 @@
+use scrypto::prelude::*;
+
+#[derive(NonFungibleData)]
+pub struct TimeLockData {}
+
+blueprint! {
+    struct TimeLockTest {
+        vault: Vault,
+    }
+
+    impl TimeLockTest {
+        fn new_vault() -> Vault {
+            let bucket = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata("name", "TestToken")
+                .initial_supply(100);
+            Vault::with_bucket(bucket)
+        }
+
+        pub fn new_locked(amount: Decimal, epoch: u64) -> ComponentAddress {
+            let mut vault = Self::new_vault();
+            vault.lock_until(amount, epoch);
+            assert_eq!(vault.time_locked_amount(), amount);
+            Self { vault }.instantiate().globalize()
+        }
+
+        pub fn new_vesting(amount: Decimal, start_epoch: u64, end_epoch: u64) -> ComponentAddress {
+            let mut vault = Self::new_vault();
+            vault.vesting_schedule(amount, start_epoch, end_epoch);
+            Self { vault }.instantiate().globalize()
+        }
+
+        pub fn new_locked_non_fungibles(epoch: u64) -> ComponentAddress {
+            let bucket = ResourceBuilder::new_non_fungible(NonFungibleIdType::U32)
+                .metadata("name", "TestToken")
+                .initial_supply([
+                    (NonFungibleId::from_u32(1), TimeLockData {}),
+                    (NonFungibleId::from_u32(2), TimeLockData {}),
+                    (NonFungibleId::from_u32(3), TimeLockData {}),
+                ]);
+            let mut vault = Vault::with_bucket(bucket);
+            vault.lock_non_fungibles_until(
+                &BTreeSet::from([NonFungibleId::from_u32(1), NonFungibleId::from_u32(2)]),
+                epoch,
+            );
+            assert_eq!(vault.time_locked_amount(), dec!("2"));
+            Self { vault }.instantiate().globalize()
+        }
+
+        pub fn take(&mut self, amount: Decimal) -> Bucket {
+            self.vault.take(amount)
+        }
+
+        pub fn take_non_fungibles(&mut self, ids: BTreeSet<NonFungibleId>) -> Bucket {
+            let bucket = self.vault.take_non_fungibles(&ids);
+            assert_eq!(bucket.non_fungible_ids(), ids);
+            bucket
+        }
+    }
+}
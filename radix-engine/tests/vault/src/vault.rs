@@ -127,6 +127,7 @@ blueprint! {
         fn create_non_fungible_vault() -> Vault {
             let bucket = ResourceBuilder::new_non_fungible()
                 .metadata("name", "TestToken")
+                .id_type(NonFungibleIdType::U32)
                 .initial_supply([(NonFungibleId::from_u32(1), Data {})]);
             Vault::with_bucket(bucket)
         }
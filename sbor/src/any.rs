@@ -285,10 +285,15 @@ fn encode_basic<T: Encode>(ty_ctx: Option<u8>, t: u8, v: &T, enc: &mut Encoder)
 
 /// Decode any SBOR data.
 pub fn decode_any(data: &[u8]) -> Result<Value, DecodeError> {
-    let mut decoder = Decoder::with_type(data);
-    let result = decode_next(None, &mut decoder);
+    decode_any_with_max_depth(data, usize::MAX)
+}
+
+/// Decode any SBOR data, with values nested at most `max_depth` levels deep.
+pub fn decode_any_with_max_depth(data: &[u8], max_depth: usize) -> Result<Value, DecodeError> {
+    let mut decoder = Decoder::with_type(data).with_max_depth(max_depth);
+    let result = decode_next(None, &mut decoder)?;
     decoder.check_end()?;
-    result
+    Ok(result)
 }
 
 fn decode_next(ty_ctx: Option<u8>, dec: &mut Decoder) -> Result<Value, DecodeError> {
@@ -297,6 +302,13 @@ fn decode_next(ty_ctx: Option<u8>, dec: &mut Decoder) -> Result<Value, DecodeErr
         None => dec.read_type()?,
     };
 
+    dec.enter()?;
+    let result = decode_next_value(ty, dec);
+    dec.exit();
+    result
+}
+
+fn decode_next_value(ty: u8, dec: &mut Decoder) -> Result<Value, DecodeError> {
     match ty {
         // primitive types
         TYPE_UNIT => Ok(Value::Unit),
@@ -768,4 +780,24 @@ mod tests {
             value
         );
     }
+
+    #[test]
+    pub fn test_max_depth() {
+        let mut bytes = Vec::new();
+        for _ in 0..10000 {
+            bytes.extend([TYPE_OPTION, 1]);
+        }
+        bytes.push(TYPE_UNIT);
+
+        assert_eq!(
+            decode_any_with_max_depth(&bytes, 64),
+            Err(DecodeError::MaxDepthExceeded(64))
+        );
+        assert_eq!(
+            decode_any_with_max_depth(&bytes[19998..], 2),
+            Ok(Value::Option {
+                value: Box::new(Some(Value::Unit))
+            })
+        );
+    }
 }
@@ -33,6 +33,8 @@ pub enum DecodeError {
     InvalidCustomData(u8),
 
     DuplicateEntry,
+
+    MaxDepthExceeded(usize),
 }
 
 /// A data structure that can be decoded from a byte array using SBOR.
@@ -40,7 +42,10 @@ pub trait Decode: Sized + TypeId {
     #[inline]
     fn decode(decoder: &mut Decoder) -> Result<Self, DecodeError> {
         decoder.check_type(Self::type_id())?;
-        Self::decode_value(decoder)
+        decoder.enter()?;
+        let result = Self::decode_value(decoder);
+        decoder.exit();
+        result
     }
 
     fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError>;
@@ -51,6 +56,8 @@ pub struct Decoder<'de> {
     input: &'de [u8],
     offset: usize,
     with_type: bool,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl<'de> Decoder<'de> {
@@ -59,9 +66,33 @@ impl<'de> Decoder<'de> {
             input,
             offset: 0,
             with_type,
+            depth: 0,
+            max_depth: usize::MAX,
         }
     }
 
+    /// Sets the maximum nesting depth, beyond which decoding fails rather than recursing further.
+    ///
+    /// By default, the nesting depth is not limited.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Enters a nested value.
+    pub fn enter(&mut self) -> Result<(), DecodeError> {
+        if self.depth >= self.max_depth {
+            return Err(DecodeError::MaxDepthExceeded(self.max_depth));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Leaves a nested value.
+    pub fn exit(&mut self) {
+        self.depth -= 1;
+    }
+
     pub fn with_type(input: &'de [u8]) -> Self {
         Self::new(input, true)
     }
@@ -559,4 +590,17 @@ mod tests {
         let value2 = <[NFA; 2]>::decode(&mut dec).unwrap();
         assert_eq!(value1, value2);
     }
+
+    #[test]
+    pub fn test_max_depth() {
+        let bytes = vec![32u8, 1, 32, 1, 7, 5]; // Some(Some(5u8))
+
+        let mut dec = Decoder::with_type(&bytes).with_max_depth(3);
+        let x = <Option<Option<u8>>>::decode(&mut dec).unwrap();
+        assert_eq!(Some(Some(5u8)), x);
+
+        let mut dec = Decoder::with_type(&bytes).with_max_depth(2);
+        let x = <Option<Option<u8>>>::decode(&mut dec);
+        assert_eq!(Err(DecodeError::MaxDepthExceeded(2)), x);
+    }
 }
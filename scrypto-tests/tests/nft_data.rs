@@ -2,6 +2,7 @@
 
 use sbor::describe::*;
 use scrypto::prelude::*;
+use scrypto::values::ScryptoValue;
 
 #[derive(NonFungibleData, Debug, Eq, PartialEq)]
 pub struct Sample {
@@ -42,3 +43,28 @@ fn test_non_fungible_data() {
         }
     );
 }
+
+#[derive(NonFungibleData)]
+pub struct Other {
+    pub a: String,
+    #[scrypto(mutable)]
+    pub b: String,
+}
+
+#[test]
+fn test_non_fungible_data_schema() {
+    let instance = Sample {
+        a: 1,
+        b: "Test".to_owned(),
+    };
+    let schema = NonFungibleDataSchema::new::<Sample>();
+    let other_schema = NonFungibleDataSchema::new::<Other>();
+
+    let immutable_data = ScryptoValue::from_slice(&instance.immutable_data()).unwrap();
+    let mutable_data = ScryptoValue::from_slice(&instance.mutable_data()).unwrap();
+    assert!(immutable_data.matches_schema(&schema.immutable));
+    assert!(mutable_data.matches_schema(&schema.mutable));
+    assert!(!immutable_data.matches_schema(&other_schema.immutable));
+    assert!(mutable_data.matches_schema(&other_schema.mutable));
+    assert!(!mutable_data.matches_schema(&schema.immutable));
+}
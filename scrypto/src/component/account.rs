@@ -0,0 +1,135 @@
+use sbor::*;
+
+use crate::rust::borrow::ToOwned;
+use crate::rust::fmt;
+use crate::rust::str::FromStr;
+use crate::rust::string::String;
+
+/// Controls which resources an account accepts as deposits, when they have no deposit rule.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, Describe, PartialEq, Eq)]
+pub enum AccountDepositMode {
+    /// Accepts all resources, except the ones explicitly denied.
+    AcceptAll,
+    /// Accepts only the resources explicitly allowed.
+    AllowList,
+    /// Accepts all resources, except the ones explicitly denied.
+    ///
+    /// As deposit rules apply in every mode, this is the same as `AcceptAll`.
+    DenyList,
+}
+
+/// Overrides an account's deposit mode for a specific resource, in every mode.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, Describe, PartialEq, Eq)]
+pub enum ResourceDepositRule {
+    Allow,
+    Deny,
+}
+
+impl AccountDepositMode {
+    /// Checks if a resource with the given deposit rule is accepted under this mode.
+    pub fn accepts(&self, rule: Option<ResourceDepositRule>) -> bool {
+        match rule {
+            Some(ResourceDepositRule::Allow) => true,
+            Some(ResourceDepositRule::Deny) => false,
+            None => *self != Self::AllowList,
+        }
+    }
+}
+
+//========
+// error
+//========
+
+/// Represents an error when parsing account deposit mode or resource deposit rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseAccountDepositError {
+    InvalidDepositMode(String),
+    InvalidDepositRule(String),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseAccountDepositError {}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Display for ParseAccountDepositError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+//======
+// text
+//======
+
+impl FromStr for AccountDepositMode {
+    type Err = ParseAccountDepositError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "accept-all" => Ok(Self::AcceptAll),
+            "allow-list" => Ok(Self::AllowList),
+            "deny-list" => Ok(Self::DenyList),
+            _ => Err(ParseAccountDepositError::InvalidDepositMode(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for AccountDepositMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AcceptAll => write!(f, "accept-all"),
+            Self::AllowList => write!(f, "allow-list"),
+            Self::DenyList => write!(f, "deny-list"),
+        }
+    }
+}
+
+impl FromStr for ResourceDepositRule {
+    type Err = ParseAccountDepositError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            _ => Err(ParseAccountDepositError::InvalidDepositRule(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ResourceDepositRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deposit_mode_accepts() {
+        assert!(AccountDepositMode::AcceptAll.accepts(None));
+        assert!(!AccountDepositMode::AcceptAll.accepts(Some(ResourceDepositRule::Deny)));
+        assert!(AccountDepositMode::AllowList.accepts(Some(ResourceDepositRule::Allow)));
+        assert!(!AccountDepositMode::AllowList.accepts(Some(ResourceDepositRule::Deny)));
+        assert!(!AccountDepositMode::AllowList.accepts(None));
+        assert!(AccountDepositMode::DenyList.accepts(None));
+        assert!(!AccountDepositMode::DenyList.accepts(Some(ResourceDepositRule::Deny)));
+    }
+
+    #[test]
+    fn test_deposit_mode_from_str() {
+        assert_eq!(
+            AccountDepositMode::from_str("allow-list"),
+            Ok(AccountDepositMode::AllowList)
+        );
+        assert_eq!(
+            ResourceDepositRule::from_str("deny"),
+            Ok(ResourceDepositRule::Deny)
+        );
+        assert!(AccountDepositMode::from_str("allow").is_err());
+    }
+}
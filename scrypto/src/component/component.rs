@@ -17,6 +17,7 @@ pub struct LocalComponent {
     blueprint_name: String,
     state: Vec<u8>,
     access_rules_list: Vec<AccessRules>,
+    fail_on_unresolved_access_rules: bool,
 }
 
 impl LocalComponent {
@@ -25,6 +26,7 @@ impl LocalComponent {
             blueprint_name,
             state,
             access_rules_list: Vec::new(),
+            fail_on_unresolved_access_rules: false,
         }
     }
 
@@ -33,11 +35,19 @@ impl LocalComponent {
         self
     }
 
+    /// Makes `globalize` fail if a dynamic access rule path can't be resolved against the
+    /// component state, rather than the rule silently denying access.
+    pub fn fail_on_unresolved_access_rules(mut self) -> Self {
+        self.fail_on_unresolved_access_rules = true;
+        self
+    }
+
     pub fn globalize(self) -> ComponentAddress {
         let input = CreateComponentInput {
             blueprint_name: self.blueprint_name,
             state: self.state,
             access_rules_list: self.access_rules_list,
+            fail_on_unresolved_access_rules: self.fail_on_unresolved_access_rules,
         };
         let output: CreateComponentOutput = call_engine(CREATE_COMPONENT, input);
         output.component_address
@@ -1,8 +1,10 @@
+mod account;
 mod component;
 mod lazy_map;
 mod package;
 mod system;
 
+pub use account::{AccountDepositMode, ParseAccountDepositError, ResourceDepositRule};
 pub use component::{
     Component, ComponentAddress, ComponentState, LocalComponent, ParseComponentAddressError,
 };
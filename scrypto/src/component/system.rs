@@ -67,6 +67,7 @@ impl ComponentSystem {
             blueprint_name: blueprint_name.to_owned(),
             state: scrypto_encode(&state),
             access_rules_list: authorization,
+            fail_on_unresolved_access_rules: false,
         };
         let output: CreateComponentOutput = call_engine(CREATE_COMPONENT, input);
 
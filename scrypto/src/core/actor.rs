@@ -3,14 +3,14 @@ use sbor::*;
 use crate::component::*;
 use crate::rust::string::String;
 
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
 pub enum ScryptoActor {
     Blueprint(PackageAddress, String),
     Component(ComponentAddress),
 }
 
 /// Represents the running entity.
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
 pub struct ScryptoActorInfo {
     package_address: PackageAddress,
     blueprint_name: String,
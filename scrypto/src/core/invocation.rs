@@ -4,7 +4,7 @@ use crate::resource::ResourceAddress;
 use crate::rust::string::ToString;
 use sbor::*;
 
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
 pub enum SNodeRef {
     PackageStatic,
     AuthZoneRef,
@@ -2,6 +2,7 @@ use crate::component::*;
 use crate::core::*;
 use crate::crypto::*;
 use crate::engine::{api::*, call_engine};
+use crate::resource::Proof;
 use crate::rust::borrow::ToOwned;
 use crate::rust::vec::Vec;
 
@@ -69,6 +70,27 @@ impl Runtime {
         output.rtn
     }
 
+    /// Invokes a method on a component, lending it the given proofs for the duration of the call.
+    ///
+    /// The lent proofs authorize the call itself and the calls made by the method, but the
+    /// method can't access them otherwise. They remain owned by the caller.
+    pub fn call_method_with_proofs<S: AsRef<str>>(
+        component_address: ComponentAddress,
+        method: S,
+        args: Vec<Vec<u8>>,
+        proofs: &[Proof],
+    ) -> Vec<u8> {
+        let input = InvokeSNodeWithProofsInput {
+            snode_ref: SNodeRef::Scrypto(ScryptoActor::Component(component_address)),
+            function: method.as_ref().to_owned(),
+            args,
+            proof_ids: proofs.iter().map(|proof| proof.0).collect(),
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE_WITH_PROOFS, input);
+
+        output.rtn
+    }
+
     /// Returns the transaction hash.
     pub fn transaction_hash() -> Hash {
         let input = GetTransactionHashInput {};
@@ -32,6 +32,8 @@ pub const PUT_LAZY_MAP_ENTRY: u32 = 0x22;
 pub const CREATE_EMPTY_VAULT: u32 = 0x40;
 
 pub const INVOKE_SNODE: u32 = 0x70;
+/// Invoke an snode, lending it some proofs for authorization
+pub const INVOKE_SNODE_WITH_PROOFS: u32 = 0x71;
 
 /// Log a message
 pub const EMIT_LOG: u32 = 0xf0;
@@ -48,6 +50,8 @@ pub const GET_ACTOR: u32 = 0xf5;
 
 /// Check that an access rule is satisfied
 pub const CHECK_ACCESS_RULE: u32 = 0xf6;
+/// Report the message of a panic, before the blueprint traps
+pub const REPORT_PANIC: u32 = 0xf7;
 
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct InvokeSNodeInput {
@@ -56,6 +60,14 @@ pub struct InvokeSNodeInput {
     pub args: Vec<Vec<u8>>,
 }
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct InvokeSNodeWithProofsInput {
+    pub snode_ref: SNodeRef,
+    pub function: String,
+    pub args: Vec<Vec<u8>>,
+    pub proof_ids: Vec<ProofId>,
+}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct InvokeSNodeOutput {
     pub rtn: Vec<u8>,
@@ -65,11 +77,35 @@ pub struct InvokeSNodeOutput {
 // component
 //==========
 
-#[derive(Debug, TypeId, Encode, Decode)]
+#[derive(Debug, TypeId, Encode)]
 pub struct CreateComponentInput {
     pub blueprint_name: String,
     pub state: Vec<u8>,
     pub access_rules_list: Vec<AccessRules>,
+    pub fail_on_unresolved_access_rules: bool,
+}
+
+impl Decode for CreateComponentInput {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        // Packages built against older Scrypto versions don't pass the last field
+        let len = decoder.read_len()?;
+        if len != 3 && len != 4 {
+            return Err(DecodeError::InvalidLength {
+                expected: 4,
+                actual: len,
+            });
+        }
+        Ok(Self {
+            blueprint_name: String::decode(decoder)?,
+            state: Vec::<u8>::decode(decoder)?,
+            access_rules_list: Vec::<AccessRules>::decode(decoder)?,
+            fail_on_unresolved_access_rules: if len == 4 {
+                bool::decode(decoder)?
+            } else {
+                false
+            },
+        })
+    }
 }
 
 #[derive(Debug, TypeId, Encode, Decode)]
@@ -164,6 +200,14 @@ pub struct EmitLogInput {
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct EmitLogOutput {}
 
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ReportPanicInput {
+    pub message: String,
+}
+
+#[derive(Debug, TypeId, Encode, Decode)]
+pub struct ReportPanicOutput {}
+
 #[derive(Debug, TypeId, Encode, Decode)]
 pub struct GetCallDataInput {}
 
@@ -218,3 +262,29 @@ pub struct CheckAccessRuleInput {
 pub struct CheckAccessRuleOutput {
     pub is_authorized: bool
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{scrypto_decode, scrypto_encode};
+
+    #[derive(TypeId, Encode)]
+    struct LegacyCreateComponentInput {
+        blueprint_name: String,
+        state: Vec<u8>,
+        access_rules_list: Vec<AccessRules>,
+    }
+
+    #[test]
+    fn test_decode_create_component_input_without_fail_on_unresolved_access_rules() {
+        let legacy = LegacyCreateComponentInput {
+            blueprint_name: "Account".to_owned(),
+            state: vec![1, 2, 3],
+            access_rules_list: Vec::new(),
+        };
+        let input: CreateComponentInput = scrypto_decode(&scrypto_encode(&legacy)).unwrap();
+        assert_eq!(input.blueprint_name, "Account");
+        assert_eq!(input.state, vec![1, 2, 3]);
+        assert!(!input.fail_on_unresolved_access_rules);
+    }
+}
@@ -33,6 +33,9 @@ pub fn call_engine<T: Encode, V: Decode>(op: u32, input: T) -> V {
         println!("{}", input_value.message);
         let output_bytes = scrypto_encode(&EmitLogOutput {});
         scrypto_decode::<V>(&output_bytes).unwrap()
+    } else if op == REPORT_PANIC {
+        let output_bytes = scrypto_encode(&ReportPanicOutput {});
+        scrypto_decode::<V>(&output_bytes).unwrap()
     } else {
         todo!()
     }
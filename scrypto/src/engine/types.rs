@@ -13,6 +13,7 @@ pub use crate::math::Decimal;
 pub use crate::resource::MintParams;
 pub use crate::resource::NonFungibleAddress;
 pub use crate::resource::NonFungibleId;
+pub use crate::resource::NonFungibleIdType;
 pub use crate::resource::ResourceAddress;
 pub use crate::resource::ResourceType;
 
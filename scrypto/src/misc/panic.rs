@@ -25,5 +25,10 @@ pub fn set_up_panic_hook() {
             payload,
             location
         ));
+
+        // forward the message, so that the engine can report it with the trap
+        let input = crate::engine::api::ReportPanicInput { message: payload };
+        let _: crate::engine::api::ReportPanicOutput =
+            crate::engine::call_engine(crate::engine::api::REPORT_PANIC, input);
     }));
 }
@@ -42,7 +42,7 @@ impl MintParams {
             Self::Fungible { .. } => {
                 matches!(resource_type, ResourceType::Fungible { .. })
             }
-            Self::NonFungible { .. } => matches!(resource_type, ResourceType::NonFungible),
+            Self::NonFungible { .. } => matches!(resource_type, ResourceType::NonFungible { .. }),
         }
     }
 
@@ -21,12 +21,12 @@ pub use bucket::{Bucket, ParseBucketError};
 pub use mint_params::MintParams;
 pub use non_fungible::NonFungible;
 pub use non_fungible_address::{NonFungibleAddress, ParseNonFungibleAddressError};
-pub use non_fungible_data::NonFungibleData;
-pub use non_fungible_id::{NonFungibleId, ParseNonFungibleIdError};
+pub use non_fungible_data::{NonFungibleData, NonFungibleDataSchema};
+pub use non_fungible_id::{NonFungibleId, NonFungibleIdType, ParseNonFungibleIdError};
 pub use proof::{ParseProofError, Proof};
 pub use proof_rule::{
-    require, require_all_of, require_amount, require_any_of, require_n_of, AccessRuleNode,
-    AccessRule, ProofRule, SoftCount, SoftDecimal, SoftResource, SoftResourceOrNonFungible,
+    require, require_all_of, require_amount, require_any_of, require_caller, require_n_of,
+    AccessRuleNode, AccessRule, CallerRequirement, ProofRule, SoftCount, SoftDecimal, SoftResource, SoftResourceOrNonFungible,
     SoftResourceOrNonFungibleList,
 };
 pub use resource_builder::{ResourceBuilder, DIVISIBILITY_MAXIMUM, DIVISIBILITY_NONE};
@@ -21,3 +21,20 @@ pub trait NonFungibleData {
     /// Returns the schema of the mutable data.
     fn mutable_data_schema() -> Type;
 }
+
+/// Represents the schema of the immutable and mutable data of a non-fungible resource.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct NonFungibleDataSchema {
+    pub immutable: Type,
+    pub mutable: Type,
+}
+
+impl NonFungibleDataSchema {
+    /// Creates the schema of a `NonFungibleData` type.
+    pub fn new<T: NonFungibleData>() -> Self {
+        Self {
+            immutable: T::immutable_data_schema(),
+            mutable: T::mutable_data_schema(),
+        }
+    }
+}
@@ -1,36 +1,155 @@
+use sbor::type_id::*;
 use sbor::*;
 
 use crate::rust::borrow::ToOwned;
 use crate::rust::fmt;
+use crate::rust::format;
 use crate::rust::str::FromStr;
 use crate::rust::string::String;
 use crate::rust::string::ToString;
 use crate::rust::vec::Vec;
 use crate::types::*;
 
-/// Represents a key for a non-fungible resource
+/// Represents the type of non-fungible IDs a resource accepts.
+#[derive(Debug, Clone, Copy, TypeId, Encode, Decode, Describe, PartialEq, Eq, Hash)]
+pub enum NonFungibleIdType {
+    /// IDs are `u32` numbers.
+    U32,
+    /// IDs are `u64` numbers.
+    U64,
+    /// IDs are `u128` UUIDs.
+    UUID,
+    /// IDs are arbitrary byte arrays.
+    Bytes,
+    /// IDs are strings.
+    String,
+}
+
+/// Represents a key for a non-fungible resource.
+///
+/// The ID is stored as the SBOR encoding (with type) of its value, so IDs of different types never collide.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NonFungibleId(Vec<u8>);
 
 impl NonFungibleId {
     /// Creates a non-fungible ID from some uuid.
     pub fn random() -> Self {
-        Self(crate::core::Runtime::generate_uuid().to_be_bytes().to_vec())
+        Self::from_uuid(crate::core::Runtime::generate_uuid())
+    }
+
+    /// Creates a non-fungible ID from a `u128` UUID.
+    pub fn from_uuid(u: u128) -> Self {
+        Self(encode_with_type(&u))
     }
 
     /// Creates a non-fungible ID from an arbitrary byte array.
     pub fn from_bytes(v: Vec<u8>) -> Self {
-        Self(v)
+        Self(encode_with_type(&v))
     }
 
     /// Creates a non-fungible ID from a `u32` number.
     pub fn from_u32(u: u32) -> Self {
-        Self(u.to_be_bytes().to_vec())
+        Self(encode_with_type(&u))
     }
 
     /// Creates a non-fungible ID from a `u64` number.
     pub fn from_u64(u: u64) -> Self {
-        Self(u.to_be_bytes().to_vec())
+        Self(encode_with_type(&u))
+    }
+
+    /// Creates a non-fungible ID from a string.
+    pub fn from_string(s: &str) -> Self {
+        Self(encode_with_type(s))
+    }
+
+    /// Returns the type of this non-fungible ID.
+    pub fn id_type(&self) -> NonFungibleIdType {
+        Self::id_type_of(&self.0).expect("Non-fungible ID is validated on construction")
+    }
+
+    /// Returns the ID in its natural form, e.g. `5u32` or `"name"`, as used in transaction manifests.
+    pub fn to_natural_string(&self) -> String {
+        match self.id_type() {
+            NonFungibleIdType::U32 => format!("{}u32", decode_with_type::<u32>(&self.0).unwrap()),
+            NonFungibleIdType::U64 => format!("{}u64", decode_with_type::<u64>(&self.0).unwrap()),
+            NonFungibleIdType::UUID => {
+                format!("{}u128", decode_with_type::<u128>(&self.0).unwrap())
+            }
+            NonFungibleIdType::Bytes => format!(
+                "Bytes(\"{}\")",
+                hex::encode(decode_with_type::<Vec<u8>>(&self.0).unwrap())
+            ),
+            NonFungibleIdType::String => {
+                let s = decode_with_type::<String>(&self.0).unwrap();
+                format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        }
+    }
+
+    /// Parses an ID in its natural form, as returned by `to_natural_string`.
+    pub fn from_natural_str(s: &str) -> Result<Self, ParseNonFungibleIdError> {
+        let invalid = || ParseNonFungibleIdError::InvalidNaturalForm(s.to_owned());
+        if let Some(hex) = s
+            .strip_prefix("Bytes(\"")
+            .and_then(|s| s.strip_suffix("\")"))
+        {
+            hex::decode(hex)
+                .map(Self::from_bytes)
+                .map_err(|_| ParseNonFungibleIdError::InvalidHex(hex.to_owned()))
+        } else if let Some(escaped) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            let mut unescaped = String::new();
+            let mut chars = escaped.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => match chars.next() {
+                        Some(c @ ('\\' | '"')) => unescaped.push(c),
+                        _ => return Err(invalid()),
+                    },
+                    '"' => return Err(invalid()),
+                    c => unescaped.push(c),
+                }
+            }
+            Ok(Self::from_string(&unescaped))
+        } else if let Some(n) = s.strip_suffix("u32") {
+            n.parse().map(Self::from_u32).map_err(|_| invalid())
+        } else if let Some(n) = s.strip_suffix("u64") {
+            n.parse().map(Self::from_u64).map_err(|_| invalid())
+        } else if let Some(n) = s.strip_suffix("u128") {
+            n.parse().map(Self::from_uuid).map_err(|_| invalid())
+        } else {
+            Err(invalid())
+        }
+    }
+
+    fn id_type_of(slice: &[u8]) -> Result<NonFungibleIdType, ParseNonFungibleIdError> {
+        let (id_type, valid) = match slice.first() {
+            Some(&TYPE_U32) => (
+                NonFungibleIdType::U32,
+                decode_with_type::<u32>(slice).is_ok(),
+            ),
+            Some(&TYPE_U64) => (
+                NonFungibleIdType::U64,
+                decode_with_type::<u64>(slice).is_ok(),
+            ),
+            Some(&TYPE_U128) => (
+                NonFungibleIdType::UUID,
+                decode_with_type::<u128>(slice).is_ok(),
+            ),
+            Some(&TYPE_VEC) => (
+                NonFungibleIdType::Bytes,
+                decode_with_type::<Vec<u8>>(slice).is_ok(),
+            ),
+            Some(&TYPE_STRING) => (
+                NonFungibleIdType::String,
+                decode_with_type::<String>(slice).is_ok(),
+            ),
+            _ => return Err(ParseNonFungibleIdError::InvalidIdType),
+        };
+        if valid {
+            Ok(id_type)
+        } else {
+            Err(ParseNonFungibleIdError::InvalidValue)
+        }
     }
 }
 
@@ -42,6 +161,9 @@ impl NonFungibleId {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseNonFungibleIdError {
     InvalidHex(String),
+    InvalidIdType,
+    InvalidValue,
+    InvalidNaturalForm(String),
 }
 
 #[cfg(not(feature = "alloc"))]
@@ -62,6 +184,7 @@ impl TryFrom<&[u8]> for NonFungibleId {
     type Error = ParseNonFungibleIdError;
 
     fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        Self::id_type_of(slice)?;
         Ok(Self(slice.to_vec()))
     }
 }
@@ -108,16 +231,97 @@ mod tests {
     #[test]
     fn test_non_fungible_id_string_rep() {
         assert_eq!(
-            NonFungibleId::from_str("3575").unwrap(),
+            NonFungibleId::from_str("3007020000003575").unwrap(),
             NonFungibleId::from_bytes(vec![53u8, 117u8])
         );
         assert_eq!(
-            NonFungibleId::from_str("00000005").unwrap(),
+            NonFungibleId::from_str("0905000000").unwrap(),
             NonFungibleId::from_u32(5)
         );
         assert_eq!(
-            NonFungibleId::from_str("0000000000000005").unwrap(),
+            NonFungibleId::from_str("0a0500000000000000").unwrap(),
             NonFungibleId::from_u64(5)
         );
     }
+
+    #[test]
+    fn test_non_fungible_id_types_do_not_collide() {
+        assert_ne!(NonFungibleId::from_u32(5), NonFungibleId::from_u64(5));
+        assert_ne!(
+            NonFungibleId::from_u32(5),
+            NonFungibleId::from_bytes(5u32.to_be_bytes().to_vec())
+        );
+        assert_eq!(NonFungibleId::from_u32(5).id_type(), NonFungibleIdType::U32);
+        assert_eq!(NonFungibleId::from_u64(5).id_type(), NonFungibleIdType::U64);
+        assert_eq!(
+            NonFungibleId::from_uuid(5).id_type(),
+            NonFungibleIdType::UUID
+        );
+        assert_eq!(
+            NonFungibleId::from_bytes(vec![5u8]).id_type(),
+            NonFungibleIdType::Bytes
+        );
+        assert_eq!(
+            NonFungibleId::from_string("5").id_type(),
+            NonFungibleIdType::String
+        );
+    }
+
+    #[test]
+    fn test_non_fungible_id_validation() {
+        assert_eq!(
+            NonFungibleId::from_str("3575"),
+            Err(ParseNonFungibleIdError::InvalidIdType)
+        );
+        assert_eq!(
+            NonFungibleId::from_str("0905"),
+            Err(ParseNonFungibleIdError::InvalidValue)
+        );
+    }
+
+    #[test]
+    fn test_non_fungible_id_natural_string() {
+        assert_eq!(NonFungibleId::from_u32(5).to_natural_string(), "5u32");
+        assert_eq!(NonFungibleId::from_u64(5).to_natural_string(), "5u64");
+        assert_eq!(NonFungibleId::from_uuid(5).to_natural_string(), "5u128");
+        assert_eq!(
+            NonFungibleId::from_bytes(vec![53u8, 117u8]).to_natural_string(),
+            "Bytes(\"3575\")"
+        );
+        assert_eq!(
+            NonFungibleId::from_string("car").to_natural_string(),
+            "\"car\""
+        );
+        assert_eq!(
+            NonFungibleId::from_string(r#"a "b" \c"#).to_natural_string(),
+            r#""a \"b\" \\c""#
+        );
+    }
+
+    #[test]
+    fn test_non_fungible_id_from_natural_str() {
+        for id in [
+            NonFungibleId::from_u32(5),
+            NonFungibleId::from_u64(5),
+            NonFungibleId::from_uuid(5),
+            NonFungibleId::from_bytes(vec![53u8, 117u8]),
+            NonFungibleId::from_string("car"),
+            NonFungibleId::from_string(r#"a "b" \c"#),
+        ] {
+            assert_eq!(
+                NonFungibleId::from_natural_str(&id.to_natural_string()),
+                Ok(id)
+            );
+        }
+        assert_eq!(
+            NonFungibleId::from_natural_str("5"),
+            Err(ParseNonFungibleIdError::InvalidNaturalForm("5".to_owned()))
+        );
+        assert_eq!(
+            NonFungibleId::from_natural_str(r#""a"b""#),
+            Err(ParseNonFungibleIdError::InvalidNaturalForm(
+                r#""a"b""#.to_owned()
+            ))
+        );
+    }
 }
@@ -112,6 +112,34 @@ impl Proof {
         non_fungibles.into_iter().next().unwrap()
     }
 
+    /// Whether this proof is restricted to the current call frame.
+    ///
+    /// A proof passed to a blueprint function or component method arrives restricted. A
+    /// restricted proof can be used for authorization, including through the auth zone, but
+    /// it can't be passed to or returned from another function or method, and it's not
+    /// considered when creating proofs from the auth zone.
+    pub fn is_restricted(&self) -> bool {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ProofRef(self.0),
+            function: "is_restricted".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Restricts this proof to the current call frame, so that it can't be moved elsewhere,
+    /// e.g. after being pushed onto the auth zone. Restriction can't be undone.
+    pub fn restrict(&self) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ProofRef(self.0),
+            function: "restrict".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Destroys this proof.
     pub fn drop(self) {
         let input = InvokeSNodeInput {
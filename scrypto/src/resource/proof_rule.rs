@@ -1,10 +1,13 @@
 use crate::engine::api::{CheckAccessRuleInput, CheckAccessRuleOutput, CHECK_ACCESS_RULE};
 use crate::engine::call_engine;
 use crate::resource::AccessRuleNode::{AllOf, AnyOf};
+use crate::component::*;
+use crate::core::ScryptoActorInfo;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
 use crate::rust::vec;
 use crate::rust::vec::Vec;
+use crate::rust::string::String;
 use crate::rust::string::ToString;
 use sbor::*;
 use scrypto::math::Decimal;
@@ -146,6 +149,50 @@ where
     }
 }
 
+/// The component, package or blueprint a caller is required to be.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Describe, TypeId, Encode, Decode)]
+pub enum CallerRequirement {
+    Component(ComponentAddress),
+    Package(PackageAddress),
+    Blueprint(PackageAddress, String),
+}
+
+impl CallerRequirement {
+    /// Returns whether the given actor satisfies this requirement.
+    pub fn matches(&self, actor: &ScryptoActorInfo) -> bool {
+        match self {
+            CallerRequirement::Component(component_address) => {
+                actor.component_address() == Some(*component_address)
+            }
+            CallerRequirement::Package(package_address) => {
+                actor.package_address() == package_address
+            }
+            CallerRequirement::Blueprint(package_address, blueprint_name) => {
+                actor.package_address() == package_address
+                    && actor.blueprint_name() == blueprint_name
+            }
+        }
+    }
+}
+
+impl From<ComponentAddress> for CallerRequirement {
+    fn from(component_address: ComponentAddress) -> Self {
+        CallerRequirement::Component(component_address)
+    }
+}
+
+impl From<PackageAddress> for CallerRequirement {
+    fn from(package_address: PackageAddress) -> Self {
+        CallerRequirement::Package(package_address)
+    }
+}
+
+impl From<(PackageAddress, &str)> for CallerRequirement {
+    fn from((package_address, blueprint_name): (PackageAddress, &str)) -> Self {
+        CallerRequirement::Blueprint(package_address, blueprint_name.to_owned())
+    }
+}
+
 /// Resource Proof Rules
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode)]
 pub enum ProofRule {
@@ -154,6 +201,7 @@ pub enum ProofRule {
     CountOf(SoftCount, SoftResourceOrNonFungibleList),
     AllOf(SoftResourceOrNonFungibleList),
     AnyOf(SoftResourceOrNonFungibleList),
+    RequireCaller(CallerRequirement),
 }
 
 // FIXME: describe types with cycles
@@ -265,6 +313,15 @@ where
     ProofRule::AmountOf(amount.into(), resource.into())
 }
 
+/// Requires the immediate caller to be the given component, or any component or function of
+/// the given package or blueprint.
+pub fn require_caller<T>(caller: T) -> ProofRule
+where
+    T: Into<CallerRequirement>,
+{
+    ProofRule::RequireCaller(caller.into())
+}
+
 // TODO: Move this logic into preprocessor. It probably needs to be implemented as a procedural macro.
 #[macro_export]
 macro_rules! access_and_or {
@@ -15,11 +15,15 @@ pub struct ResourceBuilder;
 
 pub struct FungibleResourceBuilder {
     divisibility: u8,
+    max_supply: Option<Decimal>,
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
 }
 
 pub struct NonFungibleResourceBuilder {
+    id_type: NonFungibleIdType,
+    data_schema: Option<NonFungibleDataSchema>,
+    max_supply: Option<Decimal>,
     metadata: HashMap<String, String>,
     authorization: HashMap<ResourceMethod, (AccessRule, Mutability)>,
 }
@@ -40,6 +44,7 @@ impl FungibleResourceBuilder {
     pub fn new() -> Self {
         Self {
             divisibility: DIVISIBILITY_MAXIMUM,
+            max_supply: None,
             metadata: HashMap::new(),
             authorization: HashMap::new(),
         }
@@ -54,6 +59,14 @@ impl FungibleResourceBuilder {
         self
     }
 
+    /// Set the maximum supply.
+    ///
+    /// Minting beyond this amount will fail, regardless of the mint authorization.
+    pub fn max_supply<T: Into<Decimal>>(&mut self, amount: T) -> &mut Self {
+        self.max_supply = Some(amount.into());
+        self
+    }
+
     /// Adds a resource metadata.
     ///
     /// If a previous attribute with the same name has been set, it will be overwritten.
@@ -103,6 +116,16 @@ impl FungibleResourceBuilder {
         self
     }
 
+    pub fn recallable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Recall, (method_auth, mutability));
+        self
+    }
+
+    pub fn freezable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Freeze, (method_auth, mutability));
+        self
+    }
+
     /// Creates resource with the given initial supply.
     ///
     /// # Example
@@ -133,6 +156,8 @@ impl FungibleResourceBuilder {
             self.metadata.clone(),
             authorization,
             mint_params,
+            None,
+            self.max_supply,
         )
     }
 }
@@ -140,11 +165,39 @@ impl FungibleResourceBuilder {
 impl NonFungibleResourceBuilder {
     pub fn new() -> Self {
         Self {
+            id_type: NonFungibleIdType::Bytes,
+            data_schema: None,
+            max_supply: None,
             metadata: HashMap::new(),
             authorization: HashMap::new(),
         }
     }
 
+    /// Set the type of non-fungible IDs.
+    ///
+    /// Defaults to `NonFungibleIdType::Bytes`; minting an ID of another type will fail.
+    pub fn id_type(&mut self, id_type: NonFungibleIdType) -> &mut Self {
+        self.id_type = id_type;
+        self
+    }
+
+    /// Set the data structure of the non-fungibles.
+    ///
+    /// Minted and updated data will be validated against the schema of `T`. This is implied
+    /// by `initial_supply`, but needs to be set explicitly when there is no initial supply.
+    pub fn data_schema<T: NonFungibleData>(&mut self) -> &mut Self {
+        self.data_schema = Some(NonFungibleDataSchema::new::<T>());
+        self
+    }
+
+    /// Set the maximum number of non-fungibles.
+    ///
+    /// Minting beyond this amount will fail, regardless of the mint authorization.
+    pub fn max_supply<T: Into<Decimal>>(&mut self, amount: T) -> &mut Self {
+        self.max_supply = Some(amount.into());
+        self
+    }
+
     /// Adds a resource metadata.
     ///
     /// If a previous attribute with the same name has been set, it will be overwritten.
@@ -194,6 +247,16 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    pub fn recallable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Recall, (method_auth, mutability));
+        self
+    }
+
+    pub fn freezable(&mut self, method_auth: AccessRule, mutability: Mutability) -> &mut Self {
+        self.authorization.insert(Freeze, (method_auth, mutability));
+        self
+    }
+
     pub fn updateable_non_fungible_data(
         &mut self,
         method_auth: AccessRule,
@@ -210,9 +273,10 @@ impl NonFungibleResourceBuilder {
     /// ```ignore
     /// let bucket = ResourceBuilder::new_non_fungible()
     ///     .metadata("name", "TestNonFungible")
+    ///     .id_type(NonFungibleIdType::UUID)
     ///     .initial_supply([
-    ///         (NftKey::from(1u128), "immutable_part", "mutable_part"),
-    ///         (NftKey::from(2u128), "another_immutable_part", "another_mutable_part"),
+    ///         (NonFungibleId::from_uuid(1), "immutable_part", "mutable_part"),
+    ///         (NonFungibleId::from_uuid(2), "another_immutable_part", "another_mutable_part"),
     ///     ]);
     /// ```
     pub fn initial_supply<T, V>(&self, entries: T) -> Bucket
@@ -220,27 +284,39 @@ impl NonFungibleResourceBuilder {
         T: IntoIterator<Item = (NonFungibleId, V)>,
         V: NonFungibleData,
     {
-        self.build(Some(MintParams::non_fungible(entries)))
+        let data_schema = self
+            .data_schema
+            .clone()
+            .unwrap_or_else(NonFungibleDataSchema::new::<V>);
+        self.build(Some(MintParams::non_fungible(entries)), Some(data_schema))
             .1
             .unwrap()
     }
 
     /// Creates resource with no initial supply.
     pub fn no_initial_supply(&self) -> ResourceAddress {
-        self.build(None).0
+        self.build(None, self.data_schema.clone()).0
     }
 
-    fn build(&self, mint_params: Option<MintParams>) -> (ResourceAddress, Option<Bucket>) {
+    fn build(
+        &self,
+        mint_params: Option<MintParams>,
+        data_schema: Option<NonFungibleDataSchema>,
+    ) -> (ResourceAddress, Option<Bucket>) {
         let mut authorization = self.authorization.clone();
         if !authorization.contains_key(&Withdraw) {
             authorization.insert(Withdraw, (rule!(allow_all), LOCKED));
         }
 
         resource_system().new_resource(
-            ResourceType::NonFungible,
+            ResourceType::NonFungible {
+                id_type: self.id_type,
+            },
             self.metadata.clone(),
             authorization,
             mint_params,
+            data_schema,
+            self.max_supply,
         )
     }
 }
@@ -2,12 +2,14 @@ use sbor::*;
 
 use crate::args;
 use crate::buffer::scrypto_decode;
+use crate::component::ComponentAddress;
 use crate::core::SNodeRef;
-use crate::engine::{api::*, call_engine};
+use crate::engine::{api::*, call_engine, types::VaultId};
 use crate::math::*;
 use crate::misc::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
+use crate::rust::collections::BTreeSet;
 use crate::rust::collections::HashMap;
 use crate::rust::fmt;
 use crate::rust::str::FromStr;
@@ -24,6 +26,8 @@ pub enum ResourceMethod {
     Deposit,
     UpdateMetadata,
     UpdateNonFungibleData,
+    Recall,
+    Freeze,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
@@ -211,6 +215,105 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    pub fn set_recallable(&self, recall_auth: AccessRule) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Recall, "update", recall_auth],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn lock_recallable(&self) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Recall, "lock"],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn set_freezable(&self, freeze_auth: AccessRule) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Freeze, "update", freeze_auth],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    pub fn lock_freezable(&self) -> () {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "method_auth".to_string(),
+            args: args![Freeze, "lock"],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Recalls resources from a vault owned by the given component.
+    ///
+    /// # Panics
+    /// Panics if the vault does not exist or does not contain this resource.
+    pub fn recall<T: Into<Decimal>>(
+        &self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        amount: T,
+    ) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "recall".to_string(),
+            args: args![component_address, vault_id, amount.into()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Recalls non-fungible units from a vault owned by the given component.
+    ///
+    /// # Panics
+    /// Panics if the vault does not exist or does not contain this resource.
+    pub fn recall_non_fungibles(
+        &self,
+        component_address: ComponentAddress,
+        vault_id: VaultId,
+        ids: &BTreeSet<NonFungibleId>,
+    ) -> Bucket {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "recall_non_fungibles".to_string(),
+            args: args![component_address, vault_id, ids.clone()],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Freezes a vault owned by the given component, so that nothing can be withdrawn from or
+    /// deposited into it until it's unfrozen.
+    pub fn freeze_vault(&self, component_address: ComponentAddress, vault_id: VaultId) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "freeze_vault".to_string(),
+            args: args![component_address, vault_id],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
+    /// Unfreezes a vault owned by the given component.
+    pub fn unfreeze_vault(&self, component_address: ComponentAddress, vault_id: VaultId) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "unfreeze_vault".to_string(),
+            args: args![component_address, vault_id],
+        };
+        let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+    }
+
     /// Returns the metadata associated with this resource.
     pub fn metadata(&self) -> HashMap<String, String> {
         let input = InvokeSNodeInput {
@@ -233,6 +336,17 @@ impl ResourceManager {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Returns the maximum supply of this resource, if capped.
+    pub fn max_supply(&self) -> Option<Decimal> {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::ResourceRef(self.0),
+            function: "get_max_supply".to_string(),
+            args: args![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns the data of a non-fungible unit, both the immutable and mutable parts.
     ///
     /// # Panics
@@ -251,12 +365,17 @@ impl ResourceManager {
     /// Updates the mutable part of a non-fungible unit.
     ///
     /// # Panics
-    /// Panics if this is not a non-fungible resource or the specified non-fungible is not found.
+    /// Panics if this is not a non-fungible resource, the specified non-fungible is not found,
+    /// or the immutable part of `new_data` differs from the existing one.
     pub fn update_non_fungible_data<T: NonFungibleData>(&self, id: &NonFungibleId, new_data: T) {
         let input = InvokeSNodeInput {
             snode_ref: SNodeRef::ResourceRef(self.0),
             function: "update_non_fungible_mutable_data".to_string(),
-            args: args![id.clone(), new_data.mutable_data()],
+            args: args![
+                id.clone(),
+                new_data.mutable_data(),
+                new_data.immutable_data()
+            ],
         };
         let _: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
     }
@@ -1,9 +1,16 @@
-use crate::resource::schema_path::SchemaSubPath::{Field, Index};
+use crate::buffer::scrypto_encode;
+use crate::component::LazyMap;
+use crate::engine::types::LazyMapId;
+use crate::resource::schema_path::SchemaSubPath::{Field, Index, Key};
+use crate::resource::NonFungibleId;
+use crate::rust::fmt;
 use crate::rust::str::FromStr;
 use crate::rust::string::String;
 use crate::rust::string::ToString;
 use crate::rust::vec;
 use crate::rust::vec::Vec;
+use crate::types::ScryptoType;
+use sbor::any::{decode_any, Value};
 use sbor::describe::Fields;
 use sbor::path::SborPath;
 use sbor::*;
@@ -12,12 +19,22 @@ use sbor::*;
 enum SchemaSubPath {
     Index(usize),
     Field(String),
+    /// The entry of a lazy map with the given SBOR-encoded key
+    Key(Vec<u8>),
 }
 
 impl FromStr for SchemaSubPath {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(key) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            return NonFungibleId::from_natural_str(key)
+                .map(|id| id.to_vec())
+                .or_else(|_| hex::decode(key))
+                .map(Key)
+                .map_err(|_| ());
+        }
+
         // TODO: check that field is a valid field name string
         let sub_path = s
             .parse::<usize>()
@@ -28,6 +45,12 @@ impl FromStr for SchemaSubPath {
 }
 
 /// Describes a value located in some sbor given a schema for that sbor
+///
+/// As a string, sub paths are separated by `/`. A sub path is a field name, an index, or a
+/// lazy map key in brackets, which looks up the entry of the lazy map at the current path.
+/// Keys of type `String`, `u32`, `u64`, `u128` and `Vec<u8>` are written in the same natural
+/// form as non-fungible IDs, e.g. `admins/["alice"]/badge` or `admins/[5u32]`, and can't
+/// contain `/`. Keys of other types are written as the hex of their SBOR encoding.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Describe, TypeId, Encode, Decode)]
 pub struct SchemaPath(Vec<SchemaSubPath>);
 
@@ -51,11 +74,92 @@ impl SchemaPath {
         self
     }
 
+    /// Adds a lookup of the entry with the given key, into the lazy map at the current path.
+    pub fn key<K: Encode>(&mut self, key: &K) -> &Self {
+        self.0.push(SchemaSubPath::Key(scrypto_encode(key)));
+        self
+    }
+
+    /// Converts this path into an sbor path, if it does not look up any lazy map entries.
     pub fn to_sbor_path(&self, schema: &Type) -> Option<SborPath> {
+        Self::to_sbor_path_of(&self.0, schema).map(|(sbor_path, _)| sbor_path)
+    }
+
+    /// Retrieves the value at this path, where `value` is described by `schema`. The entries of
+    /// any lazy maps along the path are loaded through `load_lazy_map_entry`.
+    pub fn get_from_value<F>(
+        &self,
+        schema: &Type,
+        value: &Value,
+        mut load_lazy_map_entry: F,
+    ) -> Option<Value>
+    where
+        F: FnMut(&LazyMapId, &[u8]) -> Option<Vec<u8>>,
+    {
+        let mut cur_type = schema;
+        let mut entry: Option<Value> = None;
+        let mut start = 0;
+
+        for (i, sub_path) in self.0.iter().enumerate() {
+            if let SchemaSubPath::Key(key) = sub_path {
+                let (sbor_path, map_type) = Self::to_sbor_path_of(&self.0[start..i], cur_type)?;
+                let lazy_map_id = match sbor_path.get_from_value(entry.as_ref().unwrap_or(value)) {
+                    Some(Value::Custom { type_id, bytes })
+                        if *type_id == ScryptoType::LazyMap.id() =>
+                    {
+                        LazyMap::<(), ()>::try_from(bytes.as_slice()).ok()?.id
+                    }
+                    _ => return None,
+                };
+                cur_type = Self::lazy_map_value_type(map_type)?;
+                let entry_bytes = load_lazy_map_entry(&lazy_map_id, key)?;
+                entry = Some(decode_any(&entry_bytes).ok()?);
+                start = i + 1;
+            }
+        }
+
+        let (sbor_path, _) = Self::to_sbor_path_of(&self.0[start..], cur_type)?;
+        sbor_path
+            .get_from_value(entry.as_ref().unwrap_or(value))
+            .cloned()
+    }
+
+    /// Retrieves the type of the value at this path, where the root value is described by
+    /// `schema`. Looking up a lazy map entry steps into the value type of the lazy map.
+    pub fn get_type<'a>(&self, schema: &'a Type) -> Option<&'a Type> {
+        let mut cur_type = schema;
+        let mut start = 0;
+
+        for (i, sub_path) in self.0.iter().enumerate() {
+            if let SchemaSubPath::Key(_) = sub_path {
+                let (_, map_type) = Self::to_sbor_path_of(&self.0[start..i], cur_type)?;
+                cur_type = Self::lazy_map_value_type(map_type)?;
+                start = i + 1;
+            }
+        }
+
+        Self::to_sbor_path_of(&self.0[start..], cur_type).map(|(_, cur_type)| cur_type)
+    }
+
+    fn lazy_map_value_type(map_type: &Type) -> Option<&Type> {
+        match map_type {
+            Type::Custom { name, generics }
+                if *name == ScryptoType::LazyMap.name() && generics.len() == 2 =>
+            {
+                Some(&generics[1])
+            }
+            _ => None,
+        }
+    }
+
+    fn to_sbor_path_of<'a>(
+        sub_paths: &[SchemaSubPath],
+        schema: &'a Type,
+    ) -> Option<(SborPath, &'a Type)> {
         let mut cur_type = schema;
         let mut sbor_path: Vec<usize> = vec![];
 
-        for sub_path in &self.0 {
+        for sub_path in sub_paths {
             match sub_path {
                 SchemaSubPath::Index(index) => match cur_type {
                     Type::Vec { element } => {
@@ -89,10 +193,11 @@ impl SchemaPath {
                         return Option::None;
                     }
                 }
+                SchemaSubPath::Key(_) => return Option::None,
             }
         }
 
-        Option::Some(SborPath::new(sbor_path))
+        Option::Some((SborPath::new(sbor_path), cur_type))
     }
 }
 
@@ -116,3 +221,129 @@ impl FromStr for SchemaPath {
         Ok(schema_path)
     }
 }
+
+impl fmt::Display for SchemaPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, sub_path) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            match sub_path {
+                Index(index) => write!(f, "{}", index)?,
+                Field(field) => write!(f, "{}", field)?,
+                Key(key) => match NonFungibleId::try_from(key.as_slice()) {
+                    Ok(id) => write!(f, "[{}]", id.to_natural_string())?,
+                    Err(_) => write!(f, "[{}]", hex::encode(key))?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::rust::marker::PhantomData;
+
+    #[derive(TypeId, Encode, Decode, Describe)]
+    struct Admin {
+        badge: ResourceAddress,
+    }
+
+    #[derive(TypeId, Encode, Decode, Describe)]
+    struct State {
+        admins: LazyMap<String, Admin>,
+    }
+
+    fn admins_path(name: &str) -> SchemaPath {
+        let mut path = SchemaPath::new();
+        path.field("admins");
+        path.key(&name.to_owned());
+        path.field("badge");
+        path
+    }
+
+    #[test]
+    fn test_get_from_lazy_map_entry() {
+        let lazy_map_id = (Hash([1u8; 32]), 7);
+        let state = State {
+            admins: LazyMap {
+                id: lazy_map_id,
+                key: PhantomData,
+                value: PhantomData,
+            },
+        };
+        let value = decode_any(&scrypto_encode(&state)).unwrap();
+        let entry = scrypto_encode(&Admin { badge: RADIX_TOKEN });
+        let load_lazy_map_entry = |id: &LazyMapId, key: &[u8]| {
+            if *id == lazy_map_id && key == scrypto_encode(&"alice".to_owned()) {
+                Some(entry.clone())
+            } else {
+                None
+            }
+        };
+
+        let badge =
+            admins_path("alice").get_from_value(&State::describe(), &value, load_lazy_map_entry);
+        assert_eq!(
+            badge,
+            Some(Value::Custom {
+                type_id: ScryptoType::ResourceAddress.id(),
+                bytes: RADIX_TOKEN.to_vec(),
+            })
+        );
+        let missing =
+            admins_path("bob").get_from_value(&State::describe(), &value, load_lazy_map_entry);
+        assert_eq!(missing, None);
+        assert!(admins_path("alice")
+            .to_sbor_path(&State::describe())
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_type_through_lazy_map_entry() {
+        assert_eq!(
+            admins_path("alice").get_type(&State::describe()),
+            Some(&ResourceAddress::describe())
+        );
+
+        let mut path = SchemaPath::new();
+        path.field("admins");
+        path.field("badge");
+        assert_eq!(path.get_type(&State::describe()), None);
+    }
+
+    #[test]
+    fn test_lazy_map_key_string_roundtrip() {
+        let path = admins_path("alice");
+        let s = path.to_string();
+        assert_eq!(s, "admins/[\"alice\"]/badge");
+        assert_eq!(SchemaPath::from_str(&s).unwrap(), path);
+
+        let mut path = SchemaPath::new();
+        path.field("admins");
+        path.key(&(1u8, 2u8));
+        let s = path.to_string();
+        assert_eq!(
+            s,
+            format!("admins/[{}]", hex::encode(scrypto_encode(&(1u8, 2u8))))
+        );
+        assert_eq!(SchemaPath::from_str(&s).unwrap(), path);
+    }
+
+    #[test]
+    fn test_lazy_map_key_from_str() {
+        let mut path = SchemaPath::new();
+        path.field("admins");
+        path.key(&5u32);
+        assert_eq!(SchemaPath::from_str("admins/[5u32]").unwrap(), path);
+        assert_eq!(
+            SchemaPath::from_str(&format!("admins/[{}]", hex::encode(scrypto_encode(&5u32))))
+                .unwrap(),
+            path
+        );
+        assert!(SchemaPath::from_str("admins/[alice]").is_err());
+    }
+}
@@ -91,6 +91,91 @@ impl Vault {
         scrypto_decode(&output.rtn).unwrap()
     }
 
+    /// Locks some amount of XRD in this vault to pay the transaction fee.
+    ///
+    /// The locked fee is paid even if the transaction fails.
+    ///
+    /// # Panics
+    /// Panics if this is not an XRD vault or the vault is not stored in a global component.
+    pub fn lock_fee<A: Into<Decimal>>(&mut self, amount: A) {
+        let amount: Decimal = amount.into();
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "lock_fee".to_string(),
+            args: vec![scrypto_encode(&amount)],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Locks some amount of resource in this vault until the given epoch.
+    ///
+    /// Locked resources can't be taken from the vault, but can still be used to create proofs.
+    pub fn lock_until<A: Into<Decimal>>(&mut self, amount: A, epoch: u64) {
+        let amount: Decimal = amount.into();
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "lock_vault_until".to_string(),
+            args: vec![scrypto_encode(&amount), scrypto_encode(&epoch)],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Locks the specified non-fungibles in this vault until the given epoch.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault or the specified non-fungible resource is not found.
+    pub fn lock_non_fungibles_until(
+        &mut self,
+        non_fungible_ids: &BTreeSet<NonFungibleId>,
+        epoch: u64,
+    ) {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "lock_non_fungibles_in_vault_until".to_string(),
+            args: vec![scrypto_encode(non_fungible_ids), scrypto_encode(&epoch)],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Locks some amount of resource in this vault and releases it linearly, from `start_epoch`
+    /// until fully released at `end_epoch`.
+    ///
+    /// # Panics
+    /// Panics if this is not a fungible vault or `start_epoch` is not before `end_epoch`.
+    pub fn vesting_schedule<A: Into<Decimal>>(
+        &mut self,
+        amount: A,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) {
+        let amount: Decimal = amount.into();
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "add_vault_vesting_schedule".to_string(),
+            args: vec![
+                scrypto_encode(&amount),
+                scrypto_encode(&start_epoch),
+                scrypto_encode(&end_epoch),
+            ],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
+    /// Returns the amount of resources within this vault which are currently time locked.
+    pub fn time_locked_amount(&self) -> Decimal {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "get_vault_time_locked_amount".to_string(),
+            args: vec![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Creates an ownership proof of this vault.
     pub fn create_proof(&self) -> Proof {
         let input = InvokeSNodeInput {
@@ -159,6 +244,17 @@ impl Vault {
         self.amount() == 0.into()
     }
 
+    /// Checks if this vault has been frozen by the resource issuer.
+    pub fn is_frozen(&self) -> bool {
+        let input = InvokeSNodeInput {
+            snode_ref: SNodeRef::VaultRef(self.0),
+            function: "is_vault_frozen".to_string(),
+            args: vec![],
+        };
+        let output: InvokeSNodeOutput = call_engine(INVOKE_SNODE, input);
+        scrypto_decode(&output.rtn).unwrap()
+    }
+
     /// Returns all the non-fungible ids contained.
     ///
     /// # Panics
@@ -1,6 +1,7 @@
 use sbor::type_id::*;
 use sbor::{any::*, *};
 use sbor::path::{MutableSborPath, SborPath};
+use sbor::describe::Fields;
 
 use crate::buffer::*;
 use crate::component::*;
@@ -43,8 +44,17 @@ pub struct ScryptoValue {
 
 impl ScryptoValue {
     pub fn from_slice(slice: &[u8]) -> Result<Self, ParseScryptoValueError> {
+        Self::from_slice_with_max_depth(slice, usize::MAX)
+    }
+
+    /// Parses a value which is nested at most `max_depth` levels deep.
+    pub fn from_slice_with_max_depth(
+        slice: &[u8],
+        max_depth: usize,
+    ) -> Result<Self, ParseScryptoValueError> {
         // Decode with SBOR
-        let value = decode_any(slice).map_err(ParseScryptoValueError::DecodeError)?;
+        let value = decode_any_with_max_depth(slice, max_depth)
+            .map_err(ParseScryptoValueError::DecodeError)?;
 
         // Scrypto specific types checking
         let mut checker = ScryptoCustomValueChecker::new();
@@ -119,6 +129,175 @@ impl ScryptoValue {
     ) -> String {
         ScryptoValueFormatter::format_value(&self.dom, bucket_ids, proof_ids)
     }
+
+    /// Checks whether this value conforms to the given schema.
+    pub fn matches_schema(&self, schema: &Type) -> bool {
+        value_matches_type(&self.dom, schema)
+    }
+}
+
+fn schema_type_id(ty: &Type) -> Option<u8> {
+    let type_id = match ty {
+        Type::Unit => TYPE_UNIT,
+        Type::Bool => TYPE_BOOL,
+        Type::I8 => TYPE_I8,
+        Type::I16 => TYPE_I16,
+        Type::I32 => TYPE_I32,
+        Type::I64 => TYPE_I64,
+        Type::I128 => TYPE_I128,
+        Type::U8 => TYPE_U8,
+        Type::U16 => TYPE_U16,
+        Type::U32 => TYPE_U32,
+        Type::U64 => TYPE_U64,
+        Type::U128 => TYPE_U128,
+        Type::String => TYPE_STRING,
+        Type::Option { .. } => TYPE_OPTION,
+        Type::Array { .. } => TYPE_ARRAY,
+        Type::Tuple { .. } => TYPE_TUPLE,
+        Type::Struct { .. } => TYPE_STRUCT,
+        Type::Enum { .. } => TYPE_ENUM,
+        Type::Result { .. } => TYPE_RESULT,
+        Type::Vec { .. } => TYPE_VEC,
+        Type::TreeSet { .. } => TYPE_TREE_SET,
+        Type::TreeMap { .. } => TYPE_TREE_MAP,
+        Type::HashSet { .. } => TYPE_HASH_SET,
+        Type::HashMap { .. } => TYPE_HASH_MAP,
+        Type::Custom { name, .. } => return ScryptoType::from_name(name).map(|t| t.id()),
+    };
+    Some(type_id)
+}
+
+fn fields_match(values: &[Value], fields: &Fields) -> bool {
+    match fields {
+        Fields::Named { named } => {
+            values.len() == named.len()
+                && values
+                    .iter()
+                    .zip(named)
+                    .all(|(v, (_, t))| value_matches_type(v, t))
+        }
+        Fields::Unnamed { unnamed } => {
+            values.len() == unnamed.len()
+                && values
+                    .iter()
+                    .zip(unnamed)
+                    .all(|(v, t)| value_matches_type(v, t))
+        }
+        Fields::Unit => values.is_empty(),
+    }
+}
+
+fn elements_match(element_type_id: u8, elements: &[Value], element: &Type) -> bool {
+    schema_type_id(element) == Some(element_type_id)
+        && elements.iter().all(|e| value_matches_type(e, element))
+}
+
+fn entries_match(
+    key_type_id: u8,
+    value_type_id: u8,
+    elements: &[Value],
+    key: &Type,
+    value: &Type,
+) -> bool {
+    schema_type_id(key) == Some(key_type_id)
+        && schema_type_id(value) == Some(value_type_id)
+        && elements.chunks(2).all(|pair| {
+            pair.len() == 2
+                && value_matches_type(&pair[0], key)
+                && value_matches_type(&pair[1], value)
+        })
+}
+
+fn value_matches_type(value: &Value, ty: &Type) -> bool {
+    match (value, ty) {
+        (Value::Unit, Type::Unit)
+        | (Value::Bool { .. }, Type::Bool)
+        | (Value::I8 { .. }, Type::I8)
+        | (Value::I16 { .. }, Type::I16)
+        | (Value::I32 { .. }, Type::I32)
+        | (Value::I64 { .. }, Type::I64)
+        | (Value::I128 { .. }, Type::I128)
+        | (Value::U8 { .. }, Type::U8)
+        | (Value::U16 { .. }, Type::U16)
+        | (Value::U32 { .. }, Type::U32)
+        | (Value::U64 { .. }, Type::U64)
+        | (Value::U128 { .. }, Type::U128)
+        | (Value::String { .. }, Type::String) => true,
+        (Value::Struct { fields }, Type::Struct { fields: schema, .. }) => {
+            fields_match(fields, schema)
+        }
+        (Value::Enum { name, fields }, Type::Enum { variants, .. }) => variants
+            .iter()
+            .find(|variant| &variant.name == name)
+            .map(|variant| fields_match(fields, &variant.fields))
+            .unwrap_or(false),
+        (Value::Option { value }, Type::Option { value: schema }) => match value.borrow() {
+            Some(v) => value_matches_type(v, schema),
+            None => true,
+        },
+        (
+            Value::Array {
+                element_type_id,
+                elements,
+            },
+            Type::Array { element, length },
+        ) => {
+            elements.len() == *length as usize
+                && elements_match(*element_type_id, elements, element)
+        }
+        (Value::Tuple { elements }, Type::Tuple { elements: schema }) => {
+            elements.len() == schema.len()
+                && elements
+                    .iter()
+                    .zip(schema)
+                    .all(|(v, t)| value_matches_type(v, t))
+        }
+        (Value::Result { value }, Type::Result { okay, error }) => match value.borrow() {
+            Ok(v) => value_matches_type(v, okay),
+            Err(v) => value_matches_type(v, error),
+        },
+        (
+            Value::Vec {
+                element_type_id,
+                elements,
+            },
+            Type::Vec { element },
+        )
+        | (
+            Value::TreeSet {
+                element_type_id,
+                elements,
+            },
+            Type::TreeSet { element },
+        )
+        | (
+            Value::HashSet {
+                element_type_id,
+                elements,
+            },
+            Type::HashSet { element },
+        ) => elements_match(*element_type_id, elements, element),
+        (
+            Value::TreeMap {
+                key_type_id,
+                value_type_id,
+                elements,
+            },
+            Type::TreeMap { key, value },
+        )
+        | (
+            Value::HashMap {
+                key_type_id,
+                value_type_id,
+                elements,
+            },
+            Type::HashMap { key, value },
+        ) => entries_match(*key_type_id, *value_type_id, elements, key, value),
+        (Value::Custom { type_id, .. }, Type::Custom { name, .. }) => {
+            ScryptoType::from_name(name).map(|t| t.id()) == Some(*type_id)
+        }
+        _ => false,
+    }
 }
 
 impl fmt::Debug for ScryptoValue {
@@ -476,8 +655,8 @@ impl ScryptoValueFormatter {
             }
             ScryptoType::Vault => format!("Vault(\"{}\")", Vault::try_from(data).unwrap()),
             ScryptoType::NonFungibleId => format!(
-                "NonFungibleId(\"{}\")",
-                NonFungibleId::try_from(data).unwrap()
+                "NonFungibleId({})",
+                NonFungibleId::try_from(data).unwrap().to_natural_string()
             ),
             ScryptoType::NonFungibleAddress => format!(
                 "NonFungibleAddress(\"{}\")",
@@ -2,8 +2,11 @@
 use colored::*;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
+use sbor::describe::{Fields, Type};
+use sbor::Value;
 use scrypto::engine::types::*;
-use scrypto::rust::collections::HashSet;
+use scrypto::resource::NonFungibleDataSchema;
+use scrypto::rust::collections::{BTreeMap, HashMap, HashSet};
 use scrypto::values::*;
 use std::collections::VecDeque;
 
@@ -147,6 +150,7 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
             .0;
 
         let amount = vault.total_amount();
+        let time_locked_amount = vault.time_locked_amount(substate_store.get_epoch());
         let resource_address = vault.resource_address();
         let resource_manager: ResourceManager = substate_store
             .get_decoded_substate(&resource_address)
@@ -154,9 +158,14 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
             .unwrap();
         writeln!(
             output,
-            "{} {{ amount: {}, resource address: {}{}{} }}",
+            "{} {{ amount: {}{}, resource address: {}{}{} }}",
             list_item_prefix(last),
             amount,
+            if time_locked_amount.is_zero() {
+                String::new()
+            } else {
+                format!(", locked: {}", time_locked_amount)
+            },
             resource_address,
             resource_manager
                 .metadata()
@@ -169,7 +178,10 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
                 .map(|symbol| format!(", symbol: \"{}\"", symbol))
                 .unwrap_or(String::new()),
         );
-        if matches!(resource_manager.resource_type(), ResourceType::NonFungible) {
+        if matches!(
+            resource_manager.resource_type(),
+            ResourceType::NonFungible { .. }
+        ) {
             let ids = vault.total_ids().unwrap();
             for (inner_last, id) in ids.iter().identify_last() {
                 let non_fungible: Option<NonFungible> = substate_store
@@ -178,18 +190,16 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
                     .0;
 
                 if let Some(non_fungible) = non_fungible {
-                    let immutable_data =
-                        ScryptoValue::from_slice(&non_fungible.immutable_data()).unwrap();
-                    let mutable_data =
-                        ScryptoValue::from_slice(&non_fungible.mutable_data()).unwrap();
                     writeln!(
                         output,
-                        "{}  {} NonFungible {{ id: {}, immutable_data: {}, mutable_data: {} }}",
+                        "{}  {} {}",
                         if last { " " } else { "│" },
                         list_item_prefix(inner_last),
-                        id,
-                        immutable_data,
-                        mutable_data
+                        format_non_fungible(
+                            id,
+                            &non_fungible,
+                            resource_manager.non_fungible_data_schema()
+                        )
                     );
                 }
             }
@@ -198,8 +208,55 @@ fn dump_resources<T: SubstateStore, O: std::io::Write>(
     Ok(())
 }
 
+fn format_non_fungible_data(data: &[u8], schema: Option<&Type>) -> String {
+    let value = ScryptoValue::from_slice(data).unwrap();
+    match (schema, &value.dom) {
+        (
+            Some(Type::Struct {
+                name,
+                fields: Fields::Named { named },
+            }),
+            Value::Struct { fields },
+        ) if named.len() == fields.len() => {
+            if fields.is_empty() {
+                return name.clone();
+            }
+            let fields: Vec<String> = named
+                .iter()
+                .zip(fields)
+                .map(|((field_name, _), field)| {
+                    format!(
+                        "{}: {}",
+                        field_name,
+                        ScryptoValueFormatter::format_value(
+                            field,
+                            &HashMap::new(),
+                            &HashMap::new()
+                        )
+                    )
+                })
+                .collect();
+            format!("{} {{ {} }}", name, fields.join(", "))
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn format_non_fungible(
+    id: &NonFungibleId,
+    non_fungible: &NonFungible,
+    schema: Option<&NonFungibleDataSchema>,
+) -> String {
+    format!(
+        "NonFungible {{ id: {}, immutable_data: {}, mutable_data: {} }}",
+        id.to_natural_string(),
+        format_non_fungible_data(&non_fungible.immutable_data(), schema.map(|s| &s.immutable)),
+        format_non_fungible_data(&non_fungible.mutable_data(), schema.map(|s| &s.mutable))
+    )
+}
+
 /// Dump a resource into console.
-pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
+pub fn dump_resource_manager<T: SubstateStore + QueryableSubstateStore, O: std::io::Write>(
     resource_address: ResourceAddress,
     substate_store: &T,
     output: &mut O,
@@ -236,6 +293,29 @@ pub fn dump_resource_manager<T: SubstateStore, O: std::io::Write>(
                 "Total Supply".green().bold(),
                 r.total_supply()
             );
+            if let Some(max_supply) = r.max_supply() {
+                writeln!(output, "{}: {}", "Max Supply".green().bold(), max_supply);
+            }
+            if let ResourceType::NonFungible { .. } = r.resource_type() {
+                let non_fungibles: BTreeMap<NonFungibleId, NonFungible> = substate_store
+                    .get_non_fungibles(resource_address)
+                    .into_iter()
+                    .collect();
+                writeln!(
+                    output,
+                    "{}: {}",
+                    "Non-fungibles".green().bold(),
+                    non_fungibles.len()
+                );
+                for (last, (id, non_fungible)) in non_fungibles.iter().identify_last() {
+                    writeln!(
+                        output,
+                        "{} {}",
+                        list_item_prefix(last),
+                        format_non_fungible(id, non_fungible, r.non_fungible_data_schema())
+                    );
+                }
+            }
             Ok(())
         }
         None => Err(DisplayError::ResourceManagerNotFound),
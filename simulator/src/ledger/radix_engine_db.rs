@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use radix_engine::ledger::*;
+use radix_engine::model::NonFungible;
 use rocksdb::{DBWithThreadMode, Direction, IteratorMode, SingleThreaded, DB};
 use sbor::{Decode, Encode};
 use scrypto::buffer::*;
@@ -92,6 +93,35 @@ impl QueryableSubstateStore for RadixEngineDB {
         }
         items
     }
+
+    fn get_non_fungibles(
+        &self,
+        resource_address: ResourceAddress,
+    ) -> HashMap<NonFungibleId, NonFungible> {
+        let id = scrypto_encode(&resource_address);
+
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(&id, Direction::Forward));
+        let mut items = HashMap::new();
+        for (key, value) in iter {
+            if !key.starts_with(&id) {
+                break;
+            }
+
+            // The resource manager itself is stored at the resource address
+            let local_key = key.split_at(id.len()).1;
+            if local_key.is_empty() {
+                continue;
+            }
+            let substate: Substate = scrypto_decode(&value).unwrap();
+            let non_fungible: Option<NonFungible> = scrypto_decode(&substate.value).unwrap();
+            if let Some(non_fungible) = non_fungible {
+                items.insert(scrypto_decode(local_key).unwrap(), non_fungible);
+            }
+        }
+        items
+    }
 }
 
 impl SubstateStore for RadixEngineDB {
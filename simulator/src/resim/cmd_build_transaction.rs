@@ -0,0 +1,38 @@
+use clap::Parser;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Compile a transaction manifest into an unsigned transaction file, for offline signing
+#[derive(Parser, Debug)]
+pub struct BuildTransaction {
+    /// The path to a transaction manifest file
+    path: PathBuf,
+
+    /// The path of the transaction file to write
+    output: PathBuf,
+
+    /// The public keys of the intended signers, separated by comma
+    #[clap(short, long)]
+    signers: Option<String>,
+}
+
+impl BuildTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let executor = TransactionExecutor::new(&mut ledger, false);
+        let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
+        let pre_processed_manifest = Run::pre_process_manifest(&manifest);
+        let mut transaction =
+            transaction_manifest::compile(&pre_processed_manifest, LOCAL_SIMULATOR_NETWORK_ID)
+                .map_err(Error::CompileError)?;
+
+        let pks = parse_public_keys(&self.signers)?;
+        transaction.add_nonce(executor.get_nonce(&pks));
+
+        write_transaction_file(&self.output, &PartiallySignedTransaction::new(transaction))?;
+        writeln!(out, "Transaction written to {}", self.output.display()).map_err(Error::IOError)
+    }
+}
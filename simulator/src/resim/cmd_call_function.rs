@@ -16,7 +16,7 @@ pub struct CallFunction {
     /// The function name
     function_name: String,
 
-    /// The call arguments, e.g. \"5\", \"hello\", \"amount,resource_address\" for Bucket, or \"#id1,#id2,..,resource_address\" for non-fungible Bucket
+    /// The call arguments, e.g. \"5\", \"hello\", \"amount,resource_address\" for Bucket, or \"#id1,#id2,..,resource_address\" for non-fungible Bucket, with IDs in the form `show` prints, e.g. \"#1u32\"
     arguments: Vec<String>,
 
     /// Output a transaction manifest without execution
@@ -30,12 +30,17 @@ pub struct CallFunction {
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Write the execution trace to the given file, as JSON
+    #[clap(long)]
+    trace_json: Option<PathBuf>,
 }
 
 impl CallFunction {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace)
+            .with_execution_trace(self.trace_json.is_some());
         let default_account = get_default_account()?;
 
         let transaction = TransactionBuilder::new()
@@ -57,6 +62,7 @@ impl CallFunction {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &self.trace_json,
             out,
         )
     }
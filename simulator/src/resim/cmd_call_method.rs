@@ -29,12 +29,17 @@ pub struct CallMethod {
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Write the execution trace to the given file, as JSON
+    #[clap(long)]
+    trace_json: Option<PathBuf>,
 }
 
 impl CallMethod {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace)
+            .with_execution_trace(self.trace_json.is_some());
         let default_account = get_default_account()?;
 
         let transaction = TransactionBuilder::new()
@@ -55,6 +60,7 @@ impl CallMethod {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &self.trace_json,
             out,
         )
     }
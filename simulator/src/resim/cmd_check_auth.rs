@@ -0,0 +1,125 @@
+#![allow(unused_must_use)]
+
+use clap::Parser;
+use colored::*;
+use radix_engine::model::*;
+use scrypto::constants::ECDSA_TOKEN;
+use scrypto::engine::types::*;
+use scrypto::resource::ResourceMethod;
+use std::collections::BTreeSet;
+
+use crate::resim::*;
+
+/// Check whether a set of proofs would be authorized to call a method, without submitting a transaction
+#[derive(Parser, Debug)]
+pub struct CheckAuth {
+    /// The address of a component or resource manager
+    address: String,
+
+    /// The method name; for resources one of `mint`, `burn`, `withdraw`, `deposit`, `update_metadata`, `update_non_fungible_data`, `recall` or `freeze`
+    method_name: String,
+
+    /// The proofs to present, each in the form of `amount,resource_address` or `#id1,#id2,..,resource_address`, with IDs in the form `show` prints, e.g. `#1u32`
+    proofs: Vec<String>,
+
+    /// The public keys of the signers, separated by comma
+    #[clap(short, long)]
+    signers: Option<String>,
+}
+
+impl CheckAuth {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+
+        let mut proofs = Vec::new();
+        let signers: BTreeSet<NonFungibleId> = parse_public_keys(&self.signers)?
+            .iter()
+            .map(|public_key| NonFungibleId::from_bytes(public_key.to_vec()))
+            .collect();
+        if !signers.is_empty() {
+            proofs.push(HypotheticalProof::Ids(signers, ECDSA_TOKEN));
+        }
+        for proof in &self.proofs {
+            proofs.push(parse_hypothetical_proof(proof)?);
+        }
+
+        let evaluation = if let Ok(component_address) = ComponentAddress::from_str(&self.address) {
+            evaluate_component_method_auth(&ledger, component_address, &self.method_name, &proofs)
+        } else if let Ok(resource_address) = ResourceAddress::from_str(&self.address) {
+            let method = parse_resource_method(&self.method_name)?;
+            evaluate_resource_method_auth(&ledger, resource_address, method, &proofs)
+        } else {
+            return Err(Error::InvalidId(self.address.clone()));
+        }
+        .map_err(Error::AuthEvaluationError)?;
+
+        writeln!(
+            out,
+            "{}: {}",
+            "Authorized".green().bold(),
+            if evaluation.is_authorized() {
+                "Yes".green()
+            } else {
+                "No".red()
+            }
+        );
+        for (authorization, result, trace) in &evaluation.layers {
+            writeln!(out, "{}", "Authorization Trace:".green().bold());
+            match &trace.rule {
+                Some(rule) => writeln!(out, "{}", rule),
+                None => writeln!(
+                    out,
+                    "└─ {} {:?}",
+                    if result.is_ok() {
+                        "✓".green()
+                    } else {
+                        "✗".red()
+                    },
+                    authorization
+                ),
+            };
+        }
+        Ok(())
+    }
+}
+
+fn parse_resource_method(method_name: &str) -> Result<ResourceMethod, Error> {
+    match method_name {
+        "mint" => Ok(ResourceMethod::Mint),
+        "burn" => Ok(ResourceMethod::Burn),
+        "withdraw" => Ok(ResourceMethod::Withdraw),
+        "deposit" => Ok(ResourceMethod::Deposit),
+        "update_metadata" => Ok(ResourceMethod::UpdateMetadata),
+        "update_non_fungible_data" => Ok(ResourceMethod::UpdateNonFungibleData),
+        "recall" => Ok(ResourceMethod::Recall),
+        "freeze" => Ok(ResourceMethod::Freeze),
+        _ => Err(Error::InvalidResourceMethod(method_name.to_owned())),
+    }
+}
+
+fn parse_hypothetical_proof(input: &str) -> Result<HypotheticalProof, Error> {
+    let invalid = || Error::InvalidProof(input.to_owned());
+    let tokens: Vec<&str> = input.trim().split(',').map(str::trim).collect();
+    if tokens.len() < 2 {
+        return Err(invalid());
+    }
+
+    let resource_address =
+        ResourceAddress::from_str(tokens[tokens.len() - 1]).map_err(|_| invalid())?;
+    if tokens[0].starts_with('#') {
+        let ids = tokens[..tokens.len() - 1]
+            .iter()
+            .map(|id| {
+                id.strip_prefix('#')
+                    .and_then(|id| NonFungibleId::from_natural_str(id).ok())
+                    .ok_or_else(invalid)
+            })
+            .collect::<Result<BTreeSet<NonFungibleId>, Error>>()?;
+        Ok(HypotheticalProof::Ids(ids, resource_address))
+    } else if tokens.len() == 2 {
+        let amount = Decimal::from_str(tokens[0]).map_err(|_| invalid())?;
+        Ok(HypotheticalProof::Amount(amount, resource_address))
+    } else {
+        Err(invalid())
+    }
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use colored::*;
+use radix_engine::engine::IdAllocator;
 use rand::Rng;
 use scrypto::prelude::*;
 
@@ -21,6 +22,14 @@ impl GenerateKeyPair {
             hex::encode(private_key.to_bytes()).green()
         )
         .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Virtual account address: {}",
+            IdAllocator::new_virtual_account_address(&public_key)
+                .to_string()
+                .green()
+        )
+        .map_err(Error::IOError)?;
         Ok(())
     }
 }
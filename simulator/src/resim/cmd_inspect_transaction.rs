@@ -0,0 +1,43 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::model::LOCAL_SIMULATOR_NETWORK_ID;
+use std::path::PathBuf;
+use transaction_manifest::decompile;
+
+use crate::resim::*;
+use crate::utils::*;
+
+/// Show the manifest and signers of a transaction file
+#[derive(Parser, Debug)]
+pub struct InspectTransaction {
+    /// The path to a transaction file
+    path: PathBuf,
+}
+
+impl InspectTransaction {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let transaction = read_transaction_file(&self.path)?;
+        let manifest = decompile(&transaction.transaction, LOCAL_SIMULATOR_NETWORK_ID)
+            .map_err(Error::DecompileError)?;
+
+        writeln!(out, "{}:", "Manifest".green().bold()).map_err(Error::IOError)?;
+        write!(out, "{}", manifest).map_err(Error::IOError)?;
+
+        writeln!(out, "{}:", "Signers".green().bold()).map_err(Error::IOError)?;
+        for (last, (public_key, valid)) in transaction.signers().iter().identify_last() {
+            if *valid {
+                writeln!(out, "{} {}", list_item_prefix(last), public_key)
+            } else {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    list_item_prefix(last),
+                    public_key,
+                    "(invalid signature)".red()
+                )
+            }
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
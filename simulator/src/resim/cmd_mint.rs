@@ -45,6 +45,7 @@ impl Mint {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &None,
             out,
         )
     }
@@ -37,7 +37,14 @@ impl NewAccount {
                     builder.new_account_with_resource(&withdraw_auth, bucket_id)
                 })
                 .build_with_no_nonce();
-            process_transaction(&mut executor, transaction, &None, &Some(path.clone()), out)?;
+            process_transaction(
+                &mut executor,
+                transaction,
+                &None,
+                &Some(path.clone()),
+                &None,
+                out,
+            )?;
             writeln!(out, "A manifest has been produced for the following key pair. To complete account creation, you will need to run the manifest!").map_err(Error::IOError)?;
             writeln!(out, "Public key: {}", public_key.to_string().green())
                 .map_err(Error::IOError)?;
@@ -75,6 +75,7 @@ impl NewBadgeFixed {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &None,
             out,
         )
     }
@@ -73,6 +73,7 @@ impl NewBadgeMutable {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &None,
             out,
         )
     }
@@ -75,6 +75,7 @@ impl NewTokenFixed {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &None,
             out,
         )
     }
@@ -73,6 +73,7 @@ impl NewTokenMutable {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &None,
             out,
         )
     }
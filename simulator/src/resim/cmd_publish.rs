@@ -45,7 +45,14 @@ impl Publish {
             let transaction = TransactionBuilder::new()
                 .publish_package(code.as_ref())
                 .build_with_no_nonce();
-            process_transaction(&mut executor, transaction, &None, &Some(path.clone()), out)?;
+            process_transaction(
+                &mut executor,
+                transaction,
+                &None,
+                &Some(path.clone()),
+                &None,
+                out,
+            )?;
         } else {
             self.store_package(out, &code)?;
         }
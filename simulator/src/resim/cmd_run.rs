@@ -1,4 +1,5 @@
 use clap::Parser;
+use radix_engine::model::LOCAL_SIMULATOR_NETWORK_ID;
 use regex::{Captures, Regex};
 use std::env;
 use std::path::PathBuf;
@@ -18,6 +19,10 @@ pub struct Run {
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Write the execution trace to the given file, as JSON
+    #[clap(long)]
+    trace_json: Option<PathBuf>,
 }
 
 impl Run {
@@ -31,12 +36,21 @@ impl Run {
 
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace)
+            .with_execution_trace(self.trace_json.is_some());
         let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
         let pre_processed_manifest = Self::pre_process_manifest(&manifest);
         let transaction =
-            transaction_manifest::compile(&pre_processed_manifest).map_err(Error::CompileError)?;
-        process_transaction(&mut executor, transaction, &self.signing_keys, &None, out)
+            transaction_manifest::compile(&pre_processed_manifest, LOCAL_SIMULATOR_NETWORK_ID)
+                .map_err(Error::CompileError)?;
+        process_transaction(
+            &mut executor,
+            transaction,
+            &self.signing_keys,
+            &None,
+            &self.trace_json,
+            out,
+        )
     }
 }
 
@@ -0,0 +1,43 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::engine::types::*;
+
+use crate::resim::*;
+
+/// Replace the key of the default account with an owner badge
+#[derive(Parser, Debug)]
+pub struct SecurifyAccount {
+    /// The component address of the account receiving the owner badge
+    badge_recipient: ComponentAddress,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl SecurifyAccount {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let transaction = TransactionBuilder::new()
+            .securify_account(get_default_account()?)
+            .call_method_with_all_resources(self.badge_recipient, "deposit_batch")
+            .build_with_no_nonce();
+        process_transaction(
+            &mut executor,
+            transaction,
+            &self.signing_keys,
+            &self.manifest,
+            &None,
+            out,
+        )
+    }
+}
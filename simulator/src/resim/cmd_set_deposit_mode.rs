@@ -0,0 +1,42 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::component::AccountDepositMode;
+
+use crate::resim::*;
+
+/// Set which resources the default account accepts as deposits
+#[derive(Parser, Debug)]
+pub struct SetDepositMode {
+    /// The deposit mode, one of `accept-all`, `allow-list` or `deny-list`
+    mode: AccountDepositMode,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl SetDepositMode {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let transaction = TransactionBuilder::new()
+            .set_account_deposit_mode(self.mode, get_default_account()?)
+            .build_with_no_nonce();
+        process_transaction(
+            &mut executor,
+            transaction,
+            &self.signing_keys,
+            &self.manifest,
+            &None,
+            out,
+        )
+    }
+}
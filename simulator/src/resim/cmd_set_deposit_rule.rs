@@ -0,0 +1,52 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::component::ResourceDepositRule;
+use scrypto::engine::types::*;
+
+use crate::resim::*;
+
+/// Allow or deny deposits of a resource into the default account
+#[derive(Parser, Debug)]
+pub struct SetDepositRule {
+    /// The resource address
+    resource_address: ResourceAddress,
+
+    /// The deposit rule, either `allow` or `deny`; removes the rule if omitted
+    rule: Option<ResourceDepositRule>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl SetDepositRule {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let account = get_default_account()?;
+        let transaction = match self.rule {
+            Some(rule) => TransactionBuilder::new()
+                .set_account_resource_deposit_rule(self.resource_address, rule, account)
+                .build_with_no_nonce(),
+            None => TransactionBuilder::new()
+                .remove_account_resource_deposit_rule(self.resource_address, account)
+                .build_with_no_nonce(),
+        };
+        process_transaction(
+            &mut executor,
+            transaction,
+            &self.signing_keys,
+            &self.manifest,
+            &None,
+            out,
+        )
+    }
+}
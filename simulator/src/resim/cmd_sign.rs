@@ -0,0 +1,28 @@
+use clap::Parser;
+use colored::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Add a signature to a transaction file
+#[derive(Parser, Debug)]
+pub struct Sign {
+    /// The path to a transaction file
+    path: PathBuf,
+
+    /// The private key used for signing
+    #[clap(short, long)]
+    key: String,
+}
+
+impl Sign {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut transaction = read_transaction_file(&self.path)?;
+        for sk in parse_signing_keys(&Some(self.key.clone()))? {
+            transaction.sign(&sk);
+            writeln!(out, "Signed by: {}", sk.public_key().to_string().green())
+                .map_err(Error::IOError)?;
+        }
+        write_transaction_file(&self.path, &transaction)
+    }
+}
@@ -0,0 +1,28 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Submit a signed transaction file
+#[derive(Parser, Debug)]
+pub struct Submit {
+    /// The path to a transaction file
+    path: PathBuf,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl Submit {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        let signed = read_transaction_file(&self.path)?.into_signed();
+        let receipt = executor
+            .validate_and_execute(&signed)
+            .map_err(Error::TransactionValidationError)?;
+        writeln!(out, "{:?}", receipt).map_err(Error::IOError)?;
+        receipt.result.map_err(Error::TransactionExecutionError)
+    }
+}
@@ -46,6 +46,7 @@ impl Transfer {
             transaction,
             &self.signing_keys,
             &self.manifest,
+            &None,
             out,
         )
     }
@@ -1,6 +1,7 @@
 use std::io;
 
 use radix_engine::errors::*;
+use radix_engine::model::*;
 use radix_engine::transaction::*;
 use sbor::*;
 
@@ -43,4 +44,14 @@ pub enum Error {
     InvalidId(String),
 
     InvalidPrivateKey,
+
+    InvalidPublicKey,
+
+    InvalidResourceMethod(String),
+
+    InvalidProof(String),
+
+    AuthEvaluationError(AuthEvaluationError),
+
+    UnsupportedTransactionFileVersion(u8),
 }
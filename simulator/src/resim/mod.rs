@@ -1,7 +1,10 @@
+mod cmd_build_transaction;
 mod cmd_call_function;
 mod cmd_call_method;
+mod cmd_check_auth;
 mod cmd_export_abi;
 mod cmd_generate_key_pair;
+mod cmd_inspect_transaction;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
@@ -11,19 +14,27 @@ mod cmd_new_token_mutable;
 mod cmd_publish;
 mod cmd_reset;
 mod cmd_run;
+mod cmd_securify_account;
 mod cmd_set_current_epoch;
 mod cmd_set_default_account;
+mod cmd_set_deposit_mode;
+mod cmd_set_deposit_rule;
 mod cmd_show;
 mod cmd_show_configs;
 mod cmd_show_ledger;
+mod cmd_sign;
+mod cmd_submit;
 mod cmd_transfer;
 mod config;
 mod error;
 
+pub use cmd_build_transaction::*;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
+pub use cmd_check_auth::*;
 pub use cmd_export_abi::*;
 pub use cmd_generate_key_pair::*;
+pub use cmd_inspect_transaction::*;
 pub use cmd_mint::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
@@ -33,11 +44,16 @@ pub use cmd_new_token_mutable::*;
 pub use cmd_publish::*;
 pub use cmd_reset::*;
 pub use cmd_run::*;
+pub use cmd_securify_account::*;
 pub use cmd_set_current_epoch::*;
 pub use cmd_set_default_account::*;
+pub use cmd_set_deposit_mode::*;
+pub use cmd_set_deposit_rule::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
 pub use cmd_show_ledger::*;
+pub use cmd_sign::*;
+pub use cmd_submit::*;
 pub use cmd_transfer::*;
 pub use config::*;
 pub use error::*;
@@ -54,9 +70,11 @@ use scrypto::crypto::*;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::str::FromStr;
 use transaction_manifest::decompile;
 
 use crate::ledger::*;
+use crate::utils::*;
 
 /// Build fast, reward everyone, and scale without friction
 #[derive(Parser, Debug)]
@@ -74,10 +92,13 @@ impl ResimCli {
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    BuildTransaction(BuildTransaction),
     CallFunction(CallFunction),
     CallMethod(CallMethod),
+    CheckAuth(CheckAuth),
     ExportAbi(ExportAbi),
     GenerateKeyPair(GenerateKeyPair),
+    InspectTransaction(InspectTransaction),
     Mint(Mint),
     NewAccount(NewAccount),
     NewBadgeFixed(NewBadgeFixed),
@@ -87,11 +108,16 @@ pub enum Command {
     Publish(Publish),
     Reset(Reset),
     Run(Run),
+    SecurifyAccount(SecurifyAccount),
     SetCurrentEpoch(SetCurrentEpoch),
     SetDefaultAccount(SetDefaultAccount),
+    SetDepositMode(SetDepositMode),
+    SetDepositRule(SetDepositRule),
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
     Show(Show),
+    Sign(Sign),
+    Submit(Submit),
     Transfer(Transfer),
 }
 
@@ -101,10 +127,13 @@ pub fn run() -> Result<(), Error> {
     let mut out = std::io::stdout();
 
     match cli.command {
+        Command::BuildTransaction(cmd) => cmd.run(&mut out),
         Command::CallFunction(cmd) => cmd.run(&mut out),
         Command::CallMethod(cmd) => cmd.run(&mut out),
+        Command::CheckAuth(cmd) => cmd.run(&mut out),
         Command::ExportAbi(cmd) => cmd.run(&mut out),
         Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
+        Command::InspectTransaction(cmd) => cmd.run(&mut out),
         Command::Mint(cmd) => cmd.run(&mut out),
         Command::NewAccount(cmd) => cmd.run(&mut out),
         Command::NewBadgeFixed(cmd) => cmd.run(&mut out),
@@ -114,11 +143,16 @@ pub fn run() -> Result<(), Error> {
         Command::Publish(cmd) => cmd.run(&mut out),
         Command::Reset(cmd) => cmd.run(&mut out),
         Command::Run(cmd) => cmd.run(&mut out),
+        Command::SecurifyAccount(cmd) => cmd.run(&mut out),
         Command::SetCurrentEpoch(cmd) => cmd.run(&mut out),
         Command::SetDefaultAccount(cmd) => cmd.run(&mut out),
+        Command::SetDepositMode(cmd) => cmd.run(&mut out),
+        Command::SetDepositRule(cmd) => cmd.run(&mut out),
         Command::ShowConfigs(cmd) => cmd.run(&mut out),
         Command::ShowLedger(cmd) => cmd.run(&mut out),
         Command::Show(cmd) => cmd.run(&mut out),
+        Command::Sign(cmd) => cmd.run(&mut out),
+        Command::Submit(cmd) => cmd.run(&mut out),
         Command::Transfer(cmd) => cmd.run(&mut out),
     }
 }
@@ -128,6 +162,7 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
     mut transaction: Transaction,
     signing_keys: &Option<String>,
     manifest_path: &Option<PathBuf>,
+    trace_json_path: &Option<PathBuf>,
     out: &mut O,
 ) -> Result<(), Error> {
     match manifest_path {
@@ -135,7 +170,7 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
             if env::var(ENV_DISABLE_MANIFEST_OUTPUT).is_ok() {
                 Ok(())
             } else {
-                let manifest = decompile(&transaction).map_err(Error::DecompileError)?;
+                let manifest = decompile(&transaction, LOCAL_SIMULATOR_NETWORK_ID).map_err(Error::DecompileError)?;
                 fs::write(path, manifest).map_err(Error::IOError)
             }
         }
@@ -152,6 +187,12 @@ pub fn process_transaction<L: SubstateStore, O: std::io::Write>(
                 .validate_and_execute(&signed)
                 .map_err(Error::TransactionValidationError)?;
             writeln!(out, "{:?}", receipt).map_err(Error::IOError)?;
+            if let (Some(path), Some(execution_trace)) = (trace_json_path, &receipt.execution_trace)
+            {
+                let json = serde_json::to_string_pretty(&execution_trace_to_json(execution_trace))
+                    .map_err(Error::JSONError)?;
+                fs::write(path, json).map_err(Error::IOError)?;
+            }
             receipt.result.map_err(Error::TransactionExecutionError)
         }
     }
@@ -175,3 +216,33 @@ pub fn parse_signing_keys(signing_keys: &Option<String>) -> Result<Vec<EcdsaPriv
 
     Ok(private_keys)
 }
+
+pub fn parse_public_keys(public_keys: &Option<String>) -> Result<Vec<EcdsaPublicKey>, Error> {
+    let public_keys = if let Some(keys) = public_keys {
+        keys.split(",")
+            .map(str::trim)
+            .map(|key| EcdsaPublicKey::from_str(key).map_err(|_| Error::InvalidPublicKey))
+            .collect::<Result<Vec<EcdsaPublicKey>, Error>>()?
+    } else {
+        vec![get_default_private_key()?.public_key()]
+    };
+
+    Ok(public_keys)
+}
+
+pub fn read_transaction_file(path: &PathBuf) -> Result<PartiallySignedTransaction, Error> {
+    let transaction =
+        PartiallySignedTransaction::from_slice(&fs::read(path).map_err(Error::IOError)?)
+            .map_err(Error::DataError)?;
+    if transaction.version != PartiallySignedTransaction::VERSION {
+        return Err(Error::UnsupportedTransactionFileVersion(transaction.version));
+    }
+    Ok(transaction)
+}
+
+pub fn write_transaction_file(
+    path: &PathBuf,
+    transaction: &PartiallySignedTransaction,
+) -> Result<(), Error> {
+    fs::write(path, transaction.to_vec()).map_err(Error::IOError)
+}
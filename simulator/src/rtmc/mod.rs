@@ -1,4 +1,5 @@
 use clap::Parser;
+use radix_engine::model::LOCAL_SIMULATOR_NETWORK_ID;
 use scrypto::buffer::scrypto_encode;
 use std::path::PathBuf;
 use transaction_manifest::compile;
@@ -11,6 +12,10 @@ pub struct Args {
     #[clap(short, long)]
     output: PathBuf,
 
+    /// The id of the network the transaction is intended for
+    #[clap(long, default_value_t = LOCAL_SIMULATOR_NETWORK_ID)]
+    network_id: u8,
+
     /// Input file
     #[clap(required = true)]
     input: PathBuf,
@@ -26,7 +31,7 @@ pub fn run() -> Result<(), Error> {
     let args = Args::parse();
 
     let content = std::fs::read_to_string(args.input).map_err(Error::IoError)?;
-    let transaction = compile(&content).map_err(Error::CompileError)?;
+    let transaction = compile(&content, args.network_id).map_err(Error::CompileError)?;
     std::fs::write(args.output, scrypto_encode(&transaction)).map_err(Error::IoError)?;
 
     Ok(())
@@ -0,0 +1,84 @@
+use radix_engine::engine::*;
+use serde_json::{json, Value};
+
+/// Converts an execution trace into JSON, for consumption by external tooling.
+pub fn execution_trace_to_json(trace: &ExecutionTrace) -> Value {
+    json!({
+        "calls": trace.calls.iter().map(traced_call_to_json).collect::<Vec<Value>>(),
+        "substates": trace.substates.iter().map(substate_access_to_json).collect::<Vec<Value>>(),
+    })
+}
+
+fn traced_call_to_json(call: &TracedCall) -> Value {
+    json!({
+        "depth": call.depth,
+        "snode_ref": format!("{:?}", call.snode_ref),
+        "function": call.function,
+        "actor": call.actor.as_ref().map(|actor| json!({
+            "package_address": actor.package_address().to_string(),
+            "blueprint_name": actor.blueprint_name(),
+            "component_address": actor.component_address().map(|address| address.to_string()),
+        })),
+        "args": call.args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>(),
+        "output": call.output.as_ref().map(|output| output.to_string()),
+        "buckets_in": traced_resources_to_json(&call.buckets_in),
+        "proofs_in": traced_resources_to_json(&call.proofs_in),
+        "buckets_out": traced_resources_to_json(&call.buckets_out),
+        "proofs_out": traced_resources_to_json(&call.proofs_out),
+        "substates": call.substates.iter().map(substate_access_to_json).collect::<Vec<Value>>(),
+        "calls": call.calls.iter().map(traced_call_to_json).collect::<Vec<Value>>(),
+    })
+}
+
+fn traced_resources_to_json(resources: &[TracedResource]) -> Vec<Value> {
+    resources
+        .iter()
+        .map(|resource| {
+            json!({
+                "id": resource.id,
+                "resource_address": resource.resource_address.to_string(),
+                "amount": resource.amount.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn substate_access_to_json(access: &SubstateAccess) -> Value {
+    let (kind, substate_id) = match access {
+        SubstateAccess::Read(substate_id) => ("read", substate_id),
+        SubstateAccess::Write(substate_id) => ("write", substate_id),
+    };
+    let substate = match substate_id {
+        SubstateId::Package(package_address) => json!({
+            "type": "package",
+            "package_address": package_address.to_string(),
+        }),
+        SubstateId::Component(component_address) => json!({
+            "type": "component",
+            "component_address": component_address.to_string(),
+        }),
+        SubstateId::ResourceManager(resource_address) => json!({
+            "type": "resource_manager",
+            "resource_address": resource_address.to_string(),
+        }),
+        SubstateId::Vault(component_address, vault_id) => json!({
+            "type": "vault",
+            "component_address": component_address.to_string(),
+            "vault_id": format!("{:?}", vault_id),
+        }),
+        SubstateId::NonFungible(non_fungible_address) => json!({
+            "type": "non_fungible",
+            "non_fungible_address": non_fungible_address.to_string(),
+        }),
+        SubstateId::LazyMapEntry(component_address, lazy_map_id, key) => json!({
+            "type": "lazy_map_entry",
+            "component_address": component_address.to_string(),
+            "lazy_map_id": format!("{:?}", lazy_map_id),
+            "key": hex::encode(key),
+        }),
+    };
+    json!({
+        "access": kind,
+        "substate": substate,
+    })
+}
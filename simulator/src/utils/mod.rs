@@ -1,7 +1,9 @@
 mod cargo;
 mod display;
+mod execution_trace;
 mod iter;
 
 pub use cargo::{build_package, fmt_package, test_package, CargoExecutionError};
 pub use display::list_item_prefix;
+pub use execution_trace::execution_trace_to_json;
 pub use iter::{IdentifyLast, Iter};
@@ -14,6 +14,7 @@ blueprint! {
             ResourceBuilder::new_non_fungible()
                 .metadata("name", "Cars!")
                 .metadata("description", "Fast Cars")
+                .id_type(NonFungibleIdType::UUID)
                 .initial_supply(vec![
                     (NonFungibleId::random(), Car {
                         manufacturer: "Ford".to_string(),
@@ -23,6 +23,12 @@ token_address=`$resim new-token-mutable $minter_badge | awk '/Resource:/ {print
 $resim mint 777 $token_address $minter_badge
 $resim transfer 111 $token_address $account2
 
+# Test - account deposit policies
+$resim set-deposit-mode allow-list
+$resim set-deposit-rule $token_address allow
+$resim set-deposit-rule $token_address
+$resim set-deposit-mode accept-all
+
 # Test - publish, call-funciton and call-method
 package=`$resim publish ../examples/hello-world | awk '/Package:/ {print $NF}'`
 component=`$resim call-function $package Hello instantiate_hello | awk '/Component:/ {print $NF}'`
@@ -50,8 +56,20 @@ $resim run ./target/temp3.rtm
 $resim generate-key-pair
 $resim run ./target/temp2.rtm --signing-keys 4fc0db017bf9b80743b7151fee3f04bad817f2d8d7e34ae96c022fe7451b0ea3,329a27258d7e9496c42a110571e6ba0d47f2bda8bd610e9777d16853ab145b0c
 
+# Test - sign a transaction offline, one key at a time
+$resim build-transaction ./target/temp2.rtm ./target/temp2.tx --signers 0423488bf37afc2b6eb9be2edaf2981ea76a7755303ac35853129e7c68c49030327cdaa536da34809bc0a77e5bf0332379ed93a1c6eee35ddd4f8cbb805d4580ad,04da9f1b2fe0aff2d133bbb93550ffb97761d1911ff4a78a0bf3aca6f2c28e9dd74f9b10b588a0a2414950dd468192e43343cf681153dc05354a4e3bc97804afb1
+$resim sign ./target/temp2.tx --key 4fc0db017bf9b80743b7151fee3f04bad817f2d8d7e34ae96c022fe7451b0ea3
+$resim sign ./target/temp2.tx --key 329a27258d7e9496c42a110571e6ba0d47f2bda8bd610e9777d16853ab145b0c
+$resim inspect-transaction ./target/temp2.tx
+$resim submit ./target/temp2.tx
+
+# Test - lock fee
+$resim call-method $account lock_fee 10
+
 # Test - nft
 package=`$resim publish ./tests/nft | awk '/Package:/ {print $NF}'`
 $resim call-function $package Foo nfts
 $resim show $account
 
+# Test - securify account
+$resim securify-account $account2
@@ -1,6 +1,15 @@
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Transaction {
     pub instructions: Vec<Instruction>,
+    pub subintents: Vec<Subintent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subintent {
+    pub nonce: Value,
+    pub expiry_epoch: Value,
+    pub signatures: Value,
+    pub instructions: Vec<Instruction>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -40,6 +49,55 @@ pub enum Instruction {
         resource_address: Value,
     },
 
+    AssertVaultBalance {
+        component_address: Value,
+        path: Value,
+        condition: Value,
+    },
+
+    AssertAccountBalance {
+        component_address: Value,
+        resource_address: Value,
+        condition: Value,
+    },
+
+    AssertResourceSupply {
+        resource_address: Value,
+        condition: Value,
+    },
+
+    AssertComponentState {
+        component_address: Value,
+        path: Value,
+        value: Value,
+    },
+
+    AssertLazyMapEntry {
+        component_address: Value,
+        path: Value,
+        key: Value,
+        value: Value,
+    },
+
+    RecallFromVault {
+        component_address: Value,
+        path: Value,
+        amount: Value,
+        resource_address: Value,
+    },
+
+    FreezeVault {
+        component_address: Value,
+        path: Value,
+        resource_address: Value,
+    },
+
+    UnfreezeVault {
+        component_address: Value,
+        path: Value,
+        resource_address: Value,
+    },
+
     PopFromAuthZone {
         new_proof: Value,
     },
@@ -102,6 +160,19 @@ pub enum Instruction {
     PublishPackage {
         code: Value,
     },
+
+    CreateVirtualAccount {
+        public_key: Value,
+    },
+
+    YieldToSubintent {
+        subintent_index: Value,
+        buckets: Vec<Value>,
+    },
+
+    ReturnToParent {
+        buckets: Vec<Value>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -144,6 +215,7 @@ pub enum Type {
     Proof,
     NonFungibleId,
     NonFungibleAddress,
+    EcdsaPublicKey,
 
     /* Bytes is a convenient way of producing `Vec<u8>` */
     Bytes,
@@ -186,6 +258,7 @@ pub enum Value {
     Proof(Box<Value>),
     NonFungibleId(Box<Value>),
     NonFungibleAddress(Box<Value>),
+    EcdsaPublicKey(Box<Value>),
 
     Bytes(Vec<u8>),
 }
@@ -226,6 +299,7 @@ impl Value {
             Value::Proof(_) => Type::Proof,
             Value::NonFungibleId(_) => Type::NonFungibleId,
             Value::NonFungibleAddress(_) => Type::NonFungibleAddress,
+            Value::EcdsaPublicKey(_) => Type::EcdsaPublicKey,
             Value::Bytes(_) => Type::Vec,
         }
     }
@@ -8,14 +8,60 @@ use scrypto::values::*;
 pub enum DecompileError {
     IdValidatorError(IdValidatorError),
     ParseScryptoValueError(ParseScryptoValueError),
+    NetworkMismatch { expected: u8, actual: u8 },
 }
 
-pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
+/// Decompiles a transaction intended for the given network.
+///
+/// The network is not part of the manifest, so compiling the manifest for the same network
+/// yields the transaction again.
+pub fn decompile(tx: &Transaction, network_id: u8) -> Result<String, DecompileError> {
+    if tx.network_id != network_id {
+        return Err(DecompileError::NetworkMismatch {
+            expected: network_id,
+            actual: tx.network_id,
+        });
+    }
+
+    let mut buf = String::new();
+    for signed_subintent in &tx.subintents {
+        let subintent = &signed_subintent.subintent;
+        let signatures: Vec<String> = signed_subintent
+            .signatures
+            .iter()
+            .map(|(pk, sig)| {
+                format!(
+                    "Tuple(EcdsaPublicKey(\"{}\"), Bytes(\"{}\"))",
+                    pk,
+                    hex::encode(sig.to_vec())
+                )
+            })
+            .collect();
+        buf.push_str(&format!(
+            "SUBINTENT {}u64 {}u64 Vec<Tuple>({}) {{\n",
+            subintent.nonce,
+            subintent.expiry_epoch,
+            signatures.join(", ")
+        ));
+        for line in decompile_instructions(&subintent.instructions)?.lines() {
+            buf.push_str(&format!("    {}\n", line));
+        }
+        buf.push_str("}\n");
+    }
+    buf.push_str(&decompile_instructions(&tx.instructions)?);
+    Ok(buf)
+}
+
+pub fn decompile_subintent(subintent: &Subintent) -> Result<String, DecompileError> {
+    decompile_instructions(&subintent.instructions)
+}
+
+fn decompile_instructions(instructions: &[Instruction]) -> Result<String, DecompileError> {
     let mut buf = String::new();
     let mut id_validator = IdValidator::new();
     let mut buckets = HashMap::<BucketId, String>::new();
     let mut proofs = HashMap::<ProofId, String>::new();
-    for inst in &tx.instructions {
+    for inst in instructions {
         match inst.clone() {
             Instruction::TakeFromWorktop { resource_address } => {
                 let bucket_id = id_validator
@@ -54,7 +100,7 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                 buf.push_str(&format!(
                     "TAKE_FROM_WORKTOP_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\") Bucket(\"{}\");\n",
                     ids.iter()
-                    .map(|k| format!("NonFungibleId(\"{}\")", k))
+                    .map(|k| format!("NonFungibleId({})", k.to_natural_string()))
                     .collect::<Vec<String>>()
                     .join(", "),
                     resource_address, name
@@ -94,12 +140,116 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                 buf.push_str(&format!(
                     "ASSERT_WORKTOP_CONTAINS_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\");\n",
                     ids.iter()
-                        .map(|k| format!("NonFungibleId(\"{}\")", k))
+                        .map(|k| format!("NonFungibleId({})", k.to_natural_string()))
                         .collect::<Vec<String>>()
                         .join(", "),
                     resource_address
                 ));
             }
+            Instruction::AssertVaultBalance {
+                component_address,
+                path,
+                condition,
+            } => {
+                buf.push_str(&format!(
+                    "ASSERT_VAULT_BALANCE ComponentAddress(\"{}\") \"{}\" {};\n",
+                    component_address,
+                    path,
+                    format_amount_condition(&condition)
+                ));
+            }
+            Instruction::AssertAccountBalance {
+                component_address,
+                resource_address,
+                condition,
+            } => {
+                buf.push_str(&format!(
+                    "ASSERT_ACCOUNT_BALANCE ComponentAddress(\"{}\") ResourceAddress(\"{}\") {};\n",
+                    component_address,
+                    resource_address,
+                    format_amount_condition(&condition)
+                ));
+            }
+            Instruction::AssertResourceSupply {
+                resource_address,
+                condition,
+            } => {
+                buf.push_str(&format!(
+                    "ASSERT_RESOURCE_SUPPLY ResourceAddress(\"{}\") {};\n",
+                    resource_address,
+                    format_amount_condition(&condition)
+                ));
+            }
+            Instruction::AssertComponentState {
+                component_address,
+                path,
+                value,
+            } => {
+                let value = ScryptoValue::from_slice(&value)
+                    .map_err(DecompileError::ParseScryptoValueError)?;
+                buf.push_str(&format!(
+                    "ASSERT_COMPONENT_STATE ComponentAddress(\"{}\") \"{}\" {};\n",
+                    component_address,
+                    path,
+                    value.to_string()
+                ));
+            }
+            Instruction::AssertLazyMapEntry {
+                component_address,
+                path,
+                key,
+                value,
+            } => {
+                let key = ScryptoValue::from_slice(&key)
+                    .map_err(DecompileError::ParseScryptoValueError)?;
+                let value = match value {
+                    Some(value) => format!(
+                        "Some({})",
+                        ScryptoValue::from_slice(&value)
+                            .map_err(DecompileError::ParseScryptoValueError)?
+                            .to_string()
+                    ),
+                    None => "None".to_string(),
+                };
+                buf.push_str(&format!(
+                    "ASSERT_LAZY_MAP_ENTRY ComponentAddress(\"{}\") \"{}\" {} {};\n",
+                    component_address,
+                    path,
+                    key.to_string(),
+                    value
+                ));
+            }
+            Instruction::RecallFromVault {
+                component_address,
+                path,
+                amount,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "RECALL_FROM_VAULT ComponentAddress(\"{}\") \"{}\" Decimal(\"{}\") ResourceAddress(\"{}\");\n",
+                    component_address, path, amount, resource_address
+                ));
+            }
+            Instruction::FreezeVault {
+                component_address,
+                path,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "FREEZE_VAULT ComponentAddress(\"{}\") \"{}\" ResourceAddress(\"{}\");\n",
+                    component_address, path, resource_address
+                ));
+            }
+            Instruction::UnfreezeVault {
+                component_address,
+                path,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "UNFREEZE_VAULT ComponentAddress(\"{}\") \"{}\" ResourceAddress(\"{}\");\n",
+                    component_address, path, resource_address
+                ));
+            }
             Instruction::PopFromAuthZone => {
                 let proof_id = id_validator
                     .new_proof(ProofKind::AuthZoneProof)
@@ -160,7 +310,7 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                 proofs.insert(proof_id, name.clone());
                 buf.push_str(&format!(
                     "CREATE_PROOF_FROM_AUTH_ZONE_BY_IDS TreeSet<NonFungibleId>({}) ResourceAddress(\"{}\") Proof(\"{}\");\n",ids.iter()
-                    .map(|k| format!("NonFungibleId(\"{}\")", k))
+                    .map(|k| format!("NonFungibleId({})", k.to_natural_string()))
                     .collect::<Vec<String>>()
                     .join(", "),
                     resource_address, name
@@ -267,6 +417,47 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     hex::encode(&code)
                 ));
             }
+            Instruction::CreateVirtualAccount { public_key } => {
+                buf.push_str(&format!(
+                    "CREATE_VIRTUAL_ACCOUNT EcdsaPublicKey(\"{}\");\n",
+                    public_key
+                ));
+            }
+            Instruction::YieldToSubintent {
+                subintent_index,
+                bucket_ids,
+            } => {
+                buf.push_str(&format!("YIELD_TO_SUBINTENT {}u32", subintent_index));
+                for bucket_id in bucket_ids {
+                    id_validator
+                        .drop_bucket(bucket_id)
+                        .map_err(DecompileError::IdValidatorError)?;
+                    buf.push_str(&format!(
+                        " Bucket({})",
+                        buckets
+                            .get(&bucket_id)
+                            .map(|name| format!("\"{}\"", name))
+                            .unwrap_or(format!("{}u32", bucket_id))
+                    ));
+                }
+                buf.push_str(";\n");
+            }
+            Instruction::ReturnToParent { bucket_ids } => {
+                buf.push_str("RETURN_TO_PARENT");
+                for bucket_id in bucket_ids {
+                    id_validator
+                        .drop_bucket(bucket_id)
+                        .map_err(DecompileError::IdValidatorError)?;
+                    buf.push_str(&format!(
+                        " Bucket({})",
+                        buckets
+                            .get(&bucket_id)
+                            .map(|name| format!("\"{}\"", name))
+                            .unwrap_or(format!("{}u32", bucket_id))
+                    ));
+                }
+                buf.push_str(";\n");
+            }
             Instruction::Nonce { .. } => {
                 // TODO: add support for this
             }
@@ -276,18 +467,132 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
     Ok(buf)
 }
 
+fn format_amount_condition(condition: &AmountCondition) -> String {
+    match condition {
+        AmountCondition::Exactly(amount) => format!("Enum(\"Exactly\", Decimal(\"{}\"))", amount),
+        AmountCondition::AtLeast(amount) => format!("Enum(\"AtLeast\", Decimal(\"{}\"))", amount),
+        AmountCondition::AtMost(amount) => format!("Enum(\"AtMost\", Decimal(\"{}\"))", amount),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::compile;
+    use scrypto::crypto::EcdsaPrivateKey;
 
     #[test]
     fn test_decompile() {
-        let tx = compile(include_str!("../examples/complex.rtm")).unwrap();
+        let tx = compile(
+            include_str!("../examples/complex.rtm"),
+            LOCAL_SIMULATOR_NETWORK_ID,
+        )
+        .unwrap();
 
-        let manifest = &decompile(&tx).unwrap();
+        let manifest = &decompile(&tx, LOCAL_SIMULATOR_NETWORK_ID).unwrap();
         println!("{}", manifest);
 
-        assert_eq!(compile(manifest).unwrap(), tx);
+        assert_eq!(compile(manifest, LOCAL_SIMULATOR_NETWORK_ID).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_decompile_keeps_network() {
+        let tx = compile(include_str!("../examples/complex.rtm"), 1).unwrap();
+
+        let manifest = &decompile(&tx, 1).unwrap();
+        assert_eq!(compile(manifest, 1).unwrap(), tx);
+        assert!(matches!(
+            decompile(&tx, LOCAL_SIMULATOR_NETWORK_ID),
+            Err(DecompileError::NetworkMismatch {
+                expected: LOCAL_SIMULATOR_NETWORK_ID,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decompile_assertions() {
+        let tx = compile(
+            r#"
+ASSERT_VAULT_BALANCE ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "vaults/0" Enum("AtLeast", Decimal("10"));
+ASSERT_ACCOUNT_BALANCE ComponentAddress("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de") ResourceAddress("030000000000000000000000000000000000000000000000000004") Enum("Exactly", Decimal("5"));
+ASSERT_RESOURCE_SUPPLY ResourceAddress("030000000000000000000000000000000000000000000000000004") Enum("AtMost", Decimal("1000"));
+ASSERT_COMPONENT_STATE ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "count" 5u32;
+ASSERT_LAZY_MAP_ENTRY ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "names" 1u32 Some("hello");
+ASSERT_LAZY_MAP_ENTRY ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "names" 2u32 None;
+"#,
+            LOCAL_SIMULATOR_NETWORK_ID,
+        )
+        .unwrap();
+
+        let manifest = &decompile(&tx, LOCAL_SIMULATOR_NETWORK_ID).unwrap();
+        assert_eq!(compile(manifest, LOCAL_SIMULATOR_NETWORK_ID).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_decompile_recall_and_freeze() {
+        let tx = compile(
+            r#"
+FREEZE_VAULT ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "vaults/0" ResourceAddress("030000000000000000000000000000000000000000000000000004");
+RECALL_FROM_VAULT ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "vaults/0" Decimal("10") ResourceAddress("030000000000000000000000000000000000000000000000000004");
+UNFREEZE_VAULT ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "vaults/0" ResourceAddress("030000000000000000000000000000000000000000000000000004");
+"#,
+            LOCAL_SIMULATOR_NETWORK_ID,
+        )
+        .unwrap();
+
+        let manifest = &decompile(&tx, LOCAL_SIMULATOR_NETWORK_ID).unwrap();
+        assert_eq!(compile(manifest, LOCAL_SIMULATOR_NETWORK_ID).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_decompile_create_virtual_account() {
+        let tx = compile(
+            r#"
+CREATE_VIRTUAL_ACCOUNT EcdsaPublicKey("046b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c2964fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+CALL_METHOD_WITH_ALL_RESOURCES ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1") "deposit_batch";
+"#,
+            LOCAL_SIMULATOR_NETWORK_ID,
+        )
+        .unwrap();
+
+        let manifest = &decompile(&tx, LOCAL_SIMULATOR_NETWORK_ID).unwrap();
+        assert_eq!(compile(manifest, LOCAL_SIMULATOR_NETWORK_ID).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_decompile_subintent() {
+        let subintent = crate::compile_subintent(
+            r#"
+TAKE_FROM_WORKTOP ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("xrd");
+RETURN_TO_PARENT Bucket("xrd");
+"#,
+            1,
+            100,
+        )
+        .unwrap();
+
+        let manifest = &decompile_subintent(&subintent).unwrap();
+        assert_eq!(
+            crate::compile_subintent(manifest, 1, 100).unwrap(),
+            subintent
+        );
+
+        let mut tx = compile(
+            r#"
+TAKE_FROM_WORKTOP ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("xrd");
+YIELD_TO_SUBINTENT 0u32 Bucket("xrd");
+"#,
+            LOCAL_SIMULATOR_NETWORK_ID,
+        )
+        .unwrap();
+        let sk = EcdsaPrivateKey::from_bytes(&[1u8; 32]).unwrap();
+        let signed_subintent = subintent.sign(tx.intent_hash(), [&sk]);
+        tx.subintents.push(signed_subintent);
+
+        let manifest = &decompile(&tx, LOCAL_SIMULATOR_NETWORK_ID).unwrap();
+        let compiled = compile(manifest, LOCAL_SIMULATOR_NETWORK_ID).unwrap();
+        assert_eq!(compiled, tx);
+        assert!(compiled.sign([]).validate().is_ok());
     }
 }
@@ -4,7 +4,9 @@ use radix_engine::model::*;
 use sbor::any::{encode_any, Value};
 use sbor::type_id::*;
 use sbor::Encoder;
+use scrypto::crypto::{EcdsaPublicKey, EcdsaSignature};
 use scrypto::engine::types::*;
+use scrypto::resource::SchemaPath;
 use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::collections::HashMap;
 use scrypto::rust::str::FromStr;
@@ -26,11 +28,15 @@ pub enum GeneratorError {
     InvalidResourceAddress(String),
     InvalidDecimal(String),
     InvalidHash(String),
+    InvalidEcdsaPublicKey(String),
+    InvalidEcdsaSignature(Vec<u8>),
     InvalidLazyMapId(String),
     InvalidVaultId(String),
-    InvalidNonFungibleId(String),
     InvalidNonFungibleAddress(String),
+    InvalidSchemaPath(String),
+    InvalidAmountCondition(String),
     OddNumberOfElements(usize),
+    SubintentNotAllowed,
     NameResolverError(NameResolverError),
     IdValidatorError(IdValidatorError),
 }
@@ -96,20 +102,67 @@ impl NameResolver {
     }
 }
 
-pub fn generate_transaction(tx: &ast::Transaction) -> Result<Transaction, GeneratorError> {
+pub fn generate_transaction(
+    tx: &ast::Transaction,
+    network_id: u8,
+) -> Result<Transaction, GeneratorError> {
+    let mut subintents = Vec::new();
+    for subintent in &tx.subintents {
+        subintents.push(generate_signed_subintent(subintent)?);
+    }
+
+    Ok(Transaction {
+        network_id,
+        instructions: generate_instructions(&tx.instructions)?,
+        subintents,
+    })
+}
+
+pub fn generate_subintent(
+    tx: &ast::Transaction,
+    nonce: u64,
+    expiry_epoch: u64,
+) -> Result<Subintent, GeneratorError> {
+    if !tx.subintents.is_empty() {
+        return Err(GeneratorError::SubintentNotAllowed);
+    }
+
+    Ok(Subintent {
+        instructions: generate_instructions(&tx.instructions)?,
+        nonce,
+        expiry_epoch,
+    })
+}
+
+fn generate_signed_subintent(
+    subintent: &ast::Subintent,
+) -> Result<SignedSubintent, GeneratorError> {
+    Ok(SignedSubintent {
+        subintent: Subintent {
+            instructions: generate_instructions(&subintent.instructions)?,
+            nonce: generate_u64(&subintent.nonce)?,
+            expiry_epoch: generate_u64(&subintent.expiry_epoch)?,
+        },
+        signatures: generate_signatures(&subintent.signatures)?,
+    })
+}
+
+fn generate_instructions(
+    instructions: &[ast::Instruction],
+) -> Result<Vec<Instruction>, GeneratorError> {
     let mut id_validator = IdValidator::new();
     let mut name_resolver = NameResolver::new();
-    let mut instructions = Vec::new();
+    let mut result = Vec::new();
 
-    for instruction in &tx.instructions {
-        instructions.push(generate_instruction(
+    for instruction in instructions {
+        result.push(generate_instruction(
             instruction,
             &mut id_validator,
             &mut name_resolver,
         )?);
     }
 
-    Ok(Transaction { instructions })
+    Ok(result)
 }
 
 pub fn generate_instruction(
@@ -187,6 +240,80 @@ pub fn generate_instruction(
             ids: generate_non_fungible_ids(ids)?,
             resource_address: generate_resource_address(resource_address)?,
         },
+        ast::Instruction::AssertVaultBalance {
+            component_address,
+            path,
+            condition,
+        } => Instruction::AssertVaultBalance {
+            component_address: generate_component_address(component_address)?,
+            path: generate_schema_path(path)?,
+            condition: generate_amount_condition(condition)?,
+        },
+        ast::Instruction::AssertAccountBalance {
+            component_address,
+            resource_address,
+            condition,
+        } => Instruction::AssertAccountBalance {
+            component_address: generate_component_address(component_address)?,
+            resource_address: generate_resource_address(resource_address)?,
+            condition: generate_amount_condition(condition)?,
+        },
+        ast::Instruction::AssertResourceSupply {
+            resource_address,
+            condition,
+        } => Instruction::AssertResourceSupply {
+            resource_address: generate_resource_address(resource_address)?,
+            condition: generate_amount_condition(condition)?,
+        },
+        ast::Instruction::AssertComponentState {
+            component_address,
+            path,
+            value,
+        } => Instruction::AssertComponentState {
+            component_address: generate_component_address(component_address)?,
+            path: generate_schema_path(path)?,
+            value: generate_arg(value, resolver)?,
+        },
+        ast::Instruction::AssertLazyMapEntry {
+            component_address,
+            path,
+            key,
+            value,
+        } => Instruction::AssertLazyMapEntry {
+            component_address: generate_component_address(component_address)?,
+            path: generate_schema_path(path)?,
+            key: generate_arg(key, resolver)?,
+            value: generate_optional_arg(value, resolver)?,
+        },
+        ast::Instruction::RecallFromVault {
+            component_address,
+            path,
+            amount,
+            resource_address,
+        } => Instruction::RecallFromVault {
+            component_address: generate_component_address(component_address)?,
+            path: generate_schema_path(path)?,
+            amount: generate_decimal(amount)?,
+            resource_address: generate_resource_address(resource_address)?,
+        },
+        ast::Instruction::FreezeVault {
+            component_address,
+            path,
+            resource_address,
+        } => Instruction::FreezeVault {
+            component_address: generate_component_address(component_address)?,
+            path: generate_schema_path(path)?,
+            resource_address: generate_resource_address(resource_address)?,
+        },
+        ast::Instruction::UnfreezeVault {
+            component_address,
+            path,
+            resource_address,
+        } => Instruction::UnfreezeVault {
+            component_address: generate_component_address(component_address)?,
+            path: generate_schema_path(path)?,
+            resource_address: generate_resource_address(resource_address)?,
+        },
         ast::Instruction::PopFromAuthZone { new_proof } => {
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
@@ -328,6 +455,21 @@ pub fn generate_instruction(
         ast::Instruction::PublishPackage { code } => Instruction::PublishPackage {
             code: generate_bytes(code)?,
         },
+        ast::Instruction::CreateVirtualAccount { public_key } => {
+            Instruction::CreateVirtualAccount {
+                public_key: generate_ecdsa_public_key(public_key)?,
+            }
+        }
+        ast::Instruction::YieldToSubintent {
+            subintent_index,
+            buckets,
+        } => Instruction::YieldToSubintent {
+            subintent_index: generate_u32(subintent_index)?,
+            bucket_ids: generate_moved_buckets(buckets, id_validator, resolver)?,
+        },
+        ast::Instruction::ReturnToParent { buckets } => Instruction::ReturnToParent {
+            bucket_ids: generate_moved_buckets(buckets, id_validator, resolver)?,
+        },
     })
 }
 
@@ -347,16 +489,33 @@ fn generate_args(
 ) -> Result<Vec<Vec<u8>>, GeneratorError> {
     let mut result = Vec::new();
     for v in values {
-        let value = generate_value(v, None, resolver)?;
-
-        let mut bytes = Vec::new();
-        let mut enc = Encoder::with_type(&mut bytes);
-        encode_any(None, &value, &mut enc);
-        result.push(bytes);
+        result.push(generate_arg(v, resolver)?);
     }
     Ok(result)
 }
 
+fn generate_arg(value: &ast::Value, resolver: &mut NameResolver) -> Result<Vec<u8>, GeneratorError> {
+    let value = generate_value(value, None, resolver)?;
+
+    let mut bytes = Vec::new();
+    let mut enc = Encoder::with_type(&mut bytes);
+    encode_any(None, &value, &mut enc);
+    Ok(bytes)
+}
+
+fn generate_optional_arg(
+    value: &ast::Value,
+    resolver: &mut NameResolver,
+) -> Result<Option<Vec<u8>>, GeneratorError> {
+    match value {
+        ast::Value::Option(inner) => match &**inner {
+            Some(v) => generate_arg(v, resolver).map(Some),
+            None => Ok(None),
+        },
+        v @ _ => invalid_type!(v, ast::Type::Option),
+    }
+}
+
 fn generate_string(value: &ast::Value) -> Result<String, GeneratorError> {
     match value {
         ast::Value::String(s) => Ok(s.into()),
@@ -407,6 +566,87 @@ fn generate_decimal(value: &ast::Value) -> Result<Decimal, GeneratorError> {
     }
 }
 
+fn generate_u32(value: &ast::Value) -> Result<u32, GeneratorError> {
+    match value {
+        ast::Value::U32(n) => Ok(*n),
+        v @ _ => invalid_type!(v, ast::Type::U32),
+    }
+}
+
+fn generate_u64(value: &ast::Value) -> Result<u64, GeneratorError> {
+    match value {
+        ast::Value::U64(n) => Ok(*n),
+        v => invalid_type!(v, ast::Type::U64),
+    }
+}
+
+fn generate_signatures(
+    value: &ast::Value,
+) -> Result<Vec<(EcdsaPublicKey, EcdsaSignature)>, GeneratorError> {
+    match value {
+        ast::Value::Vec(ast::Type::Tuple, values) => {
+            let mut signatures = Vec::new();
+            for v in values {
+                match v {
+                    ast::Value::Tuple(fields) if fields.len() == 2 => {
+                        let public_key = generate_ecdsa_public_key(&fields[0])?;
+                        let bytes = generate_bytes(&fields[1])?;
+                        let signature = EcdsaSignature::try_from(bytes.as_slice())
+                            .map_err(|_| GeneratorError::InvalidEcdsaSignature(bytes))?;
+                        signatures.push((public_key, signature));
+                    }
+                    _ => return invalid_type!(v, ast::Type::Tuple),
+                }
+            }
+            Ok(signatures)
+        }
+        v => invalid_type!(v, ast::Type::Vec),
+    }
+}
+
+fn generate_moved_buckets(
+    values: &[ast::Value],
+    id_validator: &mut IdValidator,
+    resolver: &mut NameResolver,
+) -> Result<Vec<BucketId>, GeneratorError> {
+    let mut bucket_ids = Vec::new();
+    for value in values {
+        let bucket_id = generate_bucket(value, resolver)?;
+        id_validator
+            .drop_bucket(bucket_id)
+            .map_err(GeneratorError::IdValidatorError)?;
+        bucket_ids.push(bucket_id);
+    }
+    Ok(bucket_ids)
+}
+
+fn generate_schema_path(value: &ast::Value) -> Result<SchemaPath, GeneratorError> {
+    match value {
+        ast::Value::String(s) => {
+            SchemaPath::from_str(s).map_err(|_| GeneratorError::InvalidSchemaPath(s.into()))
+        }
+        v @ _ => invalid_type!(v, ast::Type::String),
+    }
+}
+
+fn generate_amount_condition(value: &ast::Value) -> Result<AmountCondition, GeneratorError> {
+    match value {
+        ast::Value::Enum(name, fields) => {
+            let amount = match fields.as_slice() {
+                [amount] => generate_decimal(amount)?,
+                _ => return Err(GeneratorError::InvalidAmountCondition(name.clone())),
+            };
+            match name.as_str() {
+                "Exactly" => Ok(AmountCondition::Exactly(amount)),
+                "AtLeast" => Ok(AmountCondition::AtLeast(amount)),
+                "AtMost" => Ok(AmountCondition::AtMost(amount)),
+                _ => Err(GeneratorError::InvalidAmountCondition(name.clone())),
+            }
+        }
+        v @ _ => invalid_type!(v, ast::Type::Enum),
+    }
+}
+
 fn generate_package_address(value: &ast::Value) -> Result<PackageAddress, GeneratorError> {
     match value {
         ast::Value::PackageAddress(inner) => match &**inner {
@@ -452,6 +692,17 @@ fn generate_hash(value: &ast::Value) -> Result<Hash, GeneratorError> {
     }
 }
 
+fn generate_ecdsa_public_key(value: &ast::Value) -> Result<EcdsaPublicKey, GeneratorError> {
+    match value {
+        ast::Value::EcdsaPublicKey(inner) => match &**inner {
+            ast::Value::String(s) => EcdsaPublicKey::from_str(s)
+                .map_err(|_| GeneratorError::InvalidEcdsaPublicKey(s.into())),
+            v => invalid_type!(v, ast::Type::String),
+        },
+        v => invalid_type!(v, ast::Type::EcdsaPublicKey),
+    }
+}
+
 fn declare_bucket(
     value: &ast::Value,
     resolver: &mut NameResolver,
@@ -519,9 +770,19 @@ fn generate_proof(
 fn generate_non_fungible_id(value: &ast::Value) -> Result<NonFungibleId, GeneratorError> {
     match value {
         ast::Value::NonFungibleId(inner) => match &**inner {
-            ast::Value::String(s) => NonFungibleId::from_str(s)
-                .map_err(|_| GeneratorError::InvalidNonFungibleId(s.into())),
-            v @ _ => invalid_type!(v, ast::Type::String),
+            ast::Value::U32(u) => Ok(NonFungibleId::from_u32(*u)),
+            ast::Value::U64(u) => Ok(NonFungibleId::from_u64(*u)),
+            ast::Value::U128(u) => Ok(NonFungibleId::from_uuid(*u)),
+            ast::Value::String(s) => Ok(NonFungibleId::from_string(s)),
+            ast::Value::Bytes(bytes) => Ok(NonFungibleId::from_bytes(bytes.clone())),
+            v @ _ => invalid_type!(
+                v,
+                ast::Type::U32,
+                ast::Type::U64,
+                ast::Type::U128,
+                ast::Type::String,
+                ast::Type::Bytes
+            ),
         },
         v @ _ => invalid_type!(v, ast::Type::NonFungibleId),
     }
@@ -676,6 +937,10 @@ fn generate_value(
                 bytes: v.to_vec(),
             })
         }
+        ast::Value::EcdsaPublicKey(_) => generate_ecdsa_public_key(value).map(|v| Value::Custom {
+            type_id: ScryptoType::EcdsaPublicKey.id(),
+            bytes: v.to_vec(),
+        }),
         ast::Value::Bytes(_) => match value {
             ast::Value::Bytes(bytes) => {
                 let mut elements = Vec::new();
@@ -760,6 +1025,7 @@ fn generate_type_id(ty: &ast::Type) -> u8 {
         ast::Type::Proof => ScryptoType::Proof.id(),
         ast::Type::NonFungibleId => ScryptoType::NonFungibleId.id(),
         ast::Type::NonFungibleAddress => ScryptoType::NonFungibleAddress.id(),
+        ast::Type::EcdsaPublicKey => ScryptoType::EcdsaPublicKey.id(),
         ast::Type::Bytes => TYPE_VEC,
     }
 }
@@ -957,6 +1223,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_non_fungible_id() {
+        for (s, id) in [
+            (r#"NonFungibleId(1u32)"#, NonFungibleId::from_u32(1)),
+            (r#"NonFungibleId(1u64)"#, NonFungibleId::from_u64(1)),
+            (r#"NonFungibleId(1u128)"#, NonFungibleId::from_uuid(1)),
+            (r#"NonFungibleId("car")"#, NonFungibleId::from_string("car")),
+            (
+                r#"NonFungibleId("a \"b\" \\c")"#,
+                NonFungibleId::from_string(r#"a "b" \c"#),
+            ),
+            (
+                r#"NonFungibleId(Bytes("3575"))"#,
+                NonFungibleId::from_bytes(vec![53u8, 117u8]),
+            ),
+        ] {
+            generate_value_ok!(
+                s,
+                Value::Custom {
+                    type_id: ScryptoType::NonFungibleId.id(),
+                    bytes: id.to_vec()
+                }
+            );
+            assert_eq!(format!("NonFungibleId({})", id.to_natural_string()), s);
+        }
+        generate_value_error!(
+            r#"NonFungibleId(1u8)"#,
+            GeneratorError::InvalidValue {
+                expected_type: vec![
+                    ast::Type::U32,
+                    ast::Type::U64,
+                    ast::Type::U128,
+                    ast::Type::String,
+                    ast::Type::Bytes
+                ],
+                actual: ast::Value::U8(1),
+            }
+        );
+    }
+
     #[test]
     fn test_instructions() {
         generate_instruction_ok!(
@@ -1026,6 +1332,210 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_assertion_instructions() {
+        generate_instruction_ok!(
+            r#"ASSERT_VAULT_BALANCE  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "vaults/0"  Enum("AtLeast", Decimal("10"));"#,
+            Instruction::AssertVaultBalance {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("vaults/0").unwrap(),
+                condition: AmountCondition::AtLeast(Decimal::from(10)),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_ACCOUNT_BALANCE  ComponentAddress("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de")  ResourceAddress("030000000000000000000000000000000000000000000000000004")  Enum("Exactly", Decimal("5.0"));"#,
+            Instruction::AssertAccountBalance {
+                component_address: ComponentAddress::from_str(
+                    "02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de".into()
+                )
+                .unwrap(),
+                resource_address: ResourceAddress::from_str(
+                    "030000000000000000000000000000000000000000000000000004"
+                )
+                .unwrap(),
+                condition: AmountCondition::Exactly(Decimal::from(5)),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_RESOURCE_SUPPLY  ResourceAddress("030000000000000000000000000000000000000000000000000004")  Enum("AtMost", Decimal("1000"));"#,
+            Instruction::AssertResourceSupply {
+                resource_address: ResourceAddress::from_str(
+                    "030000000000000000000000000000000000000000000000000004"
+                )
+                .unwrap(),
+                condition: AmountCondition::AtMost(Decimal::from(1000)),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_COMPONENT_STATE  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "count"  5u32;"#,
+            Instruction::AssertComponentState {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("count").unwrap(),
+                value: scrypto_encode(&5u32),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_LAZY_MAP_ENTRY  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "names"  1u32  Some("hello");"#,
+            Instruction::AssertLazyMapEntry {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("names").unwrap(),
+                key: scrypto_encode(&1u32),
+                value: Some(scrypto_encode("hello")),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_LAZY_MAP_ENTRY  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "names"  2u32  None;"#,
+            Instruction::AssertLazyMapEntry {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("names").unwrap(),
+                key: scrypto_encode(&2u32),
+                value: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_recall_and_freeze_instructions() {
+        generate_instruction_ok!(
+            r#"RECALL_FROM_VAULT  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "vaults/0"  Decimal("10")  ResourceAddress("030000000000000000000000000000000000000000000000000004");"#,
+            Instruction::RecallFromVault {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("vaults/0").unwrap(),
+                amount: Decimal::from(10),
+                resource_address: ResourceAddress::from_str(
+                    "030000000000000000000000000000000000000000000000000004"
+                )
+                .unwrap(),
+            }
+        );
+        generate_instruction_ok!(
+            r#"FREEZE_VAULT  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "vaults/0"  ResourceAddress("030000000000000000000000000000000000000000000000000004");"#,
+            Instruction::FreezeVault {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("vaults/0").unwrap(),
+                resource_address: ResourceAddress::from_str(
+                    "030000000000000000000000000000000000000000000000000004"
+                )
+                .unwrap(),
+            }
+        );
+        generate_instruction_ok!(
+            r#"UNFREEZE_VAULT  ComponentAddress("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1")  "vaults/0"  ResourceAddress("030000000000000000000000000000000000000000000000000004");"#,
+            Instruction::UnfreezeVault {
+                component_address: ComponentAddress::from_str(
+                    "0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1".into()
+                )
+                .unwrap(),
+                path: SchemaPath::from_str("vaults/0").unwrap(),
+                resource_address: ResourceAddress::from_str(
+                    "030000000000000000000000000000000000000000000000000004"
+                )
+                .unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_create_virtual_account_instruction() {
+        generate_instruction_ok!(
+            r#"CREATE_VIRTUAL_ACCOUNT  EcdsaPublicKey("046b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c2964fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");"#,
+            Instruction::CreateVirtualAccount {
+                public_key: EcdsaPublicKey::from_str(
+                    "046b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c2964fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"
+                )
+                .unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_amount_condition() {
+        let instruction = Parser::new(
+            tokenize(r#"ASSERT_RESOURCE_SUPPLY ResourceAddress("030000000000000000000000000000000000000000000000000004") Enum("AtLeastOne", Decimal("1"));"#)
+                .unwrap(),
+        )
+        .parse_instruction()
+        .unwrap();
+        assert_eq!(
+            generate_instruction(
+                &instruction,
+                &mut IdValidator::new(),
+                &mut NameResolver::new()
+            ),
+            Err(GeneratorError::InvalidAmountCondition("AtLeastOne".into()))
+        );
+    }
+
+    #[test]
+    fn test_subintent() {
+        let subintent = crate::compile_subintent(
+            r#"
+CALL_METHOD ComponentAddress("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de") "withdraw" ResourceAddress("030000000000000000000000000000000000000000000000000004");
+TAKE_FROM_WORKTOP ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("xrd");
+RETURN_TO_PARENT Bucket("xrd");
+"#,
+            1,
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(
+            subintent.instructions[2],
+            Instruction::ReturnToParent {
+                bucket_ids: vec![512],
+            }
+        );
+
+        let tx = crate::compile(
+            r#"
+SUBINTENT 1u64 100u64 Vec<Tuple>() {
+    TAKE_FROM_WORKTOP ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("xrd");
+    RETURN_TO_PARENT Bucket("xrd");
+}
+TAKE_FROM_WORKTOP ResourceAddress("030000000000000000000000000000000000000000000000000004") Bucket("xrd");
+YIELD_TO_SUBINTENT 0u32 Bucket("xrd");
+"#,
+            LOCAL_SIMULATOR_NETWORK_ID,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tx.instructions[1],
+            Instruction::YieldToSubintent {
+                subintent_index: 0,
+                bucket_ids: vec![512],
+            }
+        );
+        assert_eq!(tx.subintents.len(), 1);
+        assert_eq!(tx.subintents[0].subintent.nonce, 1);
+        assert_eq!(tx.subintents[0].subintent.expiry_epoch, 100);
+        assert_eq!(
+            tx.subintents[0].subintent.instructions[1],
+            Instruction::ReturnToParent {
+                bucket_ids: vec![512],
+            }
+        );
+        assert!(tx.subintents[0].signatures.is_empty());
+    }
+
     #[test]
     fn test_transaction() {
         let tx = include_str!("../examples/complex.rtm");
@@ -1047,8 +1557,10 @@ mod tests {
         ];
 
         assert_eq!(
-            crate::compile(tx).unwrap(),
+            crate::compile(tx, LOCAL_SIMULATOR_NETWORK_ID).unwrap(),
             Transaction {
+                network_id: LOCAL_SIMULATOR_NETWORK_ID,
+                subintents: Vec::new(),
                 instructions: vec![
                     Instruction::CallMethod {
                         component_address: ComponentAddress::from_str(
@@ -1125,8 +1637,8 @@ mod tests {
                     Instruction::ReturnToWorktop { bucket_id: 513 },
                     Instruction::TakeFromWorktopByIds {
                         ids: BTreeSet::from([
-                            NonFungibleId::from_str("11").unwrap(),
-                            NonFungibleId::from_str("22").unwrap(),
+                            NonFungibleId::from_u32(11),
+                            NonFungibleId::from_u32(22),
                         ]),
                         resource_address: ResourceAddress::from_str(
                             "030000000000000000000000000000000000000000000000000004"
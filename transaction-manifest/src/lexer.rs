@@ -59,6 +59,7 @@ pub enum TokenKind {
     Proof,
     NonFungibleId,
     NonFungibleAddress,
+    EcdsaPublicKey,
 
     /* Sub-types */
     Some,
@@ -69,6 +70,8 @@ pub enum TokenKind {
     /* Punctuations */
     OpenParenthesis,
     CloseParenthesis,
+    OpenCurlyBrace,
+    CloseCurlyBrace,
     LessThan,
     GreaterThan,
     Comma,
@@ -85,6 +88,14 @@ pub enum TokenKind {
     AssertWorktopContains,
     AssertWorktopContainsByAmount,
     AssertWorktopContainsByIds,
+    AssertVaultBalance,
+    AssertAccountBalance,
+    AssertResourceSupply,
+    AssertComponentState,
+    AssertLazyMapEntry,
+    RecallFromVault,
+    FreezeVault,
+    UnfreezeVault,
     PopFromAuthZone,
     PushToAuthZone,
     ClearAuthZone,
@@ -98,6 +109,12 @@ pub enum TokenKind {
     CallMethod,
     CallMethodWithAllResources,
     PublishPackage,
+    CreateVirtualAccount,
+    YieldToSubintent,
+    ReturnToParent,
+
+    /* Subintent blocks */
+    Subintent,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -389,6 +406,7 @@ impl Lexer {
             "Proof" => Ok(TokenKind::Proof),
             "NonFungibleId" => Ok(TokenKind::NonFungibleId),
             "NonFungibleAddress" => Ok(TokenKind::NonFungibleAddress),
+            "EcdsaPublicKey" => Ok(TokenKind::EcdsaPublicKey),
 
             "Some" => Ok(TokenKind::Some),
             "None" => Ok(TokenKind::None),
@@ -404,6 +422,14 @@ impl Lexer {
             "ASSERT_WORKTOP_CONTAINS" => Ok(TokenKind::AssertWorktopContains),
             "ASSERT_WORKTOP_CONTAINS_BY_AMOUNT" => Ok(TokenKind::AssertWorktopContainsByAmount),
             "ASSERT_WORKTOP_CONTAINS_BY_IDS" => Ok(TokenKind::AssertWorktopContainsByIds),
+            "ASSERT_VAULT_BALANCE" => Ok(TokenKind::AssertVaultBalance),
+            "ASSERT_ACCOUNT_BALANCE" => Ok(TokenKind::AssertAccountBalance),
+            "ASSERT_RESOURCE_SUPPLY" => Ok(TokenKind::AssertResourceSupply),
+            "ASSERT_COMPONENT_STATE" => Ok(TokenKind::AssertComponentState),
+            "ASSERT_LAZY_MAP_ENTRY" => Ok(TokenKind::AssertLazyMapEntry),
+            "RECALL_FROM_VAULT" => Ok(TokenKind::RecallFromVault),
+            "FREEZE_VAULT" => Ok(TokenKind::FreezeVault),
+            "UNFREEZE_VAULT" => Ok(TokenKind::UnfreezeVault),
             "POP_FROM_AUTH_ZONE" => Ok(TokenKind::PopFromAuthZone),
             "PUSH_TO_AUTH_ZONE" => Ok(TokenKind::PushToAuthZone),
             "CLEAR_AUTH_ZONE" => Ok(TokenKind::ClearAuthZone),
@@ -419,6 +445,11 @@ impl Lexer {
             "CALL_METHOD" => Ok(TokenKind::CallMethod),
             "CALL_METHOD_WITH_ALL_RESOURCES" => Ok(TokenKind::CallMethodWithAllResources),
             "PUBLISH_PACKAGE" => Ok(TokenKind::PublishPackage),
+            "CREATE_VIRTUAL_ACCOUNT" => Ok(TokenKind::CreateVirtualAccount),
+            "YIELD_TO_SUBINTENT" => Ok(TokenKind::YieldToSubintent),
+            "RETURN_TO_PARENT" => Ok(TokenKind::ReturnToParent),
+
+            "SUBINTENT" => Ok(TokenKind::Subintent),
 
             s @ _ => Err(LexerError::UnknownIdentifier(s.into())),
         }
@@ -431,6 +462,8 @@ impl Lexer {
         let token_kind = match self.advance()? {
             '(' => TokenKind::OpenParenthesis,
             ')' => TokenKind::CloseParenthesis,
+            '{' => TokenKind::OpenCurlyBrace,
+            '}' => TokenKind::CloseCurlyBrace,
             '<' => TokenKind::LessThan,
             '>' => TokenKind::GreaterThan,
             ',' => TokenKind::Comma,
@@ -4,9 +4,9 @@ pub mod generator;
 pub mod lexer;
 pub mod parser;
 
-pub use decompiler::{decompile, DecompileError};
+pub use decompiler::{decompile, decompile_subintent, DecompileError};
 
-use radix_engine::model::Transaction;
+use radix_engine::model::{Subintent, Transaction};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompileError {
@@ -15,10 +15,23 @@ pub enum CompileError {
     GeneratorError(generator::GeneratorError),
 }
 
-pub fn compile(s: &str) -> Result<Transaction, CompileError> {
+/// Compiles a manifest into a transaction intended for the given network.
+pub fn compile(s: &str, network_id: u8) -> Result<Transaction, CompileError> {
     let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
     let ast = parser::Parser::new(tokens)
         .parse_transaction()
         .map_err(CompileError::ParserError)?;
-    generator::generate_transaction(&ast).map_err(CompileError::GeneratorError)
+    generator::generate_transaction(&ast, network_id).map_err(CompileError::GeneratorError)
+}
+
+pub fn compile_subintent(
+    s: &str,
+    nonce: u64,
+    expiry_epoch: u64,
+) -> Result<Subintent, CompileError> {
+    let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
+    let ast = parser::Parser::new(tokens)
+        .parse_transaction()
+        .map_err(CompileError::ParserError)?;
+    generator::generate_subintent(&ast, nonce, expiry_epoch).map_err(CompileError::GeneratorError)
 }
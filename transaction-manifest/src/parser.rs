@@ -1,4 +1,4 @@
-use crate::ast::{Instruction, Transaction, Type, Value};
+use crate::ast::{Instruction, Subintent, Transaction, Type, Value};
 use crate::lexer::{Token, TokenKind};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,12 +58,41 @@ impl Parser {
 
     pub fn parse_transaction(&mut self) -> Result<Transaction, ParserError> {
         let mut instructions = Vec::<Instruction>::new();
+        let mut subintents = Vec::<Subintent>::new();
 
         while !self.is_eof() {
+            if self.peek()?.kind == TokenKind::Subintent {
+                subintents.push(self.parse_subintent()?);
+            } else {
+                instructions.push(self.parse_instruction()?);
+            }
+        }
+
+        Ok(Transaction {
+            instructions,
+            subintents,
+        })
+    }
+
+    pub fn parse_subintent(&mut self) -> Result<Subintent, ParserError> {
+        advance_match!(self, TokenKind::Subintent);
+        let nonce = self.parse_value()?;
+        let expiry_epoch = self.parse_value()?;
+        let signatures = self.parse_value()?;
+
+        let mut instructions = Vec::<Instruction>::new();
+        advance_match!(self, TokenKind::OpenCurlyBrace);
+        while self.peek()?.kind != TokenKind::CloseCurlyBrace {
             instructions.push(self.parse_instruction()?);
         }
+        advance_match!(self, TokenKind::CloseCurlyBrace);
 
-        Ok(Transaction { instructions })
+        Ok(Subintent {
+            nonce,
+            expiry_epoch,
+            signatures,
+            instructions,
+        })
     }
 
     pub fn parse_instruction(&mut self) -> Result<Instruction, ParserError> {
@@ -99,6 +128,47 @@ impl Parser {
                 ids: self.parse_value()?,
                 resource_address: self.parse_value()?,
             },
+            TokenKind::AssertVaultBalance => Instruction::AssertVaultBalance {
+                component_address: self.parse_value()?,
+                path: self.parse_value()?,
+                condition: self.parse_value()?,
+            },
+            TokenKind::AssertAccountBalance => Instruction::AssertAccountBalance {
+                component_address: self.parse_value()?,
+                resource_address: self.parse_value()?,
+                condition: self.parse_value()?,
+            },
+            TokenKind::AssertResourceSupply => Instruction::AssertResourceSupply {
+                resource_address: self.parse_value()?,
+                condition: self.parse_value()?,
+            },
+            TokenKind::AssertComponentState => Instruction::AssertComponentState {
+                component_address: self.parse_value()?,
+                path: self.parse_value()?,
+                value: self.parse_value()?,
+            },
+            TokenKind::AssertLazyMapEntry => Instruction::AssertLazyMapEntry {
+                component_address: self.parse_value()?,
+                path: self.parse_value()?,
+                key: self.parse_value()?,
+                value: self.parse_value()?,
+            },
+            TokenKind::RecallFromVault => Instruction::RecallFromVault {
+                component_address: self.parse_value()?,
+                path: self.parse_value()?,
+                amount: self.parse_value()?,
+                resource_address: self.parse_value()?,
+            },
+            TokenKind::FreezeVault => Instruction::FreezeVault {
+                component_address: self.parse_value()?,
+                path: self.parse_value()?,
+                resource_address: self.parse_value()?,
+            },
+            TokenKind::UnfreezeVault => Instruction::UnfreezeVault {
+                component_address: self.parse_value()?,
+                path: self.parse_value()?,
+                resource_address: self.parse_value()?,
+            },
             TokenKind::PopFromAuthZone => Instruction::PopFromAuthZone {
                 new_proof: self.parse_value()?,
             },
@@ -163,6 +233,28 @@ impl Parser {
             TokenKind::PublishPackage => Instruction::PublishPackage {
                 code: self.parse_value()?,
             },
+            TokenKind::CreateVirtualAccount => Instruction::CreateVirtualAccount {
+                public_key: self.parse_value()?,
+            },
+            TokenKind::YieldToSubintent => Instruction::YieldToSubintent {
+                subintent_index: self.parse_value()?,
+                buckets: {
+                    let mut values = vec![];
+                    while self.peek()?.kind != TokenKind::Semicolon {
+                        values.push(self.parse_value()?);
+                    }
+                    values
+                },
+            },
+            TokenKind::ReturnToParent => Instruction::ReturnToParent {
+                buckets: {
+                    let mut values = vec![];
+                    while self.peek()?.kind != TokenKind::Semicolon {
+                        values.push(self.parse_value()?);
+                    }
+                    values
+                },
+            },
             _ => {
                 return Err(ParserError::UnexpectedToken(token));
             }
@@ -210,7 +302,8 @@ impl Parser {
             | TokenKind::Bucket
             | TokenKind::Proof
             | TokenKind::NonFungibleId
-            | TokenKind::NonFungibleAddress => self.parse_scrypto_types(),
+            | TokenKind::NonFungibleAddress
+            | TokenKind::EcdsaPublicKey => self.parse_scrypto_types(),
             TokenKind::Bytes => self.parse_bytes(),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
@@ -352,6 +445,7 @@ impl Parser {
             TokenKind::NonFungibleAddress => {
                 Ok(Value::NonFungibleAddress(self.parse_values_one()?.into()))
             }
+            TokenKind::EcdsaPublicKey => Ok(Value::EcdsaPublicKey(self.parse_values_one()?.into())),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }
@@ -443,6 +537,7 @@ impl Parser {
             TokenKind::Bucket => Ok(Type::Bucket),
             TokenKind::Proof => Ok(Type::Proof),
             TokenKind::NonFungibleId => Ok(Type::NonFungibleId),
+            TokenKind::EcdsaPublicKey => Ok(Type::EcdsaPublicKey),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }